@@ -0,0 +1,44 @@
+//! Circuit construction time for the algorithm builders.
+//!
+//! Run with: `cargo bench -p homaya-algorithms`
+//!
+//! Only building is timed, not simulation. Gate counts are pinned by the
+//! unit tests; this tracks how long producing them takes as n grows.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use homaya_algorithms::{bernstein_vazirani, deutsch::FunctionType, DeutschJozsa, GroverSearch};
+
+fn grover_build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("grover_build");
+    // Each iteration is linear in n, but there are O(√N) of them
+    for n in [2, 4, 8, 12, 16] {
+        let grover = GroverSearch::new(n, (1 << n) - 1);
+        group.bench_with_input(BenchmarkId::from_parameter(n), &grover, |b, grover| {
+            b.iter(|| black_box(grover.build()))
+        });
+    }
+    group.finish();
+}
+
+fn bernstein_vazirani_build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bernstein_vazirani_find_secret");
+    for n in [4, 8, 16, 32, 63] {
+        let secret = usize::MAX >> (usize::BITS as usize - n);
+        group.bench_with_input(BenchmarkId::from_parameter(n), &n, |b, &n| {
+            b.iter(|| black_box(bernstein_vazirani::find_secret(n, black_box(secret))))
+        });
+    }
+    group.finish();
+}
+
+fn deutsch_jozsa_build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deutsch_jozsa_build");
+    for n in [4, 8, 16, 32, 63] {
+        let dj = DeutschJozsa::new(n, FunctionType::BalancedParity);
+        group.bench_with_input(BenchmarkId::from_parameter(n), &dj, |b, dj| b.iter(|| black_box(dj.build())));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, grover_build, bernstein_vazirani_build, deutsch_jozsa_build);
+criterion_main!(benches);
@@ -0,0 +1,121 @@
+//! # Amplitude Amplification
+//!
+//! Boost the probability of "good" states of any state preparation.
+//!
+//! ## The Idea
+//!
+//! Grover's search starts from the uniform superposition H⊗ⁿ|0⟩. Amplitude
+//! amplification starts from whatever A|0⟩ a circuit A prepares:
+//!
+//! A|0⟩ = √a |good⟩ + √(1-a) |bad⟩
+//!
+//! Each iteration applies the oracle S_χ (flip the sign of good states) and
+//! then reflects about A|0⟩ itself:
+//!
+//! A · (2|0⟩⟨0| - I) · A†
+//!
+//! Together they rotate the state by 2θ towards |good⟩, where a = sin²θ, so
+//! after k iterations P(good) = sin²((2k + 1)θ). With A = H⊗ⁿ this is
+//! exactly Grover's search.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use homaya_algorithms::amplification::amplitude_amplification;
+//! use homaya_core::Circuit;
+//!
+//! // Ry(0.5)|0⟩ is mostly |0⟩; the oracle marks |1⟩
+//! let prep = Circuit::new(1).ry(0.5, 0);
+//! let oracle = Circuit::new(1).z(0);
+//! let circuit = amplitude_amplification(&prep, &oracle, 1);
+//! ```
+
+use homaya_core::Circuit;
+
+use crate::grover::{reflect_about_zero_on, ORACLE_BLOCK};
+
+/// Apply `state_prep`, then `iterations` rounds of oracle and reflection.
+///
+/// Each oracle call is recorded as an [`ORACLE_BLOCK`] block, as in
+/// [`GroverSearch::build`](crate::GroverSearch::build). The circuit is left
+/// unmeasured so it can be composed further.
+///
+/// # Panics
+///
+/// Panics if `oracle` and `state_prep` act on different numbers of qubits.
+pub fn amplitude_amplification(state_prep: &Circuit, oracle: &Circuit, iterations: usize) -> Circuit {
+    assert_eq!(
+        oracle.num_qubits(),
+        state_prep.num_qubits(),
+        "oracle and state preparation must act on the same qubits"
+    );
+
+    let reflection = reflect_about_prepared(state_prep);
+    let mut circuit = state_prep.clone();
+    for _ in 0..iterations {
+        circuit = circuit
+            .compose_block(ORACLE_BLOCK, oracle)
+            .expect("oracle has the preparation's width")
+            .append(&reflection);
+    }
+    circuit
+}
+
+/// Reflect about the prepared state A|0⟩: A · (2|0⟩⟨0| - I) · A†.
+///
+/// Correct up to a global phase. Uses [`Circuit::inverse`] for A†, so the
+/// preparation must be unitary.
+pub fn reflect_about_prepared(state_prep: &Circuit) -> Circuit {
+    reflect_about_prepared_on(state_prep, state_prep.num_qubits())
+}
+
+/// [`reflect_about_prepared`] about the first `n_qubits` qubits only.
+///
+/// The qubits past them are clean ancillas for the multi-controlled Z;
+/// `state_prep` must leave them alone.
+pub(crate) fn reflect_about_prepared_on(state_prep: &Circuit, n_qubits: usize) -> Circuit {
+    let qubits: Vec<usize> = (0..n_qubits).collect();
+    let ancillas: Vec<usize> = (n_qubits..state_prep.num_qubits()).collect();
+    reflect_about_zero_on(state_prep.inverse(), &qubits, &ancillas).append(state_prep)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GroverSearch;
+    use homaya_sim::Simulator;
+
+    #[test]
+    fn test_uniform_prep_is_grover() {
+        let grover = GroverSearch::new(3, 5);
+        let uniform = Circuit::new(3).h(0).h(1).h(2);
+        let circuit = amplitude_amplification(&uniform, &grover.oracle_circuit(), grover.oracle_call_count());
+
+        let state = Simulator::new().run(&circuit).unwrap();
+        assert!((state.probability(5) - grover.success_probability()).abs() < 1e-10);
+        assert_eq!(circuit.count_blocks(ORACLE_BLOCK), grover.oracle_call_count());
+
+        // Same gates as Grover's own circuit, apart from measurement
+        assert_eq!(circuit.gate_count(), grover.build().gate_count());
+    }
+
+    #[test]
+    fn test_amplifies_non_uniform_prep() {
+        // a = sin²(0.2), far from a uniform superposition
+        let theta: f64 = 0.2;
+        let prep = Circuit::new(2).ry(2.0 * theta, 0).cx(0, 1);
+        let oracle = Circuit::new(2).cz(0, 1);
+
+        for k in 0..4 {
+            let state = Simulator::new().run(&amplitude_amplification(&prep, &oracle, k)).unwrap();
+            let expected = ((2 * k + 1) as f64 * theta).sin().powi(2);
+            assert!((state.probability(0b11) - expected).abs() < 1e-10, "k = {}", k);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "same qubits")]
+    fn test_width_mismatch() {
+        amplitude_amplification(&Circuit::new(2), &Circuit::new(3), 1);
+    }
+}
@@ -0,0 +1,368 @@
+//! # Amplitude Estimation
+//!
+//! Estimate how likely a state preparation is to land on a "good" state.
+//!
+//! ## The Problem
+//!
+//! A circuit A prepares
+//!
+//! A|0⟩ = √a |good⟩ + √(1-a) |bad⟩
+//!
+//! and we want to learn a. Measuring A|0⟩ directly needs O(1/ε²) shots
+//! for precision ε; amplitude estimation gets there with O(1/ε) oracle calls.
+//!
+//! ## How It Works
+//!
+//! Write a = sin²θ. The Grover operator
+//!
+//! Q = A · S₀ · A† · S_χ
+//!
+//! (S_χ flips the sign of good states, S₀ flips the sign of |0⟩) rotates the
+//! state by 2θ in the plane spanned by |good⟩ and |bad⟩. After k applications:
+//!
+//! P(good) = sin²((2k + 1)θ)
+//!
+//! Watching how P(good) oscillates with k pins θ down far more sharply than
+//! looking at A|0⟩ alone.
+//!
+//! ## The Catch
+//!
+//! The reflection must be about the *prepared* state A|0⟩, not the uniform
+//! superposition. The two only coincide when A = H⊗ⁿ, which is plain Grover.
+//! With any other preparation the uniform diffuser rotates in the wrong plane
+//! and the estimate comes out wrong.
+//!
+//! No phase-estimation register is used. [`AmplitudeEstimation::estimate_sampled`]
+//! measures Q^k A|0⟩ for k = 0..=K and picks the θ that maximizes the
+//! likelihood of the observed good counts (maximum-likelihood amplitude
+//! estimation); [`AmplitudeEstimation::estimate_from_counts`] does the same
+//! for histograms from elsewhere, e.g. real hardware.
+//! [`AmplitudeEstimation::estimate`] instead fits θ to the simulator's exact
+//! probabilities, a reference with no shot noise.
+
+use std::collections::HashMap;
+
+use homaya_core::{parse_outcome, Circuit, Result, PI};
+use homaya_sim::Simulator;
+
+use crate::amplification::reflect_about_prepared;
+use crate::grover::multi_controlled_z;
+
+/// Amplitude estimation builder.
+///
+/// Estimates the probability that `state_prep` produces one of the
+/// `good_states` basis states.
+#[derive(Debug, Clone)]
+pub struct AmplitudeEstimation {
+    /// The state preparation A
+    state_prep: Circuit,
+    /// Basis states counted as "good"
+    good_states: Vec<usize>,
+    /// Largest number of Grover operator applications to sample
+    max_power: usize,
+}
+
+impl AmplitudeEstimation {
+    /// Create a new amplitude estimation instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `state_prep` - Circuit A preparing the state from |0...0⟩
+    /// * `good_states` - Basis states whose total probability is estimated
+    ///
+    /// # Panics
+    ///
+    /// Panics if a good state doesn't fit in the preparation's qubits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::AmplitudeEstimation;
+    /// use homaya_core::Circuit;
+    ///
+    /// // Ry(0.6)|0⟩ has P(|1⟩) = sin²(0.3)
+    /// let ae = AmplitudeEstimation::new(Circuit::new(1).ry(0.6, 0), &[1]);
+    /// let a = ae.estimate().unwrap();
+    /// assert!((a - 0.3f64.sin().powi(2)).abs() < 1e-3);
+    /// ```
+    pub fn new(state_prep: Circuit, good_states: &[usize]) -> Self {
+        let max_state = 1 << state_prep.num_qubits();
+        for &state in good_states {
+            assert!(
+                state < max_state,
+                "Good state {} is too large for {} qubits (max: {})",
+                state,
+                state_prep.num_qubits(),
+                max_state - 1
+            );
+        }
+
+        Self {
+            state_prep,
+            good_states: good_states.to_vec(),
+            max_power: 4,
+        }
+    }
+
+    /// Set the largest number of Grover operator applications to sample.
+    ///
+    /// More powers sharpen the estimate. Default is 4.
+    pub fn with_max_power(mut self, max_power: usize) -> Self {
+        self.max_power = max_power;
+        self
+    }
+
+    /// Build the oracle S_χ that flips the sign of every good state.
+    pub fn oracle(&self) -> Circuit {
+        let n_qubits = self.state_prep.num_qubits();
+        let mut circuit = Circuit::new(n_qubits);
+
+        for &state in &self.good_states {
+            // Map |state⟩ → |11...1⟩, flip its sign, then map back
+            for i in 0..n_qubits {
+                if (state >> i) & 1 == 0 {
+                    circuit = circuit.x(i);
+                }
+            }
+            circuit = multi_controlled_z(circuit, n_qubits);
+            for i in 0..n_qubits {
+                if (state >> i) & 1 == 0 {
+                    circuit = circuit.x(i);
+                }
+            }
+        }
+
+        circuit
+    }
+
+    /// Build the Grover operator Q = A · S₀ · A† · S_χ.
+    ///
+    /// The reflection is about the prepared state A|0⟩, so it matches
+    /// whatever `state_prep` was supplied.
+    pub fn grover_operator(&self) -> Circuit {
+        // Read right to left: S_χ first, then A†, S₀ and finally A
+        self.oracle().append(&reflect_about_prepared(&self.state_prep))
+    }
+
+    /// Build A followed by `k` applications of the Grover operator.
+    pub fn build(&self, k: usize) -> Circuit {
+        let grover_operator = self.grover_operator();
+        let mut circuit = self.state_prep.clone();
+
+        for _ in 0..k {
+            circuit = circuit
+                .compose(&grover_operator)
+                .expect("all blocks share the preparation's width");
+        }
+
+        circuit
+    }
+
+    /// Simulate Q^k A|0⟩ and return the exact probability of a good state.
+    pub fn good_probability(&self, k: usize) -> Result<f64> {
+        let state = Simulator::new().run(&self.build(k))?;
+        Ok(self.good_states.iter().map(|&s| state.probability(s)).sum())
+    }
+
+    /// Fit the good-state probability a to exact simulated probabilities.
+    ///
+    /// Simulates k = 0..=max_power Grover operator applications and fits
+    /// θ to P(good) = sin²((2k + 1)θ) by least squares, returning a = sin²θ.
+    /// This is an exact fit, not an estimate from measurements: with no
+    /// shot noise the only error is the θ grid resolution (about 1e-4).
+    /// Use [`estimate_sampled`](Self::estimate_sampled) for what a run with
+    /// finite shots would report.
+    pub fn estimate(&self) -> Result<f64> {
+        let probabilities = (0..=self.max_power)
+            .map(|k| self.good_probability(k))
+            .collect::<Result<Vec<f64>>>()?;
+
+        Ok(fit_amplitude(&probabilities))
+    }
+
+    /// Estimate a by maximum likelihood from `shots` measurements per power.
+    ///
+    /// Measures Q^k A|0⟩ for k = 0..=max_power on a simulator seeded with
+    /// `seed` and passes the histograms to
+    /// [`estimate_from_counts`](Self::estimate_from_counts). The error
+    /// shrinks as 1/√shots and, for fixed shots, roughly as 1/max_power².
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::AmplitudeEstimation;
+    /// use homaya_core::Circuit;
+    ///
+    /// let ae = AmplitudeEstimation::new(Circuit::new(1).ry(0.6, 0), &[1]);
+    /// let a = ae.estimate_sampled(1000, 7).unwrap();
+    /// assert!((a - 0.3f64.sin().powi(2)).abs() < 0.01);
+    /// ```
+    pub fn estimate_sampled(&self, shots: usize, seed: u64) -> Result<f64> {
+        let mut sim = Simulator::with_seed(seed);
+        let counts = (0..=self.max_power)
+            .map(|k| sim.sample(&self.build(k).measure_all(), shots))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(self.estimate_from_counts(&counts))
+    }
+
+    /// Estimate a by maximum likelihood from measured histograms.
+    ///
+    /// `counts[k]` is the histogram of Q^k A|0⟩ with every qubit measured,
+    /// keyed by canonical bitstrings (qubit 0 rightmost). Each power
+    /// contributes its good-state hits h_k out of N_k shots, and the θ
+    /// maximizing Σ h_k ln p_k + (N_k - h_k) ln(1 - p_k), with
+    /// p_k = sin²((2k + 1)θ), gives a = sin²θ.
+    pub fn estimate_from_counts(&self, counts: &[HashMap<String, usize>]) -> f64 {
+        let tallies: Vec<(usize, usize)> = counts
+            .iter()
+            .map(|histogram| {
+                let good = histogram
+                    .iter()
+                    .filter(|(bits, _)| parse_outcome(bits).is_some_and(|x| self.good_states.contains(&x)))
+                    .map(|(_, &n)| n)
+                    .sum();
+                (good, histogram.values().sum())
+            })
+            .collect();
+
+        max_likelihood_amplitude(&tallies)
+    }
+}
+
+/// Number of grid steps over θ ∈ [0, π/2] when fitting.
+const THETA_STEPS: usize = 20_000;
+
+/// The grid point θ ∈ [0, π/2] minimizing `cost`, mapped to a = sin²θ.
+fn grid_minimize(cost: impl Fn(f64) -> f64) -> f64 {
+    let best_theta = (0..=THETA_STEPS)
+        .map(|i| i as f64 * (PI / 2.0) / THETA_STEPS as f64)
+        .min_by(|&a, &b| cost(a).total_cmp(&cost(b)))
+        .unwrap_or(0.0);
+
+    best_theta.sin().powi(2)
+}
+
+/// Fit a = sin²θ to probabilities observed after k = 0, 1, 2, ... Grover operators.
+fn fit_amplitude(probabilities: &[f64]) -> f64 {
+    grid_minimize(|theta| {
+        probabilities
+            .iter()
+            .enumerate()
+            .map(|(k, &p)| {
+                let predicted = ((2 * k + 1) as f64 * theta).sin().powi(2);
+                (p - predicted).powi(2)
+            })
+            .sum()
+    })
+}
+
+/// Maximum-likelihood a = sin²θ from (good hits, shots) after k = 0, 1, 2, ... Grover operators.
+fn max_likelihood_amplitude(tallies: &[(usize, usize)]) -> f64 {
+    // Keeps ln finite where the model predicts a certain outcome
+    const P_FLOOR: f64 = 1e-12;
+
+    grid_minimize(|theta| {
+        -tallies
+            .iter()
+            .enumerate()
+            .map(|(k, &(good, shots))| {
+                let p = ((2 * k + 1) as f64 * theta).sin().powi(2).clamp(P_FLOOR, 1.0 - P_FLOOR);
+                good as f64 * p.ln() + (shots - good) as f64 * (1.0 - p).ln()
+            })
+            .sum::<f64>()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A non-uniform 2-qubit preparation with good state |11⟩.
+    fn biased_prep() -> (Circuit, f64) {
+        let circuit = Circuit::new(2).ry(0.7, 0).ry(1.1, 1);
+        let a = 0.35f64.sin().powi(2) * 0.55f64.sin().powi(2);
+        (circuit, a)
+    }
+
+    #[test]
+    fn test_grover_operator_rotates_by_two_theta() {
+        let (prep, a) = biased_prep();
+        let ae = AmplitudeEstimation::new(prep, &[0b11]);
+        let theta = a.sqrt().asin();
+
+        for k in 0..4 {
+            let expected = ((2 * k + 1) as f64 * theta).sin().powi(2);
+            let actual = ae.good_probability(k).unwrap();
+            assert!((actual - expected).abs() < 1e-10, "k={}: {} vs {}", k, actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_estimate_with_non_uniform_prep() {
+        let (prep, a) = biased_prep();
+        let estimate = AmplitudeEstimation::new(prep, &[0b11]).estimate().unwrap();
+        assert!((estimate - a).abs() < 1e-3, "estimate {} vs true {}", estimate, a);
+    }
+
+    #[test]
+    fn test_sampled_estimate_with_non_uniform_prep() {
+        let (prep, a) = biased_prep();
+        let ae = AmplitudeEstimation::new(prep, &[0b11]);
+
+        let estimate = ae.estimate_sampled(2000, 11).unwrap();
+        assert!((estimate - a).abs() < 0.01, "estimate {} vs true {}", estimate, a);
+        assert_eq!(ae.estimate_sampled(2000, 11).unwrap(), estimate);
+
+        // Shot noise: unlike the exact fit, a few shots land visibly off
+        let rough = ae.estimate_sampled(5, 11).unwrap();
+        assert!(rough != estimate);
+    }
+
+    #[test]
+    fn test_estimate_from_counts() {
+        // a = 1/2 means sin²((2k + 1)π/4) = 1/2 for every k
+        let ae = AmplitudeEstimation::new(Circuit::new(1).h(0), &[1]);
+        let histogram: HashMap<String, usize> = [("0".to_string(), 50), ("1".to_string(), 50)].into();
+        let estimate = ae.estimate_from_counts(&[histogram.clone(), histogram.clone(), histogram]);
+        assert!((estimate - 0.5).abs() < 1e-3, "{}", estimate);
+
+        // Only k = 0 observed, with every shot good
+        let histogram: HashMap<String, usize> = [("1".to_string(), 10)].into();
+        assert!((ae.estimate_from_counts(&[histogram]) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_uniform_diffuser_gives_wrong_estimate() {
+        let (prep, a) = biased_prep();
+        let ae = AmplitudeEstimation::new(prep.clone(), &[0b11]);
+
+        // Q built with the uniform (Grover) diffuser instead of A·S₀·A†
+        let uniform_q = crate::grover::reflect_about_zero_on(
+            Circuit::new(2).compose(&ae.oracle()).unwrap().h(0).h(1),
+            &[0, 1],
+            &[],
+        )
+        .h(0)
+        .h(1);
+
+        let mut sim = Simulator::new();
+        let probabilities: Vec<f64> = (0..=4)
+            .map(|k| {
+                let circuit = (0..k).fold(prep.clone(), |c, _| c.compose(&uniform_q).unwrap());
+                sim.run(&circuit).unwrap().probability(0b11)
+            })
+            .collect();
+
+        let wrong = fit_amplitude(&probabilities);
+        assert!((wrong - a).abs() > 0.01, "uniform diffuser estimate {} unexpectedly close to {}", wrong, a);
+    }
+
+    #[test]
+    fn test_multiple_good_states() {
+        // Uniform 2-qubit state with two good states: a = 1/2
+        let prep = Circuit::new(2).h(0).h(1);
+        let estimate = AmplitudeEstimation::new(prep, &[0b01, 0b10]).estimate().unwrap();
+        assert!((estimate - 0.5).abs() < 1e-3);
+    }
+}
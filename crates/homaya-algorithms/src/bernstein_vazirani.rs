@@ -8,6 +8,10 @@
 //! Given a black-box function f(x) = s · x (dot product mod 2),
 //! find s.
 //!
+//! The affine variant f(x) = s · x ⊕ b works the same way: the constant
+//! offset b only flips the overall sign of the state, a global phase, so the
+//! measured bits are still s.
+//!
 //! Classically: Need n queries (ask about each bit).
 //! Quantum: ONE query reveals s directly!
 //!
@@ -24,8 +28,21 @@
 //!
 //! Due to quantum interference, measuring the query qubits
 //! directly gives you the secret string. No iterative guessing needed!
+//!
+//! ## Bring Your Own Oracle
+//!
+//! [`BernsteinVazirani::with_oracle`] runs the same circuit around an
+//! oracle you supply, e.g. one loaded from a QASM file, without being told
+//! the secret. [`BernsteinVazirani::recover_secret`] then reads s off the
+//! measured bits.
+
+use std::collections::HashMap;
 
-use homaya_core::Circuit;
+use homaya_core::{format_outcome, most_likely_outcome, parse_outcome, BitOrder, Circuit, GateType, PI};
+use homaya_sim::MeasurementResult;
+
+use crate::deutsch::oracle_truth_table;
+use crate::AlgorithmError;
 
 /// Bernstein-Vazirani algorithm builder.
 ///
@@ -34,8 +51,22 @@ use homaya_core::Circuit;
 pub struct BernsteinVazirani {
     /// Number of qubits (length of secret string)
     n_qubits: usize,
-    /// The secret string we're trying to find
-    secret: usize,
+    /// The oracle computing f
+    oracle: Oracle,
+}
+
+/// Where the oracle comes from.
+#[derive(Debug, Clone)]
+enum Oracle {
+    /// Synthesized from a known f(x) = s · x ⊕ b
+    Secret {
+        /// The secret string we're trying to find
+        secret: usize,
+        /// Constant offset b
+        offset: bool,
+    },
+    /// Supplied by the caller, on n query qubits + 1 ancilla
+    Circuit(Circuit),
 }
 
 impl BernsteinVazirani {
@@ -46,6 +77,11 @@ impl BernsteinVazirani {
     /// * `n_qubits` - Number of bits in the secret string
     /// * `secret` - The secret string as an integer (0 to 2^n - 1)
     ///
+    /// # Panics
+    ///
+    /// Panics if n_qubits is 0 or the secret doesn't fit; see
+    /// [`try_new`](Self::try_new) for a non-panicking version.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -57,16 +93,182 @@ impl BernsteinVazirani {
     /// // Measuring will give "101" with 100% probability
     /// ```
     pub fn new(n_qubits: usize, secret: usize) -> Self {
-        let max_secret = 1 << n_qubits;
-        assert!(
-            secret < max_secret,
-            "Secret {} is too large for {} qubits (max: {})",
-            secret,
+        Self::new_affine(n_qubits, secret, false)
+    }
+
+    /// Create a new Bernstein-Vazirani instance, validating the arguments.
+    ///
+    /// # Errors
+    ///
+    /// [`AlgorithmError::ZeroQubits`] for an empty register and
+    /// [`AlgorithmError::SecretOutOfRange`] if secret >= 2^n_qubits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::{AlgorithmError, BernsteinVazirani};
+    ///
+    /// assert_eq!(
+    ///     BernsteinVazirani::try_new(2, 0b100).unwrap_err(),
+    ///     AlgorithmError::SecretOutOfRange { secret: 0b100, n_qubits: 2 }
+    /// );
+    /// ```
+    pub fn try_new(n_qubits: usize, secret: usize) -> Result<Self, AlgorithmError> {
+        Self::try_new_affine(n_qubits, secret, false)
+    }
+
+    /// Create an instance for the affine function f(x) = s · x ⊕ b.
+    ///
+    /// The oracle applies an extra X on the ancilla when `b` is true. The
+    /// ancilla sits in |−⟩, so that X only multiplies the whole state by -1:
+    /// a global phase that leaves the measured bits equal to s.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::BernsteinVazirani;
+    ///
+    /// // f(x) = 101 · x ⊕ 1 - measuring still gives "101"
+    /// let circuit = BernsteinVazirani::new_affine(3, 0b101, true).build();
+    /// ```
+    pub fn new_affine(n_qubits: usize, secret: usize, b: bool) -> Self {
+        Self::try_new_affine(n_qubits, secret, b).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Create an instance from the secret written as a bitstring.
+    ///
+    /// The string uses the same canonical order as
+    /// [`secret_as_binary`](Self::secret_as_binary) and measured bitstrings:
+    /// the *rightmost* character is bit 0. Its length sets the number of
+    /// query qubits, so leading zeros count.
+    ///
+    /// # Errors
+    ///
+    /// [`AlgorithmError::InvalidBitstring`] for a character other than `0`
+    /// or `1`, [`AlgorithmError::ZeroQubits`] for an empty string, and
+    /// [`AlgorithmError::TooManyQubits`] if the secret won't fit in a `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::BernsteinVazirani;
+    ///
+    /// // Bits 0, 1 and 3 set
+    /// let bv = BernsteinVazirani::from_binary("1011").unwrap();
+    /// assert_eq!(bv.secret_as_binary(), "1011");
+    /// ```
+    pub fn from_binary(secret: &str) -> Result<Self, AlgorithmError> {
+        if secret.len() > usize::BITS as usize {
+            return Err(AlgorithmError::TooManyQubits { n_qubits: secret.len() });
+        }
+
+        let mut value = 0usize;
+        for (index, found) in secret.chars().enumerate() {
+            let bit = match found {
+                '0' => 0,
+                '1' => 1,
+                _ => return Err(AlgorithmError::InvalidBitstring { index, found }),
+            };
+            value = value << 1 | bit;
+        }
+
+        Self::try_new(secret.len(), value)
+    }
+
+    /// Fallible version of [`new_affine`](Self::new_affine).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`try_new`](Self::try_new).
+    pub fn try_new_affine(n_qubits: usize, secret: usize, b: bool) -> Result<Self, AlgorithmError> {
+        if n_qubits == 0 {
+            return Err(AlgorithmError::ZeroQubits);
+        }
+        if secret.checked_shr(n_qubits as u32).unwrap_or(0) != 0 {
+            return Err(AlgorithmError::SecretOutOfRange { secret, n_qubits });
+        }
+
+        Ok(Self {
             n_qubits,
-            max_secret - 1
-        );
+            oracle: Oracle::Secret { secret, offset: b },
+        })
+    }
 
-        Self { n_qubits, secret }
+    /// Run the algorithm around a caller-supplied oracle.
+    ///
+    /// `oracle` acts on the n query qubits 0..n and the ancilla, qubit n,
+    /// and should map |x⟩|y⟩ → |x⟩|y ⊕ f(x)⟩ for some f(x) = s · x ⊕ b.
+    /// It goes between the H layers in place of the synthesized CX gates,
+    /// so the secret never has to be known: read it from the measurement
+    /// with [`recover_secret`](Self::recover_secret) or
+    /// [`recover_counts`](Self::recover_counts), and check the oracle
+    /// really is linear with [`verify_secret`](Self::verify_secret).
+    ///
+    /// # Panics
+    ///
+    /// Panics if n_qubits is 0, the oracle doesn't have n_qubits + 1
+    /// qubits, or it measures or resets; see
+    /// [`try_with_oracle`](Self::try_with_oracle).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::BernsteinVazirani;
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// // Hides s = 110 behind two CX gates into the ancilla (qubit 3)
+    /// let oracle = Circuit::new(4).cx(1, 3).cx(2, 3);
+    /// let bv = BernsteinVazirani::with_oracle(3, oracle);
+    ///
+    /// let (_, result) = Simulator::new().run_with_measurements(&bv.build()).unwrap();
+    /// assert_eq!(BernsteinVazirani::recover_secret(&result), 0b110);
+    /// ```
+    pub fn with_oracle(n_qubits: usize, oracle: Circuit) -> Self {
+        Self::try_with_oracle(n_qubits, oracle).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Fallible version of [`with_oracle`](Self::with_oracle).
+    ///
+    /// # Errors
+    ///
+    /// [`AlgorithmError::ZeroQubits`], [`AlgorithmError::OracleWidth`] or
+    /// [`AlgorithmError::NonUnitaryOracle`].
+    pub fn try_with_oracle(n_qubits: usize, oracle: Circuit) -> Result<Self, AlgorithmError> {
+        if n_qubits == 0 {
+            return Err(AlgorithmError::ZeroQubits);
+        }
+        if oracle.num_qubits() != n_qubits + 1 {
+            return Err(AlgorithmError::OracleWidth {
+                expected: n_qubits + 1,
+                found: oracle.num_qubits(),
+            });
+        }
+        if oracle.gates().any(|gate| matches!(gate.gate_type, GateType::Measure | GateType::Reset)) {
+            return Err(AlgorithmError::NonUnitaryOracle);
+        }
+
+        Ok(Self {
+            n_qubits,
+            oracle: Oracle::Circuit(oracle),
+        })
+    }
+
+    /// The secret, if this instance was built from one.
+    ///
+    /// `None` for [`with_oracle`](Self::with_oracle): the point there is
+    /// that only the measurement knows it.
+    pub fn secret(&self) -> Option<usize> {
+        match self.oracle {
+            Oracle::Secret { secret, .. } => Some(secret),
+            Oracle::Circuit(_) => None,
+        }
+    }
+
+    /// The secret, for the methods that need one.
+    fn known_secret(&self) -> usize {
+        self.secret()
+            .expect("the secret of a custom oracle is unknown; measure it with recover_secret")
     }
 
     /// Build the Bernstein-Vazirani circuit.
@@ -86,32 +288,188 @@ impl BernsteinVazirani {
             circuit = circuit.h(i);
         }
 
-        // Step 3: Apply oracle for f(x) = s · x
+        // Step 3: Apply oracle for f(x) = s · x ⊕ b
+        circuit = self.apply_oracle(circuit, ancilla);
+
+        // Step 4: Apply H to query qubits
+        for i in 0..self.n_qubits {
+            circuit = circuit.h(i);
+        }
+
+        // Step 5: Measure query qubits
+        circuit = circuit.measure_range(0..self.n_qubits);
+
+        circuit
+    }
+
+    /// Apply the oracle |x⟩|y⟩ → |x⟩|y ⊕ s · x ⊕ b⟩.
+    fn apply_oracle(&self, mut circuit: Circuit, ancilla: usize) -> Circuit {
+        let (secret, offset) = match &self.oracle {
+            Oracle::Secret { secret, offset } => (*secret, *offset),
+            Oracle::Circuit(oracle) => return circuit.append(oracle),
+        };
+
         // For each bit i where secret[i] = 1, apply CNOT from qubit i to ancilla
         for i in 0..self.n_qubits {
-            if (self.secret >> i) & 1 == 1 {
+            if (secret >> i) & 1 == 1 {
                 circuit = circuit.cx(i, ancilla);
             }
         }
+        if offset {
+            circuit = circuit.x(ancilla);
+        }
+        circuit
+    }
 
-        // Step 4: Apply H to query qubits
+    /// Check a measured secret against the oracle's classical action.
+    ///
+    /// Simulates the oracle this instance builds on every input x and
+    /// confirms f(x) = m · x ⊕ f(0) for the measured bitstring m. This
+    /// validates both the measurement and the oracle gates: a string that
+    /// merely equals [`secret_as_binary`](Self::secret_as_binary) still
+    /// fails if the oracle doesn't compute that dot product. Costs 2^n
+    /// simulations; meant for tests and debug checks.
+    ///
+    /// Returns false for a string that isn't an n-bit canonical bitstring.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::BernsteinVazirani;
+    ///
+    /// let bv = BernsteinVazirani::new_affine(3, 0b110, true);
+    /// assert!(bv.verify_secret("110"));
+    /// assert!(!bv.verify_secret("011"));
+    /// ```
+    pub fn verify_secret(&self, measured: &str) -> bool {
+        let secret = match parse_outcome(measured) {
+            Some(secret) if measured.len() == self.n_qubits => secret,
+            _ => return false,
+        };
+
+        let oracle = self.apply_oracle(Circuit::new(self.n_qubits + 1), self.n_qubits);
+        let Ok(values) = oracle_truth_table(&oracle, self.n_qubits) else {
+            return false;
+        };
+        values
+            .iter()
+            .enumerate()
+            .all(|(x, &fx)| fx == (((secret & x).count_ones() % 2 == 1) ^ values[0]))
+    }
+
+    /// Build the algorithm in its Fourier (hidden-subgroup) form.
+    ///
+    /// Bernstein-Vazirani is the hidden-subgroup problem over Z₂ⁿ: the oracle
+    /// is the character χ_s(x) = (-1)^(s·x), and Fourier sampling reads s off
+    /// directly. This form makes that structure explicit:
+    ///
+    /// 1. QFT over Z₂ on every digit
+    /// 2. Phase oracle χ_s(x) - a phase e^(2πi·sᵢ/2) on each digit, no ancilla
+    /// 3. Inverse QFT over Z₂ on every digit
+    ///
+    /// Over Z_d the same steps use the d-dimensional QFT and phases
+    /// e^(2πi·sᵢ/d). For d = 2 the QFT is exactly H and the phase is Z, so
+    /// this reduces to the standard circuit with the ancilla's phase
+    /// kickback folded into the oracle. An affine offset b is a global phase
+    /// (-1)^b, so it drops out of this form entirely.
+    ///
+    /// # Panics
+    ///
+    /// Panics for an instance built [`with_oracle`](Self::with_oracle),
+    /// whose secret is unknown.
+    pub fn fourier_form(&self) -> Circuit {
+        const D: usize = 2;
+        let secret = self.known_secret();
+
+        let mut circuit = Circuit::new(self.n_qubits);
+
+        // Step 1: QFT over Z₂ (the 2-point DFT is H)
         for i in 0..self.n_qubits {
             circuit = circuit.h(i);
         }
 
-        // Step 5: Measure query qubits
+        // Step 2: Phase oracle χ_s(x) = Π e^(2πi·sᵢxᵢ/d)
         for i in 0..self.n_qubits {
-            circuit = circuit.measure(i, i);
+            let digit = (secret >> i) & 1;
+            if digit != 0 {
+                circuit = circuit.p(2.0 * PI * digit as f64 / D as f64, i);
+            }
         }
 
-        circuit
+        // Step 3: Inverse QFT over Z₂ (H is its own inverse)
+        for i in 0..self.n_qubits {
+            circuit = circuit.h(i);
+        }
+
+        // Step 4: Measure - the register holds s
+        circuit.measure_all()
     }
 
-    /// Get the secret string as a binary string.
+    /// Recover the secret from a measurement histogram.
     ///
-    /// Useful for verifying the measurement result.
+    /// Takes counts keyed by canonical bitstrings, e.g. from real hardware.
+    /// Noise can scatter a few shots, so the most frequent outcome wins.
+    /// Returns `None` for an empty histogram.
+    pub fn recover_counts(counts: &HashMap<String, usize>) -> Option<usize> {
+        most_likely_outcome(counts)
+    }
+
+    /// Recover the secret from a single shot, e.g. from
+    /// [`Simulator::run_with_measurements`](homaya_sim::Simulator::run_with_measurements)
+    /// on [`build`](Self::build).
+    ///
+    /// Query qubit i is measured into classical bit i, so the bits are s.
+    /// An ideal run needs only this one shot.
+    pub fn recover_secret(result: &MeasurementResult) -> usize {
+        result.as_int() as usize
+    }
+
+    /// Get the secret string as a canonical bitstring (bit 0 rightmost).
+    ///
+    /// Matches the measured bitstring key exactly.
+    ///
+    /// # Panics
+    ///
+    /// Panics for an instance built [`with_oracle`](Self::with_oracle).
     pub fn secret_as_binary(&self) -> String {
-        format!("{:0width$b}", self.secret, width = self.n_qubits)
+        format_outcome(self.known_secret(), self.n_qubits)
+    }
+
+    /// Get the secret string in the given bit order.
+    ///
+    /// Matches the keys of a simulator using the same order, see
+    /// [`Simulator::with_bit_order`](homaya_sim::Simulator::with_bit_order).
+    /// Panics like [`secret_as_binary`](Self::secret_as_binary).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::BernsteinVazirani;
+    /// use homaya_core::BitOrder;
+    ///
+    /// let bv = BernsteinVazirani::new(4, 0b0011);
+    /// assert_eq!(bv.secret_as_binary_with(BitOrder::LittleEndian), "0011");
+    /// assert_eq!(bv.secret_as_binary_with(BitOrder::BigEndian), "1100");
+    /// ```
+    pub fn secret_as_binary_with(&self, order: BitOrder) -> String {
+        order.format(self.known_secret(), self.n_qubits)
+    }
+
+    /// The outcome an ideal run of [`build`](Self::build) always measures.
+    ///
+    /// Worked out without simulating: the register ends in |s⟩, so this is
+    /// [`secret_as_binary`](Self::secret_as_binary), and panics the same
+    /// way. Useful as a reference to print next to sampled results.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::BernsteinVazirani;
+    ///
+    /// assert_eq!(BernsteinVazirani::new(4, 0b1010).expected_measurement(), "1010");
+    /// ```
+    pub fn expected_measurement(&self) -> String {
+        self.secret_as_binary()
     }
 }
 
@@ -156,6 +514,129 @@ mod tests {
         BernsteinVazirani::new(4, 16);  // Max is 15 for 4 qubits
     }
 
+    #[test]
+    fn test_find_secret_end_to_end() {
+        let secret = 0b1010;
+        let state = homaya_sim::Simulator::new().run(&find_secret(4, secret)).unwrap();
+
+        // The ancilla ends in |−⟩, so the secret shows up on both of its values
+        let p: f64 = [secret, secret | 1 << 4].iter().map(|&i| state.probability(i)).sum();
+        assert!((p - 1.0).abs() < 1e-10);
+        assert!((state.amplitude(secret).norm_sqr() - 0.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_most_likely_state_is_secret() {
+        // Return the ancilla from |−⟩ to |0⟩ so the whole state is |secret⟩
+        let circuit = find_secret(4, 0b1010).h(4).x(4);
+        let state = homaya_sim::Simulator::new().run(&circuit).unwrap();
+
+        let (index, p) = state.most_likely();
+        assert_eq!(index, 0b1010);
+        assert!((p - 1.0).abs() < 1e-10);
+        assert!((state.marginal_probabilities(&[1, 3])[0b11] - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_per_qubit_probabilities_match_secret() {
+        let secret = 0b1011;
+        let circuit = BernsteinVazirani::new(4, secret).build();
+        let ones = homaya_sim::Simulator::new().per_qubit_probabilities(&circuit).unwrap();
+
+        for (i, p) in ones.iter().take(4).enumerate() {
+            let expected = ((secret >> i) & 1) as f64;
+            assert!((p - expected).abs() < 1e-10, "qubit {}: P(1) = {}", i, p);
+        }
+    }
+
+    #[test]
+    fn test_measured_key_matches_secret() {
+        let bv = BernsteinVazirani::new(4, 0b0011);
+        let counts = homaya_sim::Simulator::with_seed(7).sample(&bv.build(), 20).unwrap();
+        assert_eq!(counts.get("0011"), Some(&20));
+        assert_eq!(bv.secret_as_binary(), "0011");
+    }
+
+    #[test]
+    fn test_recover_from_noisy_counts() {
+        let counts = HashMap::from([
+            ("1010".to_string(), 880),
+            ("1011".to_string(), 70),
+            ("0010".to_string(), 50),
+        ]);
+        assert_eq!(BernsteinVazirani::recover_counts(&counts), Some(0b1010));
+        assert_eq!(BernsteinVazirani::recover_counts(&HashMap::new()), None);
+    }
+
+    #[test]
+    fn test_fourier_form_recovers_secret() {
+        for secret in [0b0000, 0b1010, 0b0111, 0b1111] {
+            let bv = BernsteinVazirani::new(4, secret);
+            let circuit = bv.fourier_form();
+            assert_eq!(circuit.num_qubits(), 4);
+
+            let counts = homaya_sim::Simulator::with_seed(3).sample(&circuit, 20).unwrap();
+            assert_eq!(counts.get(&bv.secret_as_binary()), Some(&20));
+        }
+    }
+
+    #[test]
+    fn test_affine_offset_is_global_phase() {
+        let secret = 0b1101;
+        let linear = homaya_sim::Simulator::new().run(&BernsteinVazirani::new(4, secret).build()).unwrap();
+        let affine = homaya_sim::Simulator::new()
+            .run(&BernsteinVazirani::new_affine(4, secret, true).build())
+            .unwrap();
+
+        // Same state up to an overall sign
+        assert!(affine.inner_product(&linear).approx_eq(homaya_core::Complex::new(-1.0, 0.0), 1e-10));
+
+        let counts = homaya_sim::Simulator::with_seed(2)
+            .sample(&BernsteinVazirani::new_affine(4, secret, true).build(), 20)
+            .unwrap();
+        assert_eq!(counts.get("1101"), Some(&20));
+    }
+
+    #[test]
+    fn test_try_new_errors() {
+        assert!(BernsteinVazirani::try_new(4, 0b1111).is_ok());
+        assert_eq!(BernsteinVazirani::try_new(0, 0).unwrap_err(), AlgorithmError::ZeroQubits);
+        assert_eq!(
+            BernsteinVazirani::try_new_affine(3, 9, true).unwrap_err(),
+            AlgorithmError::SecretOutOfRange { secret: 9, n_qubits: 3 }
+        );
+    }
+
+    #[test]
+    fn test_from_binary() {
+        let bv = BernsteinVazirani::from_binary("1010").unwrap();
+        assert_eq!(bv.build().instructions(), BernsteinVazirani::new(4, 0b1010).build().instructions());
+
+        for secret in ["0", "1", "0011", "100000", "1011"] {
+            assert_eq!(BernsteinVazirani::from_binary(secret).unwrap().secret_as_binary(), secret);
+        }
+
+        assert_eq!(
+            BernsteinVazirani::from_binary("10x1").unwrap_err(),
+            AlgorithmError::InvalidBitstring { index: 2, found: 'x' }
+        );
+        assert_eq!(BernsteinVazirani::from_binary("").unwrap_err(), AlgorithmError::ZeroQubits);
+    }
+
+    #[test]
+    fn test_verify_secret() {
+        let bv = BernsteinVazirani::new(4, 0b1011);
+        assert!(bv.verify_secret("1011"));
+        assert!(!bv.verify_secret("1010"));
+        assert!(!bv.verify_secret("01011"));
+        assert!(!bv.verify_secret("10a1"));
+
+        // The measured key from a run verifies against the oracle
+        let counts = homaya_sim::Simulator::with_seed(5).sample(&bv.build(), 10).unwrap();
+        let measured = format_outcome(BernsteinVazirani::recover_counts(&counts).unwrap(), 4);
+        assert!(bv.verify_secret(&measured));
+    }
+
     #[test]
     fn test_zero_secret() {
         let bv = BernsteinVazirani::new(3, 0);
@@ -163,4 +644,120 @@ mod tests {
         // Should still create valid circuit
         assert_eq!(circuit.num_qubits(), 4);
     }
+
+    #[test]
+    fn test_batch_recovers_each_secret() {
+        let instances = [
+            BernsteinVazirani::new(4, 0b1010),
+            BernsteinVazirani::new(4, 0b0111),
+            BernsteinVazirani::new(3, 0b100),
+        ];
+        let circuits: Vec<Circuit> = instances.iter().map(|bv| bv.build()).collect();
+
+        let batch = homaya_sim::Simulator::with_seed(1).sample_batch(&circuits, 50).unwrap();
+        for (bv, counts) in instances.iter().zip(&batch) {
+            assert_eq!(counts.get(&bv.secret_as_binary()), Some(&50), "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_secret_matches_modal_key_in_either_order() {
+        let bv = BernsteinVazirani::new(5, 0b00110);
+        for order in [BitOrder::LittleEndian, BitOrder::BigEndian] {
+            let counts = homaya_sim::Simulator::with_seed(2).with_bit_order(order).sample(&bv.build(), 40).unwrap();
+            let (modal, _) = counts.iter().max_by_key(|&(_, &count)| count).unwrap();
+            assert_eq!(modal, &bv.secret_as_binary_with(order), "{:?}", order);
+        }
+        assert_eq!(bv.secret_as_binary_with(BitOrder::BigEndian), "01100");
+    }
+
+    #[test]
+    fn test_query_qubits_are_deterministic() {
+        // Each query qubit is definite, so the measurements don't disturb it
+        let state = homaya_sim::Simulator::new().run(&find_secret(3, 0b101)).unwrap();
+
+        assert!((state.qubit_probability_one(0) - 1.0).abs() < 1e-10);
+        assert!(state.qubit_probability_one(1).abs() < 1e-10);
+        assert!((state.qubit_probability_one(2) - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_expected_measurement_is_modal_outcome() {
+        for (n, secret) in [(1, 1), (3, 0b000), (4, 0b1011)] {
+            let bv = BernsteinVazirani::new(n, secret);
+            let counts = homaya_sim::Simulator::with_seed(8).sample(&bv.build(), 30).unwrap();
+            assert_eq!(most_likely_outcome(&counts), Some(secret));
+            assert_eq!(counts.get(&bv.expected_measurement()), Some(&30), "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_gate_count_is_linear() {
+        // X and n+1 H, one CX per secret bit, then n H: 2n + 2 + |s|
+        for n in 1..=32 {
+            for secret in [0, 1 << (n - 1), (1usize << n) - 1] {
+                let expected = 2 * n + 2 + secret.count_ones() as usize;
+                assert_eq!(find_secret(n, secret).gate_count(), expected, "n = {}, s = {:b}", n, secret);
+            }
+        }
+    }
+
+    #[test]
+    fn test_gate_count_regression_ten_qubits() {
+        // 1 X + 11 H + 5 CX + 10 H
+        assert_eq!(find_secret(10, 0b1010101010).gate_count(), 27);
+        assert_eq!(find_secret(10, 0b1010101010).two_qubit_gate_count(), 5);
+    }
+
+    #[test]
+    fn test_custom_oracle_recovers_secret() {
+        // An oracle built elsewhere from a secret this side never sees
+        let hidden = BernsteinVazirani::new_affine(5, 0b10110, true);
+        let oracle = hidden.apply_oracle(Circuit::new(6), 5);
+
+        let bv = BernsteinVazirani::with_oracle(5, oracle);
+        assert_eq!(bv.secret(), None);
+
+        let (_, result) = homaya_sim::Simulator::with_seed(3).run_with_measurements(&bv.build()).unwrap();
+        let secret = BernsteinVazirani::recover_secret(&result);
+        assert_eq!(secret, 0b10110);
+        assert!(bv.verify_secret(&format_outcome(secret, 5)));
+        assert_eq!(bv.build().instructions(), hidden.build().instructions());
+    }
+
+    #[test]
+    fn test_custom_oracle_is_validated() {
+        assert_eq!(
+            BernsteinVazirani::try_with_oracle(3, Circuit::new(3)).unwrap_err(),
+            AlgorithmError::OracleWidth { expected: 4, found: 3 }
+        );
+        assert_eq!(
+            BernsteinVazirani::try_with_oracle(1, Circuit::with_clbits(2, 1).measure(1, 0)).unwrap_err(),
+            AlgorithmError::NonUnitaryOracle
+        );
+        assert_eq!(BernsteinVazirani::try_with_oracle(0, Circuit::new(1)).unwrap_err(), AlgorithmError::ZeroQubits);
+    }
+
+    #[test]
+    #[should_panic(expected = "the secret of a custom oracle is unknown")]
+    fn test_custom_oracle_has_no_secret_string() {
+        BernsteinVazirani::with_oracle(2, Circuit::new(3).cx(0, 2)).secret_as_binary();
+    }
+
+    use proptest::prelude::*;
+
+    proptest! {
+        #![proptest_config(crate::proptest_config())]
+
+        #[test]
+        fn prop_recovers_any_secret(
+            (n, secret) in (1usize..=6).prop_flat_map(|n| (Just(n), 0..1usize << n)),
+            offset: bool,
+        ) {
+            let bv = BernsteinVazirani::new_affine(n, secret, offset);
+            let mut sim = homaya_sim::Simulator::new();
+            let p = sim.success_probability(&bv.build(), &format_outcome(secret, n)).unwrap();
+            prop_assert!((p - 1.0).abs() < 1e-9, "n = {}, s = {:b}: {}", n, secret, p);
+        }
+    }
 }
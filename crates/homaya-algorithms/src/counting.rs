@@ -0,0 +1,157 @@
+//! # Quantum Counting
+//!
+//! Estimate how many items an oracle marks, before searching for them.
+//!
+//! ## The Problem
+//!
+//! Grover's search needs the number of marked items M to pick its
+//! iteration count, but often M is exactly what you don't know. Quantum
+//! counting estimates M with O(√N) oracle calls.
+//!
+//! ## How It Works
+//!
+//! The Grover iteration G = (2|ψ⟩⟨ψ| - I) · S_χ rotates by θ in the plane
+//! of marked and unmarked states, where sin²(θ/2) = M/N. Its eigenvalues
+//! are e^(±iθ), and the uniform superposition |ψ⟩ is a mix of both
+//! eigenvectors. So:
+//!
+//! 1. Prepare |ψ⟩ = H⊗ⁿ|0⟩ on the search register
+//! 2. Run [phase estimation](crate::phase_estimation) on G
+//! 3. Read off θ and return M = N·sin²(θ/2)
+//!
+//! Either eigenvalue gives the same M, since sin² is symmetric.
+//!
+//! ## Precision
+//!
+//! With t counting qubits θ is known to within 2π/2^t, which bounds the
+//! error in M by roughly 2π·√(M(N-M))/2^t. Each extra counting qubit
+//! doubles the number of controlled Grover iterations.
+
+use homaya_core::{Circuit, Result, PI};
+
+use crate::amplification::reflect_about_prepared_on;
+use crate::PhaseEstimation;
+
+/// Quantum counting builder.
+///
+/// Qubits `0..counting_qubits` form the counting register, the search
+/// register follows, then any ancillas the oracle uses.
+#[derive(Debug, Clone)]
+pub struct QuantumCounting {
+    /// Number of search qubits (N = 2^n_qubits)
+    n_qubits: usize,
+    /// Phase estimation on the Grover iteration
+    qpe: PhaseEstimation,
+}
+
+impl QuantumCounting {
+    /// Create a new quantum counting instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `n_qubits` - Number of search qubits (search space = 2^n_qubits)
+    /// * `counting_qubits` - Bits of precision in the phase estimate
+    /// * `oracle` - Circuit flipping the sign of every marked state, as
+    ///   returned by [`GroverSearch::oracle_circuit`](crate::GroverSearch::oracle_circuit).
+    ///   Qubits past the first `n_qubits` are clean ancillas: they start in
+    ///   |0⟩ and the oracle must leave them there.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the oracle acts on fewer than `n_qubits` qubits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::{GroverSearch, QuantumCounting};
+    ///
+    /// let oracle = GroverSearch::with_targets(3, &[1, 6]).oracle_circuit();
+    /// let count = QuantumCounting::new(3, 4, &oracle).estimate().unwrap();
+    /// assert_eq!(count.round(), 2.0);
+    /// ```
+    pub fn new(n_qubits: usize, counting_qubits: usize, oracle: &Circuit) -> Self {
+        assert!(
+            oracle.num_qubits() >= n_qubits,
+            "oracle must act on the {} search qubits",
+            n_qubits
+        );
+
+        let uniform = (0..n_qubits).fold(Circuit::new(oracle.num_qubits()), |c, q| c.h(q));
+        let grover_iteration = oracle.clone().append(&reflect_about_prepared_on(&uniform, n_qubits));
+        let qpe = PhaseEstimation::new(counting_qubits, grover_iteration).with_eigenstate(uniform);
+
+        Self { n_qubits, qpe }
+    }
+
+    /// Total number of qubits: counting register, search register and ancillas.
+    pub fn total_qubits(&self) -> usize {
+        self.qpe.total_qubits()
+    }
+
+    /// Build the counting circuit, measuring the counting register.
+    ///
+    /// Counting qubit j is measured into classical bit j; pass the measured
+    /// value to [`count_from_measurement`](Self::count_from_measurement).
+    pub fn build(&self) -> Result<Circuit> {
+        self.qpe.build()
+    }
+
+    /// Convert a measured counting-register value into an estimate of M.
+    pub fn count_from_measurement(&self, measured: usize) -> f64 {
+        self.count_from_phase(self.qpe.phase_from_measurement(measured))
+    }
+
+    /// Simulate the circuit and estimate the number of marked items M.
+    ///
+    /// Not rounded: the distance to the nearest integer hints at how much
+    /// resolution the counting register has.
+    pub fn estimate(&self) -> Result<f64> {
+        Ok(self.count_from_phase(self.qpe.estimate()?))
+    }
+
+    /// M = N·sin²(θ/2) for a measured phase φ.
+    ///
+    /// The reflection about |ψ⟩ is built up to a global sign, so the circuit
+    /// implements -G and φ = (θ + π)/2π.
+    fn count_from_phase(&self, phi: f64) -> f64 {
+        let theta = 2.0 * PI * phi - PI;
+        let search_space = (1usize << self.n_qubits) as f64;
+        search_space * (theta / 2.0).sin().powi(2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GroverSearch;
+    use homaya_sim::Simulator;
+
+    #[test]
+    fn test_counts_two_marked_items() {
+        let oracle = GroverSearch::with_targets(4, &[3, 12]).oracle_circuit();
+        let counting = QuantumCounting::new(4, 5, &oracle);
+        // 5 counting, 4 search and 1 ancilla
+        assert_eq!(counting.total_qubits(), 10);
+
+        // Within one counting step M moves by at most 2π·√(M(N-M))/2^t ≈ 1.1
+        let estimate = counting.estimate().unwrap();
+        assert!((estimate - 2.0).abs() < 0.5, "estimate {}", estimate);
+        assert_eq!(estimate.round(), 2.0);
+    }
+
+    #[test]
+    fn test_sampled_count() {
+        let oracle = GroverSearch::with_targets(3, &[0, 5, 6]).oracle_circuit();
+        let counting = QuantumCounting::new(3, 5, &oracle);
+
+        let counts = Simulator::with_seed(17).sample(&counting.build().unwrap(), 200).unwrap();
+        let measured = homaya_core::most_likely_outcome(&counts).unwrap();
+        assert_eq!(counting.count_from_measurement(measured).round(), 3.0);
+    }
+
+    #[test]
+    fn test_no_marked_items() {
+        let counting = QuantumCounting::new(3, 3, &Circuit::new(3));
+        assert!(counting.estimate().unwrap() < 1e-9);
+    }
+}
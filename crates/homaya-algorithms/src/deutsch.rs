@@ -21,8 +21,28 @@
 //!    - All zeros → Constant
 //!    - Any non-zero → Balanced
 //!
+//! ## The Single-Bit Case
+//!
+//! Deutsch's original problem is n = 1: of the four functions {0,1} → {0,1},
+//! is f one of the two constant ones or one of the two balanced ones?
+//! [`deutsch_single`] builds that 2-qubit circuit directly:
+//!
+//! 1. |0⟩|1⟩ → H⊗H → |+⟩|−⟩
+//! 2. The oracle kicks the phase (−1)^f(x) back onto the query qubit,
+//!    leaving (−1)^f(0) (|0⟩ + (−1)^(f(0) ⊕ f(1)) |1⟩)/√2 ⊗ |−⟩
+//! 3. H on the query qubit maps that to |f(0) ⊕ f(1)⟩
+//!
+//! One measurement reads f(0) ⊕ f(1): 0 for constant, 1 for balanced.
+//! Classically that takes two evaluations of f.
+//!
+
+use std::collections::HashMap;
+use std::fmt;
 
-use homaya_core::Circuit;
+use homaya_core::{format_outcome, Circuit, HomayaError};
+use homaya_sim::{MeasurementResult, Simulator};
+
+use crate::AlgorithmError;
 
 /// Types of functions for Deutsch-Jozsa.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -35,8 +55,73 @@ pub enum FunctionType {
     BalancedParity,
     /// f(x) = first bit of x (balanced)
     BalancedFirstBit,
+    /// f(x) = last bit of x, x_(n-1) (balanced)
+    BalancedLastBit,
+    /// f(x) = parity of x AND mask (balanced for any nonzero mask)
+    ///
+    /// Generalizes `BalancedParity` (all ones) and `BalancedFirstBit` (1).
+    BalancedMask(usize),
+}
+
+/// Where the oracle's function comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Oracle {
+    /// One of the built-in functions
+    Function(FunctionType),
+    /// f(x) = table[x]
+    TruthTable(Vec<bool>),
+}
+
+/// The verdict Deutsch-Jozsa reaches about a function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Query register measured all zeros
+    Constant,
+    /// Query register measured anything else
+    Balanced,
+}
+
+/// Why a custom oracle circuit breaks the Deutsch-Jozsa promise.
+///
+/// Returned by [`DeutschJozsa::verify_oracle_circuit`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PromiseViolation {
+    /// f is neither constant nor balanced
+    Unbalanced {
+        /// Number of inputs with f(x) = 1
+        ones: usize,
+        /// Total number of inputs, 2^n
+        inputs: usize,
+    },
+    /// The oracle does not act as |x⟩|y⟩ → |x⟩|y ⊕ f(x)⟩ on this input
+    NotClassical {
+        /// The offending input x
+        input: usize,
+    },
+    /// The oracle could not be simulated
+    Simulation(HomayaError),
+}
+
+impl fmt::Display for PromiseViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unbalanced { ones, inputs } => write!(
+                f,
+                "f(x) = 1 on {} of {} inputs: neither constant nor balanced",
+                ones, inputs
+            ),
+            Self::NotClassical { input } => write!(
+                f,
+                "oracle does not compute a classical function on input {}",
+                input
+            ),
+            Self::Simulation(err) => write!(f, "oracle could not be simulated: {}", err),
+        }
+    }
 }
 
+impl std::error::Error for PromiseViolation {}
+
 /// Deutsch-Jozsa algorithm builder.
 ///
 /// Creates a circuit that determines if a function is constant or balanced.
@@ -44,8 +129,8 @@ pub enum FunctionType {
 pub struct DeutschJozsa {
     /// Number of query qubits
     n_qubits: usize,
-    /// The oracle function type
-    function: FunctionType,
+    /// The oracle function
+    oracle: Oracle,
 }
 
 impl DeutschJozsa {
@@ -56,6 +141,11 @@ impl DeutschJozsa {
     /// * `n_qubits` - Number of query qubits (domain is {0,1}^n)
     /// * `function` - The oracle function type
     ///
+    /// # Panics
+    ///
+    /// Panics if n_qubits is 0 or a `BalancedMask` is invalid; see
+    /// [`try_new`](Self::try_new) for a non-panicking version.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -64,47 +154,136 @@ impl DeutschJozsa {
     /// let dj = DeutschJozsa::new(3, FunctionType::BalancedParity);
     /// ```
     pub fn new(n_qubits: usize, function: FunctionType) -> Self {
-        assert!(n_qubits >= 1, "Need at least 1 query qubit");
-        Self { n_qubits, function }
+        Self::try_new(n_qubits, function).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Create a new Deutsch-Jozsa instance, validating the arguments.
+    ///
+    /// # Errors
+    ///
+    /// [`AlgorithmError::ZeroQubits`] if n_qubits is 0, and
+    /// [`AlgorithmError::InvalidMask`] for a `BalancedMask` that is zero
+    /// (f would be constant) or has bits beyond the register.
+    pub fn try_new(n_qubits: usize, function: FunctionType) -> Result<Self, AlgorithmError> {
+        if n_qubits == 0 {
+            return Err(AlgorithmError::ZeroQubits);
+        }
+        if let FunctionType::BalancedMask(mask) = function {
+            if mask == 0 || mask.checked_shr(n_qubits as u32).unwrap_or(0) != 0 {
+                return Err(AlgorithmError::InvalidMask { mask, n_qubits });
+            }
+        }
+
+        Ok(Self {
+            n_qubits,
+            oracle: Oracle::Function(function),
+        })
+    }
+
+    /// Create an instance whose oracle computes f(x) = `values[x]`.
+    ///
+    /// The number of query qubits is log₂ of the table length. Each input
+    /// with f(x) = 1 becomes one multi-controlled X onto the ancilla
+    /// ([`Circuit::mcx`]), a Toffoli ladder of 2n - 3 CCX gates on n - 2
+    /// clean `work` qubits, so the oracle grows with the number of ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length is not a power of two (at least 2), or if the
+    /// function breaks the promise by being neither constant nor balanced -
+    /// the algorithm's answer would be meaningless.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::DeutschJozsa;
+    ///
+    /// // f(x) = x0 XOR x1
+    /// let dj = DeutschJozsa::from_truth_table(&[false, true, true, false]);
+    /// assert_eq!(dj.build().num_qubits(), 3);
+    /// ```
+    pub fn from_truth_table(values: &[bool]) -> Self {
+        let inputs = values.len();
+        assert!(
+            inputs >= 2 && inputs.is_power_of_two(),
+            "Truth table length {} is not a power of two (need 2^n entries, n >= 1)",
+            inputs
+        );
+
+        let ones = values.iter().filter(|&&v| v).count();
+        if let Err(violation) = promise_verdict(ones, inputs) {
+            panic!("Truth table breaks the Deutsch-Jozsa promise: {}", violation);
+        }
+
+        Self {
+            n_qubits: inputs.trailing_zeros() as usize,
+            oracle: Oracle::TruthTable(values.to_vec()),
+        }
     }
 
     /// Build the Deutsch-Jozsa circuit.
     ///
-    /// The circuit has n+1 qubits: n query qubits + 1 ancilla.
-    /// After measurement, check if query qubits are all zero.
+    /// The circuit has n+1 qubits in two [registers](Circuit::registers):
+    /// `query` (qubits 0..n) and `ancilla` (qubit n). Truth-table oracles on
+    /// three or more query qubits add a third, `work`, of n - 2 clean
+    /// ancillas for their multi-controlled X gates. After measurement,
+    /// check if query qubits are all zero.
     pub fn build(&self) -> Circuit {
-        let total_qubits = self.n_qubits + 1;
-        let ancilla = self.n_qubits;  // Last qubit is ancilla
+        let mut circuit = self.build_unmeasured();
+
+        // Step 5: Measure query qubits (qubits 0..n, ahead of the ancilla)
+        circuit = circuit.measure_range(0..self.n_qubits);
 
-        let mut circuit = Circuit::new(total_qubits);
+        circuit
+    }
+
+    /// Build the circuit up to (but excluding) measurement.
+    fn build_unmeasured(&self) -> Circuit {
+        let mut circuit = Circuit::new(0);
+        let query = circuit.add_register("query", self.n_qubits);
+        let ancilla = circuit.add_register("ancilla", 1)[0];
+        let work = match self.num_work_qubits() {
+            0 => Vec::new(),
+            size => circuit.add_register("work", size).qubits().to_vec(),
+        };
 
         // Step 1: Initialize ancilla to |1⟩
         circuit = circuit.x(ancilla);
 
         // Step 2: Apply H to all qubits
-        for i in 0..total_qubits {
-            circuit = circuit.h(i);
+        for &q in query.qubits() {
+            circuit = circuit.h(q);
         }
+        circuit = circuit.h(ancilla);
 
         // Step 3: Apply the oracle
-        circuit = self.apply_oracle(circuit, ancilla);
+        circuit = self.apply_oracle(circuit, ancilla, &work);
 
         // Step 4: Apply H to query qubits (not ancilla)
-        for i in 0..self.n_qubits {
-            circuit = circuit.h(i);
-        }
-
-        // Step 5: Measure query qubits
-        for i in 0..self.n_qubits {
-            circuit = circuit.measure(i, i);
+        for &q in query.qubits() {
+            circuit = circuit.h(q);
         }
 
         circuit
     }
 
+    /// Clean ancillas the oracle needs: n - 2 for a truth table's
+    /// multi-controlled X gates, none for the built-in functions.
+    fn num_work_qubits(&self) -> usize {
+        match self.oracle {
+            Oracle::Function(_) => 0,
+            Oracle::TruthTable(_) => self.n_qubits.saturating_sub(2),
+        }
+    }
+
     /// Apply the oracle based on function type.
-    fn apply_oracle(&self, mut circuit: Circuit, ancilla: usize) -> Circuit {
-        match self.function {
+    fn apply_oracle(&self, mut circuit: Circuit, ancilla: usize, work: &[usize]) -> Circuit {
+        let function = match &self.oracle {
+            Oracle::Function(function) => *function,
+            Oracle::TruthTable(values) => return self.apply_truth_table(circuit, values, ancilla, work),
+        };
+
+        match function {
             FunctionType::ConstantZero => {
                 // f(x) = 0: do nothing (identity)
                 circuit
@@ -125,20 +304,394 @@ impl DeutschJozsa {
                 // f(x) = x_0 (first bit)
                 circuit.cx(0, ancilla)
             }
+            FunctionType::BalancedLastBit => {
+                // f(x) = x_(n-1) (last bit)
+                circuit.cx(self.n_qubits - 1, ancilla)
+            }
+            FunctionType::BalancedMask(mask) => {
+                // f(x) = parity of the masked bits: CNOT from each one
+                for i in (0..self.n_qubits).filter(|&i| (mask >> i) & 1 == 1) {
+                    circuit = circuit.cx(i, ancilla);
+                }
+                circuit
+            }
+        }
+    }
+
+    /// Create an instance whose oracle computes f(x) = `f(x)`.
+    ///
+    /// The closure is evaluated once on each of the 2^n inputs to build the
+    /// oracle, and the results are checked against the promise.
+    ///
+    /// # Errors
+    ///
+    /// [`AlgorithmError::ZeroQubits`] for an empty register,
+    /// [`AlgorithmError::TooManyQubits`] if 2^n_qubits overflows a `usize`,
+    /// and [`AlgorithmError::Unbalanced`] if f is neither constant nor
+    /// balanced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::{AlgorithmError, DeutschJozsa};
+    ///
+    /// // f(x) = 1 when x ≥ 4: balanced on 3 bits
+    /// let dj = DeutschJozsa::from_fn(3, |x| x >= 4).unwrap();
+    /// assert_eq!(
+    ///     DeutschJozsa::from_fn(3, |x| x == 5).unwrap_err(),
+    ///     AlgorithmError::Unbalanced { ones: 1, inputs: 8 }
+    /// );
+    /// ```
+    pub fn from_fn(n_qubits: usize, f: impl Fn(usize) -> bool) -> Result<Self, AlgorithmError> {
+        if n_qubits == 0 {
+            return Err(AlgorithmError::ZeroQubits);
+        }
+        if n_qubits >= usize::BITS as usize {
+            return Err(AlgorithmError::TooManyQubits { n_qubits });
         }
+
+        let values: Vec<bool> = (0..1usize << n_qubits).map(f).collect();
+        let (ones, inputs) = (values.iter().filter(|&&v| v).count(), values.len());
+        if promise_verdict(ones, inputs).is_err() {
+            return Err(AlgorithmError::Unbalanced { ones, inputs });
+        }
+
+        Ok(Self {
+            n_qubits,
+            oracle: Oracle::TruthTable(values),
+        })
+    }
+
+    /// Flip the ancilla on every input x with `values[x]` set.
+    ///
+    /// `work` holds the n - 2 clean ancillas [`Circuit::mcx`] needs.
+    fn apply_truth_table(&self, mut circuit: Circuit, values: &[bool], ancilla: usize, work: &[usize]) -> Circuit {
+        if values.iter().all(|&v| v) {
+            return circuit.x(ancilla);
+        }
+
+        let query: Vec<usize> = (0..self.n_qubits).collect();
+        for (x, _) in values.iter().enumerate().filter(|(_, &v)| v) {
+            // Map |x⟩ to |1...1⟩, flip the ancilla on all ones, map back
+            let zeros: Vec<usize> = (0..self.n_qubits).filter(|&i| (x >> i) & 1 == 0).collect();
+            for &i in &zeros {
+                circuit = circuit.x(i);
+            }
+            circuit = circuit.mcx(&query, ancilla, work);
+            for &i in &zeros {
+                circuit = circuit.x(i);
+            }
+        }
+
+        circuit
+    }
+
+    /// Simulate, classify, and explain the verdict in plain text.
+    ///
+    /// For a balanced function each dominant outcome s is listed with its
+    /// meaning: f(x) follows the parity of the query bits set in s.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::{DeutschJozsa, deutsch::FunctionType};
+    ///
+    /// let text = DeutschJozsa::new(2, FunctionType::BalancedParity).explain();
+    /// assert!(text.starts_with("Balanced"));
+    /// ```
+    pub fn explain(&self) -> String {
+        let state = Simulator::new()
+            .run(&self.build_unmeasured())
+            .expect("Deutsch-Jozsa circuits only use simulable gates");
+
+        // Marginal distribution over the query register
+        let mask = (1 << self.n_qubits) - 1;
+        let mut outcomes = vec![0.0; 1 << self.n_qubits];
+        for (index, p) in state.probabilities().into_iter().enumerate() {
+            outcomes[index & mask] += p;
+        }
+
+        let zeros = format_outcome(0, self.n_qubits);
+        if outcomes[0] > 0.5 {
+            return format!("Constant: measured {} with p = {:.3}", zeros, outcomes[0]);
+        }
+
+        let mut dominant: Vec<(usize, f64)> = outcomes
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, p)| p >= 0.01)
+            .collect();
+        dominant.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let mut text = format!("Balanced: never measured {}\nDominant outcomes:", zeros);
+        for (outcome, p) in dominant {
+            let bits: Vec<String> = (0..self.n_qubits)
+                .filter(|&i| (outcome >> i) & 1 == 1)
+                .map(|i| format!("x{}", i))
+                .collect();
+            text.push_str(&format!(
+                "\n  {} (p = {:.3}): f(x) follows the parity of {}",
+                format_outcome(outcome, self.n_qubits),
+                p,
+                bits.join(" ⊕ ")
+            ));
+        }
+        text
+    }
+
+    /// Classify a function from a measurement histogram.
+    ///
+    /// Takes counts keyed by canonical bitstrings, e.g. from real hardware.
+    /// Ideally every shot agrees; with noise the verdict is constant when
+    /// the all-zeros string holds the majority of shots. Returns `None` for
+    /// an empty histogram.
+    pub fn classify_counts(counts: &HashMap<String, usize>) -> Option<Verdict> {
+        let total: usize = counts.values().sum();
+        if total == 0 {
+            return None;
+        }
+
+        let zeros: usize = counts
+            .iter()
+            .filter(|(bits, _)| Self::is_constant(bits))
+            .map(|(_, &count)| count)
+            .sum();
+
+        Some(if 2 * zeros > total { Verdict::Constant } else { Verdict::Balanced })
+    }
+
+    /// Check that a custom oracle circuit keeps the constant-or-balanced promise.
+    ///
+    /// The oracle must use the same layout as [`build`](Self::build):
+    /// `n_qubits` query qubits followed by one ancilla, mapping
+    /// |x⟩|y⟩ → |x⟩|y ⊕ f(x)⟩; any qubits after the ancilla are work qubits
+    /// it must return to |0⟩. Each of the 2^n basis inputs is simulated
+    /// with the ancilla in |0⟩ to read off the truth table of f.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::{DeutschJozsa, deutsch::Verdict};
+    /// use homaya_core::Circuit;
+    ///
+    /// // f(x) = x1
+    /// let oracle = Circuit::new(3).cx(1, 2);
+    /// assert_eq!(DeutschJozsa::verify_oracle_circuit(&oracle, 2), Ok(Verdict::Balanced));
+    /// ```
+    pub fn verify_oracle_circuit(oracle: &Circuit, n_qubits: usize) -> Result<Verdict, PromiseViolation> {
+        let values = oracle_truth_table(oracle, n_qubits)?;
+        let ones = values.iter().filter(|&&v| v).count();
+        promise_verdict(ones, values.len())
+    }
+
+    /// Check that the oracle this instance builds keeps the promise.
+    ///
+    /// Simulates the oracle on every input, as
+    /// [`verify_oracle_circuit`](Self::verify_oracle_circuit) does, so it
+    /// checks the gates actually emitted rather than the description they
+    /// came from. Costs 2^n simulations; meant for tests and debug checks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::{DeutschJozsa, deutsch::FunctionType};
+    ///
+    /// assert!(DeutschJozsa::new(3, FunctionType::BalancedFirstBit).verify_promise());
+    /// ```
+    pub fn verify_promise(&self) -> bool {
+        Self::verify_oracle_circuit(&self.oracle_circuit(), self.n_qubits).is_ok()
+    }
+
+    /// The oracle alone, on n query qubits, the ancilla and any work qubits.
+    fn oracle_circuit(&self) -> Circuit {
+        let width = self.n_qubits + 1 + self.num_work_qubits();
+        let work: Vec<usize> = (self.n_qubits + 1..width).collect();
+        self.apply_oracle(Circuit::new(width), self.n_qubits, &work)
+    }
+
+    /// Classify a single shot, e.g. from
+    /// [`Simulator::run_with_measurements`] on [`build`](Self::build).
+    ///
+    /// All-zero query bits mean constant, anything else balanced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::{DeutschJozsa, deutsch::{FunctionType, Verdict}};
+    /// use homaya_sim::Simulator;
+    ///
+    /// let dj = DeutschJozsa::new(3, FunctionType::BalancedParity);
+    /// let (_, result) = Simulator::new().run_with_measurements(&dj.build()).unwrap();
+    /// assert_eq!(DeutschJozsa::interpret(&result), Verdict::Balanced);
+    /// ```
+    pub fn interpret(result: &MeasurementResult) -> Verdict {
+        if result.all_zero() {
+            Verdict::Constant
+        } else {
+            Verdict::Balanced
+        }
+    }
+
+    /// The outcome an ideal run of [`build`](Self::build) is most likely to
+    /// measure, worked out without simulating.
+    ///
+    /// All zeros for a constant function. Every built-in balanced function
+    /// is a parity f(x) = s · x, which interferes to exactly |s⟩: all ones
+    /// for `BalancedParity`, bit 0 for `BalancedFirstBit`, bit n-1 for
+    /// `BalancedLastBit` and the mask itself for `BalancedMask`. Other
+    /// balanced truth tables give a spread of nonzero outcomes; the most
+    /// likely one is returned, lowest value first on ties.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::{DeutschJozsa, deutsch::FunctionType};
+    ///
+    /// assert_eq!(DeutschJozsa::new(3, FunctionType::ConstantOne).expected_measurement(), "000");
+    /// assert_eq!(DeutschJozsa::new(3, FunctionType::BalancedLastBit).expected_measurement(), "100");
+    /// ```
+    pub fn expected_measurement(&self) -> String {
+        let n = self.n_qubits;
+        let outcome = match &self.oracle {
+            Oracle::Function(FunctionType::ConstantZero | FunctionType::ConstantOne) => 0,
+            Oracle::Function(FunctionType::BalancedParity) => (1 << n) - 1,
+            Oracle::Function(FunctionType::BalancedFirstBit) => 1,
+            Oracle::Function(FunctionType::BalancedLastBit) => 1 << (n - 1),
+            Oracle::Function(FunctionType::BalancedMask(mask)) => *mask,
+            Oracle::TruthTable(values) => {
+                // Amplitude of |y⟩ is Σ_x (-1)^(f(x) + x·y) / 2^n: a Walsh-Hadamard transform
+                let mut amplitudes: Vec<i64> = values.iter().map(|&v| if v { -1 } else { 1 }).collect();
+                for i in 0..n {
+                    for x in 0..amplitudes.len() {
+                        if (x >> i) & 1 == 0 {
+                            let (a, b) = (amplitudes[x], amplitudes[x | (1 << i)]);
+                            amplitudes[x] = a + b;
+                            amplitudes[x | (1 << i)] = a - b;
+                        }
+                    }
+                }
+                (0..amplitudes.len()).rev().max_by_key(|&y| amplitudes[y].abs()).unwrap_or(0)
+            }
+        };
+        format_outcome(outcome, n)
     }
 
     /// Check if the function is constant based on measurement result.
     ///
     /// Returns true if the measurement string indicates a constant function.
+    /// All zeros reads the same in either [`BitOrder`](homaya_core::BitOrder),
+    /// so this (and [`is_balanced`](Self::is_balanced)) needs no order.
     pub fn is_constant(measurement: &str) -> bool {
         measurement.chars().all(|c| c == '0')
     }
+
+    /// Check if the function is balanced based on measurement result.
+    ///
+    /// The complement of [`is_constant`](Self::is_constant): any set bit
+    /// means balanced.
+    pub fn is_balanced(measurement: &str) -> bool {
+        !Self::is_constant(measurement)
+    }
+}
+
+/// Deutsch's algorithm: the single-bit Deutsch-Jozsa circuit.
+///
+/// Qubit 0 is the query qubit, measured into classical bit 0; qubit 1 is
+/// the ancilla. Equivalent to `DeutschJozsa::new(1, function).build()`.
+/// With one input bit, `BalancedParity`, `BalancedFirstBit`,
+/// `BalancedLastBit` and `BalancedMask(1)` are all f(x) = x.
+///
+/// # Panics
+///
+/// Panics for a `BalancedMask` other than 1.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_algorithms::deutsch::{deutsch_single, deutsch_single_verdict, FunctionType, Verdict};
+/// use homaya_sim::Simulator;
+///
+/// let circuit = deutsch_single(FunctionType::BalancedFirstBit);
+/// let counts = Simulator::new().sample(&circuit, 10).unwrap();
+/// assert_eq!(counts.get("1"), Some(&10));
+/// assert_eq!(deutsch_single_verdict("1"), Some(Verdict::Balanced));
+/// ```
+pub fn deutsch_single(function: FunctionType) -> Circuit {
+    DeutschJozsa::new(1, function).build()
+}
+
+/// Interpret the outcome of [`deutsch_single`].
+///
+/// "0" means f is constant, "1" that it is balanced; anything else is not
+/// a one-bit outcome and gives `None`.
+pub fn deutsch_single_verdict(outcome: &str) -> Option<Verdict> {
+    match outcome {
+        "0" => Some(Verdict::Constant),
+        "1" => Some(Verdict::Balanced),
+        _ => None,
+    }
+}
+
+/// Read the truth table of a classical oracle |x⟩|y⟩ → |x⟩|y ⊕ f(x)⟩.
+///
+/// The oracle acts on `n_qubits` query qubits followed by one ancilla; any
+/// further qubits are work qubits that must start and end in |0⟩. Each
+/// basis input is simulated with the ancilla and work qubits in |0⟩.
+pub(crate) fn oracle_truth_table(oracle: &Circuit, n_qubits: usize) -> Result<Vec<bool>, PromiseViolation> {
+    if oracle.num_qubits() < n_qubits + 1 {
+        return Err(PromiseViolation::Simulation(HomayaError::QubitMismatch {
+            expected: n_qubits + 1,
+            got: oracle.num_qubits(),
+        }));
+    }
+
+    let inputs = 1usize << n_qubits;
+    let mut sim = Simulator::new();
+    (0..inputs)
+        .map(|x| {
+            let mut circuit = Circuit::new(oracle.num_qubits());
+            for i in (0..n_qubits).filter(|&i| (x >> i) & 1 == 1) {
+                circuit = circuit.x(i);
+            }
+            let circuit = circuit.compose(oracle).map_err(PromiseViolation::Simulation)?;
+            let state = sim.run(&circuit).map_err(PromiseViolation::Simulation)?;
+
+            // A classical oracle leaves a single basis state with x intact and clean work qubits
+            let outcome = (0..state.dimension()).find(|&i| state.probability(i) > 1.0 - 1e-9);
+            match outcome {
+                Some(index) if index & (inputs - 1) == x && index >> (n_qubits + 1) == 0 => {
+                    Ok(index >> n_qubits == 1)
+                }
+                _ => Err(PromiseViolation::NotClassical { input: x }),
+            }
+        })
+        .collect()
+}
+
+/// The verdict for a function with `ones` of `inputs` values set, if it
+/// keeps the promise.
+fn promise_verdict(ones: usize, inputs: usize) -> Result<Verdict, PromiseViolation> {
+    match ones {
+        0 => Ok(Verdict::Constant),
+        _ if ones == inputs => Ok(Verdict::Constant),
+        _ if 2 * ones == inputs => Ok(Verdict::Balanced),
+        _ => Err(PromiseViolation::Unbalanced { ones, inputs }),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use homaya_core::GateType;
+
+    #[test]
+    fn test_try_new_zero_qubits() {
+        assert_eq!(
+            DeutschJozsa::try_new(0, FunctionType::BalancedParity).unwrap_err(),
+            AlgorithmError::ZeroQubits
+        );
+        assert!(DeutschJozsa::try_new(1, FunctionType::ConstantOne).is_ok());
+    }
 
     #[test]
     fn test_circuit_size() {
@@ -155,6 +708,8 @@ mod tests {
             FunctionType::ConstantOne,
             FunctionType::BalancedParity,
             FunctionType::BalancedFirstBit,
+            FunctionType::BalancedLastBit,
+            FunctionType::BalancedMask(0b10),
         ] {
             let dj = DeutschJozsa::new(2, func);
             let circuit = dj.build();
@@ -162,11 +717,333 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mask_oracle_gates() {
+        let oracle = DeutschJozsa::new(3, FunctionType::BalancedMask(0b101)).oracle_circuit();
+        let pairs: Vec<&[usize]> = oracle.instructions().iter().map(|inst| inst.qubits.as_slice()).collect();
+        assert_eq!(pairs, [[0, 3], [2, 3]]);
+
+        let last = DeutschJozsa::new(3, FunctionType::BalancedLastBit).oracle_circuit();
+        assert_eq!(last.instructions()[0].qubits, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_mask_must_be_nonzero_and_fit() {
+        assert_eq!(
+            DeutschJozsa::try_new(3, FunctionType::BalancedMask(0)).unwrap_err(),
+            AlgorithmError::InvalidMask { mask: 0, n_qubits: 3 }
+        );
+        assert!(DeutschJozsa::try_new(3, FunctionType::BalancedMask(0b1000)).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Mask 0 makes f constant")]
+    fn test_zero_mask_panics() {
+        DeutschJozsa::new(2, FunctionType::BalancedMask(0));
+    }
+
+    #[test]
+    fn test_truth_table_matches_builtin() {
+        // f(x) = x0 on 3 qubits
+        let table: Vec<bool> = (0..8).map(|x| x & 1 == 1).collect();
+        let from_table = DeutschJozsa::from_truth_table(&table);
+        let builtin = DeutschJozsa::new(3, FunctionType::BalancedFirstBit);
+
+        // The table's circuit is wider (one work qubit), but measures the same
+        let mut sim = Simulator::new();
+        assert_eq!(from_table.build().num_qubits(), 5);
+        assert!((sim.success_probability(&from_table.build(), "001").unwrap() - 1.0).abs() < 1e-10);
+        assert!((sim.success_probability(&builtin.build(), "001").unwrap() - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_truth_table_oracle_uses_toffoli_ladder() {
+        // f(x) = 1 on the upper half of 5-bit inputs
+        let table: Vec<bool> = (0..32).map(|x| x >= 16).collect();
+        let dj = DeutschJozsa::from_truth_table(&table);
+        let circuit = dj.build();
+
+        let names: Vec<&str> = circuit.registers().iter().map(|reg| reg.name()).collect();
+        assert_eq!(names, ["query", "ancilla", "work"]);
+        assert_eq!(circuit.register("work").unwrap().len(), 3);
+
+        // Each marked input: X on its zero bits, then 2n - 3 = 7 CCX gates
+        let oracle = dj.oracle_circuit();
+        assert!(oracle.gates().all(|gate| matches!(gate.gate_type, GateType::X | GateType::CCX)));
+        let zero_bits: usize = (16..32usize).map(|x| 5 - x.count_ones() as usize).sum();
+        assert_eq!(oracle.gate_count(), 16 * 7 + 2 * zero_bits);
+        assert!(dj.verify_promise());
+    }
+
+    #[test]
+    fn test_verify_rejects_dirty_work_qubit() {
+        // f(x) = x0, but the work qubit is left flipped
+        let oracle = Circuit::new(3).cx(0, 1).x(2);
+        assert_eq!(
+            DeutschJozsa::verify_oracle_circuit(&oracle, 1),
+            Err(PromiseViolation::NotClassical { input: 0 })
+        );
+    }
+
+    #[test]
+    fn test_truth_table_verdicts() {
+        let balanced = DeutschJozsa::from_truth_table(&[false, true, true, false]);
+        assert!(balanced.explain().starts_with("Balanced"));
+
+        let constant = DeutschJozsa::from_truth_table(&[true; 4]);
+        assert!(constant.explain().starts_with("Constant"));
+    }
+
+    #[test]
+    #[should_panic(expected = "f(x) = 1 on 3 of 4 inputs")]
+    fn test_truth_table_breaking_promise() {
+        DeutschJozsa::from_truth_table(&[true, true, true, false]);
+    }
+
+    #[test]
+    fn test_explain_balanced_first_bit() {
+        let text = DeutschJozsa::new(3, FunctionType::BalancedFirstBit).explain();
+        assert!(text.starts_with("Balanced"));
+        assert!(text.contains("001 (p = 1.000): f(x) follows the parity of x0"), "{}", text);
+    }
+
+    #[test]
+    fn test_explain_constant() {
+        let text = DeutschJozsa::new(3, FunctionType::ConstantOne).explain();
+        assert_eq!(text, "Constant: measured 000 with p = 1.000");
+    }
+
+    #[test]
+    fn test_classify_counts() {
+        let constant = HashMap::from([("000".to_string(), 950), ("100".to_string(), 50)]);
+        assert_eq!(DeutschJozsa::classify_counts(&constant), Some(Verdict::Constant));
+
+        let balanced = HashMap::from([("001".to_string(), 600), ("000".to_string(), 400)]);
+        assert_eq!(DeutschJozsa::classify_counts(&balanced), Some(Verdict::Balanced));
+
+        assert_eq!(DeutschJozsa::classify_counts(&HashMap::new()), None);
+    }
+
+    #[test]
+    fn test_verify_oracle_circuit() {
+        // f(x) = x0 ⊕ x2
+        let balanced = Circuit::new(4).cx(0, 3).cx(2, 3);
+        assert_eq!(DeutschJozsa::verify_oracle_circuit(&balanced, 3), Ok(Verdict::Balanced));
+
+        let constant_one = Circuit::new(4).x(3);
+        assert_eq!(DeutschJozsa::verify_oracle_circuit(&constant_one, 3), Ok(Verdict::Constant));
+    }
+
+    #[test]
+    fn test_built_in_functions_keep_promise() {
+        use FunctionType::*;
+
+        for (function, verdict) in [
+            (ConstantZero, Verdict::Constant),
+            (ConstantOne, Verdict::Constant),
+            (BalancedParity, Verdict::Balanced),
+            (BalancedFirstBit, Verdict::Balanced),
+            (BalancedLastBit, Verdict::Balanced),
+            (BalancedMask(0b110), Verdict::Balanced),
+        ] {
+            let dj = DeutschJozsa::new(3, function);
+            assert!(dj.verify_promise(), "{:?}", function);
+            assert_eq!(DeutschJozsa::verify_oracle_circuit(&dj.oracle_circuit(), 3), Ok(verdict));
+        }
+
+        let table = DeutschJozsa::from_truth_table(&[true, false, false, true, false, true, true, false]);
+        assert!(table.verify_promise());
+    }
+
+    #[test]
+    fn test_verify_oracle_circuit_violations() {
+        // f(x) = x0 AND x1 is 1 on one input of four
+        let and = Circuit::new(3).ccx(0, 1, 2);
+        assert_eq!(
+            DeutschJozsa::verify_oracle_circuit(&and, 2),
+            Err(PromiseViolation::Unbalanced { ones: 1, inputs: 4 })
+        );
+
+        // Puts the ancilla in superposition instead of flipping it
+        let quantum = Circuit::new(3).h(2);
+        assert_eq!(
+            DeutschJozsa::verify_oracle_circuit(&quantum, 2),
+            Err(PromiseViolation::NotClassical { input: 0 })
+        );
+
+        assert!(matches!(
+            DeutschJozsa::verify_oracle_circuit(&Circuit::new(2), 2),
+            Err(PromiseViolation::Simulation(_))
+        ));
+    }
+
     #[test]
     fn test_is_constant() {
         assert!(DeutschJozsa::is_constant("000"));
         assert!(DeutschJozsa::is_constant("0000"));
         assert!(!DeutschJozsa::is_constant("001"));
         assert!(!DeutschJozsa::is_constant("100"));
+        assert!(DeutschJozsa::is_balanced("010"));
+        assert!(!DeutschJozsa::is_balanced("000"));
+    }
+
+    #[test]
+    fn test_from_fn_constant_and_parity() {
+        let sample = |dj: &DeutschJozsa| {
+            let counts = Simulator::with_seed(4).sample(&dj.build(), 20).unwrap();
+            assert_eq!(counts.len(), 1);
+            counts.into_keys().next().unwrap()
+        };
+
+        let constant = DeutschJozsa::from_fn(3, |_| true).unwrap();
+        assert!(DeutschJozsa::is_constant(&sample(&constant)));
+
+        // Parity is balanced and interferes to all ones
+        let parity = DeutschJozsa::from_fn(3, |x| x.count_ones() % 2 == 1).unwrap();
+        let bits = sample(&parity);
+        assert!(DeutschJozsa::is_balanced(&bits));
+        assert_eq!(bits, "111");
+    }
+
+    #[test]
+    fn test_interpret_measurement_result() {
+        let zeros = MeasurementResult::from_bitstring("000").unwrap();
+        assert!(zeros.all_zero());
+        assert_eq!(DeutschJozsa::interpret(&zeros), Verdict::Constant);
+
+        let one_set = MeasurementResult::from_bitstring("010").unwrap();
+        assert_eq!(DeutschJozsa::interpret(&one_set), Verdict::Balanced);
+
+        for (function, verdict) in [(FunctionType::ConstantOne, Verdict::Constant), (FunctionType::BalancedLastBit, Verdict::Balanced)] {
+            let (_, result) = Simulator::new().run_with_measurements(&DeutschJozsa::new(3, function).build()).unwrap();
+            assert_eq!(DeutschJozsa::interpret(&result), verdict);
+        }
+    }
+
+    #[test]
+    fn test_deutsch_single_one_query() {
+        use FunctionType::*;
+
+        for (function, verdict) in [
+            (ConstantZero, Verdict::Constant),
+            (ConstantOne, Verdict::Constant),
+            (BalancedFirstBit, Verdict::Balanced),
+            (BalancedMask(1), Verdict::Balanced),
+        ] {
+            let circuit = deutsch_single(function);
+            assert_eq!(circuit.num_qubits(), 2);
+
+            let counts = Simulator::with_seed(3).sample(&circuit, 50).unwrap();
+            assert_eq!(counts.len(), 1, "{:?}: {:?}", function, counts);
+            let outcome = counts.keys().next().unwrap();
+            assert_eq!(deutsch_single_verdict(outcome), Some(verdict), "{:?}", function);
+        }
+        assert_eq!(deutsch_single_verdict("01"), None);
+    }
+
+    #[test]
+    fn test_from_fn_rejects_broken_promise() {
+        assert_eq!(
+            DeutschJozsa::from_fn(2, |x| x == 0).unwrap_err(),
+            AlgorithmError::Unbalanced { ones: 1, inputs: 4 }
+        );
+    }
+
+    #[test]
+    fn test_from_fn_rejects_bad_widths() {
+        // Neither width may evaluate f at all
+        let never = |_: usize| -> bool { unreachable!("f must not be called") };
+        assert_eq!(DeutschJozsa::from_fn(0, never).unwrap_err(), AlgorithmError::ZeroQubits);
+        let wide = usize::BITS as usize;
+        assert_eq!(
+            DeutschJozsa::from_fn(wide, never).unwrap_err(),
+            AlgorithmError::TooManyQubits { n_qubits: wide }
+        );
+    }
+
+    #[test]
+    fn test_expected_measurement_is_modal_outcome() {
+        use FunctionType::*;
+
+        for function in [ConstantZero, ConstantOne, BalancedParity, BalancedFirstBit, BalancedLastBit, BalancedMask(0b101)] {
+            let dj = DeutschJozsa::new(3, function);
+            let counts = Simulator::with_seed(6).sample(&dj.build(), 30).unwrap();
+            assert_eq!(counts.get(&dj.expected_measurement()), Some(&30), "{:?}: {:?}", function, counts);
+        }
+
+        // f(x) = x0 AND x1 XOR x2 is balanced but not a parity: "100" and
+        // "101" to "111" each come up a quarter of the time
+        let table = DeutschJozsa::from_fn(3, |x| (x & 1 == 1 && x & 2 == 2) ^ (x & 4 == 4)).unwrap();
+        assert_eq!(table.expected_measurement(), "100");
+        let probability = Simulator::new().success_probability(&table.build(), "100").unwrap();
+        assert!((probability - 0.25).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_build_names_registers() {
+        let circuit = DeutschJozsa::new(3, FunctionType::BalancedParity).build();
+        assert_eq!(circuit.register("query").unwrap().qubits(), &[0, 1, 2]);
+        assert_eq!(circuit.register("ancilla").unwrap()[0], 3);
+        assert!(circuit.to_qasm().contains("qreg query[3];\nqreg ancilla[1];\ncreg c[3];\nx ancilla[0];"));
+    }
+
+    #[test]
+    fn test_gate_count_is_linear() {
+        use FunctionType::*;
+
+        for n in 1..=32 {
+            // X and n+1 H, the oracle, then n H
+            for (function, oracle) in [(ConstantZero, 0), (ConstantOne, 1), (BalancedFirstBit, 1), (BalancedParity, n)] {
+                let expected = 2 * n + 2 + oracle;
+                assert_eq!(DeutschJozsa::new(n, function).build().gate_count(), expected, "n = {}, {:?}", n, function);
+            }
+        }
+    }
+
+    use proptest::prelude::*;
+
+    /// A DJ instance on 1..=6 qubits and whether its function is constant.
+    ///
+    /// Built-in functions with random masks, and random balanced truth
+    /// tables, whose one-per-input oracles lean on the multi-controlled X.
+    fn any_instance() -> impl Strategy<Value = (DeutschJozsa, bool)> {
+        use FunctionType::*;
+
+        (1usize..=6).prop_flat_map(|n| {
+            let built_in = prop_oneof![
+                Just(ConstantZero),
+                Just(ConstantOne),
+                Just(BalancedParity),
+                Just(BalancedFirstBit),
+                Just(BalancedLastBit),
+                (1..1usize << n).prop_map(BalancedMask),
+            ]
+            .prop_map(move |function| {
+                let constant = matches!(function, ConstantZero | ConstantOne);
+                (DeutschJozsa::new(n, function), constant)
+            });
+            let table = Just((0..1usize << n).collect::<Vec<_>>()).prop_shuffle().prop_map(|order| {
+                // The first half of a random order maps to 1
+                let mut values = vec![false; order.len()];
+                for &x in &order[..order.len() / 2] {
+                    values[x] = true;
+                }
+                (DeutschJozsa::from_truth_table(&values), false)
+            });
+            prop_oneof![built_in, table]
+        })
+    }
+
+    proptest! {
+        #![proptest_config(crate::proptest_config())]
+
+        #[test]
+        fn prop_distinguishes_constant_from_balanced((dj, constant) in any_instance()) {
+            let circuit = dj.build();
+            let zeros = "0".repeat(circuit.num_clbits());
+            let p = Simulator::new().success_probability(&circuit, &zeros).unwrap();
+            let expected = if constant { 1.0 } else { 0.0 };
+            prop_assert!((p - expected).abs() < 1e-9, "{:?}: P(all zero) = {}", dj, p);
+        }
     }
 }
@@ -0,0 +1,134 @@
+//! Errors from algorithm constructors.
+
+use std::fmt;
+
+/// Invalid arguments to an algorithm constructor.
+///
+/// Returned by the `try_new` constructors; the matching `new` panics with
+/// the same message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlgorithmError {
+    /// The algorithm needs at least one qubit
+    ZeroQubits,
+    /// A search target doesn't fit in the register
+    TargetOutOfRange {
+        /// The requested target
+        target: usize,
+        /// Register width
+        n_qubits: usize,
+    },
+    /// A hidden string doesn't fit in the register
+    SecretOutOfRange {
+        /// The requested secret
+        secret: usize,
+        /// Register width
+        n_qubits: usize,
+    },
+    /// A Deutsch-Jozsa mask that is zero or doesn't fit in the register
+    InvalidMask {
+        /// The requested mask
+        mask: usize,
+        /// Register width
+        n_qubits: usize,
+    },
+    /// More qubits than a `usize` secret or target can address
+    TooManyQubits {
+        /// Requested register width
+        n_qubits: usize,
+    },
+    /// A bitstring argument contains something other than `0` and `1`
+    InvalidBitstring {
+        /// Character position, counting from the left
+        index: usize,
+        /// The offending character
+        found: char,
+    },
+    /// A modulus too small to have a nontrivial multiplicative order
+    InvalidModulus {
+        /// The requested modulus
+        modulus: usize,
+    },
+    /// A supplied oracle circuit has the wrong number of qubits
+    OracleWidth {
+        /// Qubits the algorithm needs the oracle to act on
+        expected: usize,
+        /// Qubits the circuit has
+        found: usize,
+    },
+    /// A supplied oracle circuit measures or resets a qubit
+    NonUnitaryOracle,
+    /// A Deutsch-Jozsa function that is neither constant nor balanced
+    Unbalanced {
+        /// Number of inputs with f(x) = 1
+        ones: usize,
+        /// Total number of inputs, 2^n
+        inputs: usize,
+    },
+    /// A period-finding base outside 2..N or sharing a factor with N
+    InvalidBase {
+        /// The requested base
+        a: usize,
+        /// The modulus N
+        modulus: usize,
+    },
+}
+
+impl fmt::Display for AlgorithmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::ZeroQubits => write!(f, "Need at least 1 qubit"),
+            Self::TargetOutOfRange { target, n_qubits } => write!(
+                f,
+                "Target {} is too large for {} qubits (max: {})",
+                target,
+                n_qubits,
+                (1usize << n_qubits) - 1
+            ),
+            Self::SecretOutOfRange { secret, n_qubits } => write!(
+                f,
+                "Secret {} is too large for {} qubits (max: {})",
+                secret,
+                n_qubits,
+                (1usize << n_qubits) - 1
+            ),
+            Self::InvalidMask { mask: 0, .. } => {
+                write!(f, "Mask 0 makes f constant, not balanced")
+            }
+            Self::InvalidMask { mask, n_qubits } => write!(
+                f,
+                "Mask {:#b} is too large for {} qubits (max: {:#b})",
+                mask,
+                n_qubits,
+                (1usize << n_qubits) - 1
+            ),
+            Self::TooManyQubits { n_qubits } => write!(
+                f,
+                "{} qubits is too many (max: {})",
+                n_qubits,
+                usize::BITS
+            ),
+            Self::InvalidBitstring { index, found } => write!(
+                f,
+                "Bitstring may only contain '0' and '1', found {:?} at position {}",
+                found, index
+            ),
+            Self::OracleWidth { expected, found } => {
+                write!(f, "Oracle acts on {} qubits, expected {}", found, expected)
+            }
+            Self::NonUnitaryOracle => write!(f, "Oracle must not measure or reset qubits"),
+            Self::Unbalanced { ones, inputs } => write!(
+                f,
+                "f(x) = 1 on {} of {} inputs: neither constant nor balanced",
+                ones, inputs
+            ),
+            Self::InvalidModulus { modulus } => write!(f, "Modulus {} is too small (min: 3)", modulus),
+            Self::InvalidBase { a, modulus } => write!(
+                f,
+                "Base {} must lie in 2..{} and be coprime to {}",
+                a, modulus, modulus
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AlgorithmError {}
@@ -34,23 +34,164 @@
 //!
 //! P(target) = sin²((2k + 1)θ)
 //!
-//! where θ = arcsin(1/√N) and optimal k ≈ π√N/4
+//! where θ = arcsin(1/√N) and optimal k ≈ π√N/4. With M marked states
+//! θ = arcsin(√(M/N)) instead.
 //!
 
-use homaya_core::{Circuit, PI};
+use std::collections::HashMap;
+use std::fmt;
+
+use homaya_core::{most_likely_outcome, Circuit, GateType, PI};
+use homaya_sim::Simulator;
+
+use crate::amplification::reflect_about_prepared_on;
+use crate::oracle::marking_oracle;
+use crate::AlgorithmError;
+
+/// Block label of each oracle call in a built Grover circuit.
+pub const ORACLE_BLOCK: &str = "oracle";
+
+/// Slack added to π/4θ before flooring in [`GroverSearch::optimal_iterations`].
+///
+/// arcsin(√½) rounds one ulp above π/4, so half the space marked would give
+/// 0.999... iterations and floor to 0 instead of 1. Far below the gap
+/// between π/4θ and the next integer for any register `usize` can index.
+const ITERATION_SLACK: f64 = 1e-9;
+
+/// Reject registers too narrow to search or too wide to index with a `usize`.
+fn check_width(n_qubits: usize) -> Result<(), AlgorithmError> {
+    if n_qubits == 0 {
+        return Err(AlgorithmError::ZeroQubits);
+    }
+    if n_qubits >= usize::BITS as usize {
+        return Err(AlgorithmError::TooManyQubits { n_qubits });
+    }
+    Ok(())
+}
+
+/// Which states the oracle marks.
+#[derive(Debug, Clone)]
+enum Marking {
+    /// States matching fixed values on some qubits (all qubits for one target)
+    Pattern {
+        /// Qubits whose value the oracle checks
+        mask: usize,
+        /// Required values on those qubits
+        value: usize,
+    },
+    /// An explicit list of distinct states
+    States(Vec<usize>),
+}
+
+/// Diagnostics about how well Grover's algorithm suits a search.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroverAnalysis {
+    /// Search space size N
+    pub search_space: usize,
+    /// Number of marked states M
+    pub num_marked: usize,
+    /// Iterations the circuit will use
+    pub iterations: usize,
+    /// Probability of measuring a marked state
+    pub success_probability: f64,
+    /// More than half the space is marked (M > N/2).
+    ///
+    /// Each Grover iteration then rotates past the marked subspace, so
+    /// amplification only hurts. Sampling uniformly already succeeds with
+    /// probability M/N > 1/2 - use classical search instead.
+    pub too_many_solutions: bool,
+}
+
+/// A Grover search configured so that it does worse than it could.
+///
+/// Returned by [`GroverSearch::try_with_iterations`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroverError {
+    /// More iterations than the optimum, which lowers the success probability
+    Overshoot {
+        /// Requested iterations
+        iterations: usize,
+        /// Optimal iterations
+        optimal: usize,
+        /// Success probability with the requested iterations
+        success_probability: f64,
+        /// Success probability with the optimal iterations
+        optimal_probability: f64,
+    },
+    /// More than half the space is marked, so any iteration hurts
+    TooManySolutions {
+        /// Number of marked states M
+        num_marked: usize,
+        /// Search space size N
+        search_space: usize,
+    },
+}
+
+impl fmt::Display for GroverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overshoot {
+                iterations,
+                optimal,
+                success_probability,
+                optimal_probability,
+            } => write!(
+                f,
+                "{} iterations overshoot the optimal {}: success probability drops from {:.3} to {:.3}",
+                iterations, optimal, optimal_probability, success_probability
+            ),
+            Self::TooManySolutions { num_marked, search_space } => write!(
+                f,
+                "{} of {} states are marked: Grover iterations only lower the success probability, sample uniformly instead",
+                num_marked, search_space
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GroverError {}
+
+/// One point on a Grover success-probability curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurvePoint {
+    /// Number of Grover iterations
+    pub iteration: usize,
+    /// Analytic probability of measuring a marked state
+    pub success_probability: f64,
+    /// Depth of the circuit built with this many iterations
+    pub depth: usize,
+}
+
+impl CurvePoint {
+    /// Column names for [`to_csv`](Self::to_csv) rows.
+    pub const CSV_HEADER: &'static str = "iteration,success_probability,depth";
+
+    /// Format as a CSV row matching [`CSV_HEADER`](Self::CSV_HEADER).
+    pub fn to_csv(&self) -> String {
+        format!("{},{},{}", self.iteration, self.success_probability, self.depth)
+    }
+}
 
 /// Grover's Search algorithm builder.
 ///
 /// Creates a quantum circuit that searches for a specific item
 /// in an unsorted database with quadratic speedup.
+///
+/// Qubits `0..n` are the search register. From four qubits up, the
+/// multi-controlled Z gates in the oracle and diffuser are Toffoli ladders
+/// ([`Circuit::mcz`]) that borrow n - 3 clean ancillas, qubits
+/// `n..total_qubits()`; they start and end every iteration in |0⟩.
 #[derive(Debug, Clone)]
 pub struct GroverSearch {
     /// Number of qubits (search space = 2^n_qubits)
     n_qubits: usize,
-    /// The item we're searching for (0 to 2^n_qubits - 1)
-    target: usize,
+    /// The states the oracle marks
+    marking: Marking,
     /// Number of Grover iterations (auto-calculated if None)
     iterations: Option<usize>,
+    /// Start state preparation replacing H on every qubit
+    initial_state: Option<Circuit>,
 }
 
 impl GroverSearch {
@@ -63,7 +204,9 @@ impl GroverSearch {
     ///
     /// # Panics
     ///
-    /// Panics if target >= 2^n_qubits
+    /// Panics if n_qubits is 0, 2^n_qubits overflows a `usize`, or
+    /// target >= 2^n_qubits; see [`try_new`](Self::try_new) for a
+    /// non-panicking version.
     ///
     /// # Example
     ///
@@ -73,22 +216,229 @@ impl GroverSearch {
     /// let grover = GroverSearch::new(4, 11);  // Search for 11 in 16 items
     /// ```
     pub fn new(n_qubits: usize, target: usize) -> Self {
+        Self::try_new(n_qubits, target).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Create a new Grover search instance, validating the arguments.
+    ///
+    /// # Errors
+    ///
+    /// [`AlgorithmError::ZeroQubits`] for an empty register,
+    /// [`AlgorithmError::TooManyQubits`] if 2^n_qubits overflows a `usize`,
+    /// and [`AlgorithmError::TargetOutOfRange`] if target >= 2^n_qubits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::{AlgorithmError, GroverSearch};
+    ///
+    /// assert!(GroverSearch::try_new(3, 7).is_ok());
+    /// assert_eq!(
+    ///     GroverSearch::try_new(3, 8).unwrap_err(),
+    ///     AlgorithmError::TargetOutOfRange { target: 8, n_qubits: 3 }
+    /// );
+    /// ```
+    pub fn try_new(n_qubits: usize, target: usize) -> Result<Self, AlgorithmError> {
+        check_width(n_qubits)?;
+        if target.checked_shr(n_qubits as u32).unwrap_or(0) != 0 {
+            return Err(AlgorithmError::TargetOutOfRange { target, n_qubits });
+        }
+
+        Ok(Self {
+            n_qubits,
+            marking: Marking::Pattern {
+                mask: (1 << n_qubits) - 1,
+                value: target,
+            },
+            iterations: None,
+            initial_state: None,
+        })
+    }
+
+    /// Search for any of several targets.
+    ///
+    /// Duplicates are ignored. Check [`analysis`](Self::analysis) when many
+    /// states are marked: beyond half the space Grover stops helping.
+    ///
+    /// # Panics
+    ///
+    /// Panics if n_qubits is 0 or 2^n_qubits overflows a `usize` (the
+    /// checks of [`try_new`](Self::try_new)), if `targets` is empty, or if a
+    /// target >= 2^n_qubits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::GroverSearch;
+    ///
+    /// let grover = GroverSearch::with_targets(3, &[1, 6]);
+    /// assert_eq!(grover.num_marked(), 2);
+    /// ```
+    pub fn with_targets(n_qubits: usize, targets: &[usize]) -> Self {
+        check_width(n_qubits).unwrap_or_else(|err| panic!("{}", err));
+        assert!(!targets.is_empty(), "Need at least one target to search for");
+
         let max_target = 1 << n_qubits;
-        assert!(
-            target < max_target,
-            "Target {} is too large for {} qubits (max: {})",
-            target,
+        for &target in targets {
+            assert!(
+                target < max_target,
+                "Target {} is too large for {} qubits (max: {})",
+                target,
+                n_qubits,
+                max_target - 1
+            );
+        }
+
+        let mut states = targets.to_vec();
+        states.sort_unstable();
+        states.dedup();
+
+        Self {
             n_qubits,
-            max_target - 1
+            marking: Marking::States(states),
+            iterations: None,
+            initial_state: None,
+        }
+    }
+
+    /// Search for every state satisfying a predicate.
+    ///
+    /// Evaluates `predicate` on all 2^n indices up front, so the marked set
+    /// and [`num_marked`](Self::num_marked) are known before building.
+    ///
+    /// # Panics
+    ///
+    /// Panics if n_qubits is 0 or 2^n_qubits overflows a `usize` (the
+    /// checks of [`try_new`](Self::try_new)), or if no index satisfies
+    /// `predicate`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::GroverSearch;
+    ///
+    /// // Multiples of three in 0..16
+    /// let grover = GroverSearch::with_predicate(4, |x| x % 3 == 0);
+    /// assert_eq!(grover.num_marked(), 6);
+    /// ```
+    pub fn with_predicate(n_qubits: usize, predicate: impl Fn(usize) -> bool) -> Self {
+        check_width(n_qubits).unwrap_or_else(|err| panic!("{}", err));
+        let states: Vec<usize> = (0..1usize << n_qubits).filter(|&x| predicate(x)).collect();
+        assert!(!states.is_empty(), "No state satisfies the predicate, nothing to find");
+
+        Self {
+            n_qubits,
+            marking: Marking::States(states),
+            iterations: None,
+            initial_state: None,
+        }
+    }
+
+    /// Search for every state matching a bit pattern.
+    ///
+    /// The pattern is written like a canonical bitstring (qubit 0 is the
+    /// rightmost character) using `0`, `1` and `.` for "either". Only the
+    /// fixed positions are checked by the oracle, so a pattern with k
+    /// wildcards marks 2^k states.
+    ///
+    /// # Panics
+    ///
+    /// Panics if n_qubits is 0 (so the pattern is empty) or 2^n_qubits
+    /// overflows a `usize`, if the pattern isn't `n_qubits` long, or if it
+    /// contains other characters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::GroverSearch;
+    ///
+    /// // Marks 100 and 110
+    /// let grover = GroverSearch::with_pattern(3, "1.0");
+    /// assert_eq!(grover.num_marked(), 2);
+    /// ```
+    pub fn with_pattern(n_qubits: usize, pattern: &str) -> Self {
+        check_width(n_qubits).unwrap_or_else(|err| panic!("{}", err));
+        assert!(
+            pattern.chars().count() == n_qubits,
+            "Pattern {:?} has {} characters but there are {} qubits",
+            pattern,
+            pattern.chars().count(),
+            n_qubits
         );
 
+        let mut fixed_mask = 0;
+        let mut fixed_value = 0;
+        // The last character is qubit 0
+        for (qubit, c) in pattern.chars().rev().enumerate() {
+            match c {
+                '0' => fixed_mask |= 1 << qubit,
+                '1' => {
+                    fixed_mask |= 1 << qubit;
+                    fixed_value |= 1 << qubit;
+                }
+                '.' => {}
+                _ => panic!("Pattern {:?} may only contain '0', '1' and '.'", pattern),
+            }
+        }
+
         Self {
             n_qubits,
-            target,
+            marking: Marking::Pattern {
+                mask: fixed_mask,
+                value: fixed_value,
+            },
             iterations: None,
+            initial_state: None,
         }
     }
 
+    /// Start from the state `prep` prepares instead of the uniform superposition.
+    ///
+    /// `prep` replaces the layer of H gates, and the diffuser reflects about
+    /// the prepared state, A · (2|0⟩⟨0| - I) · A†, as in
+    /// [`amplitude_amplification`](crate::amplification::amplitude_amplification).
+    /// A start that already leans towards the targets needs fewer iterations.
+    ///
+    /// [`rotation_angle`](Self::rotation_angle),
+    /// [`optimal_iterations`](Self::optimal_iterations) and
+    /// [`success_probability`](Self::success_probability) still assume the
+    /// uniform start, so they are only estimates afterwards; choose the
+    /// iteration count with [`with_iterations`](Self::with_iterations) or
+    /// check the result by simulation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prep` acts on a different number of qubits, or measures
+    /// or resets a qubit (the reflection needs its inverse).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::GroverSearch;
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// // Same as the default start: H on both qubits
+    /// let grover = GroverSearch::new(2, 3).with_initial_state(Circuit::new(2).h(0).h(1));
+    /// let p = Simulator::new().success_probability(&grover.build(), "11").unwrap();
+    /// assert!((p - 1.0).abs() < 1e-10);
+    /// ```
+    pub fn with_initial_state(mut self, prep: Circuit) -> Self {
+        assert_eq!(
+            prep.num_qubits(),
+            self.n_qubits,
+            "initial state prepares {} qubits, the search has {}",
+            prep.num_qubits(),
+            self.n_qubits
+        );
+        assert!(
+            prep.gates().all(|gate| !matches!(gate.gate_type, GateType::Measure | GateType::Reset)),
+            "initial state preparation must be unitary"
+        );
+        self.initial_state = Some(prep);
+        self
+    }
+
     /// Set a custom number of iterations.
     ///
     /// By default, the optimal number is calculated automatically.
@@ -98,68 +448,270 @@ impl GroverSearch {
         self
     }
 
+    /// Set the number of iterations, rejecting counts that hurt.
+    ///
+    /// Each iteration past [`optimal_iterations`](Self::optimal_iterations)
+    /// rotates the state beyond the marked subspace, so more iterations
+    /// give *worse* results. Use [`with_iterations`](Self::with_iterations)
+    /// to overshoot deliberately.
+    ///
+    /// # Errors
+    ///
+    /// [`GroverError::TooManySolutions`] if any iterations are requested
+    /// while more than half the space is marked, otherwise
+    /// [`GroverError::Overshoot`] if `k` succeeds less often than the optimum.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::GroverSearch;
+    ///
+    /// // 8 items: 2 iterations is optimal, 3 overshoots
+    /// assert!(GroverSearch::new(3, 5).try_with_iterations(2).is_ok());
+    /// assert!(GroverSearch::new(3, 5).try_with_iterations(3).is_err());
+    /// ```
+    pub fn try_with_iterations(self, k: usize) -> Result<Self, GroverError> {
+        let search_space = 1 << self.n_qubits;
+        let num_marked = self.num_marked();
+        if k > 0 && 2 * num_marked > search_space {
+            return Err(GroverError::TooManySolutions { num_marked, search_space });
+        }
+
+        let optimal = self.optimal_iterations();
+        let success_probability = self.success_probability_at(k);
+        let optimal_probability = self.success_probability_at(optimal);
+        if k > optimal && success_probability < optimal_probability {
+            return Err(GroverError::Overshoot {
+                iterations: k,
+                optimal,
+                success_probability,
+                optimal_probability,
+            });
+        }
+
+        Ok(self.with_iterations(k))
+    }
+
+    /// Number of clean ancillas the circuits use: n - 3 from four qubits up.
+    pub fn num_ancillas(&self) -> usize {
+        self.n_qubits.saturating_sub(3)
+    }
+
+    /// Total number of qubits: the search register plus ancillas.
+    pub fn total_qubits(&self) -> usize {
+        self.n_qubits + self.num_ancillas()
+    }
+
+    /// Global indices of the ancillas, right after the search register.
+    fn ancillas(&self) -> Vec<usize> {
+        (self.n_qubits..self.total_qubits()).collect()
+    }
+
+    /// Number of states the oracle marks.
+    pub fn num_marked(&self) -> usize {
+        match &self.marking {
+            Marking::Pattern { mask, .. } => 1 << (self.n_qubits - mask.count_ones() as usize),
+            Marking::States(states) => states.len(),
+        }
+    }
+
+    /// Rotation angle θ = arcsin(√(M/N)) per half Grover iteration.
+    ///
+    /// In the plane spanned by the unmarked and marked superpositions, the
+    /// uniform start state sits θ above the unmarked axis and every
+    /// iteration rotates it a further 2θ toward the marked one. M counts
+    /// every marked state, so multi-target searches have a larger θ.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::GroverSearch;
+    ///
+    /// let theta = GroverSearch::new(4, 5).rotation_angle();
+    /// assert!((theta - 0.25f64.asin()).abs() < 1e-12);
+    /// ```
+    pub fn rotation_angle(&self) -> f64 {
+        let n = (1 << self.n_qubits) as f64;
+        (self.num_marked() as f64 / n).sqrt().asin()
+    }
+
+    /// Angle (2k + 1)θ of the state above the unmarked axis after `k` iterations.
+    ///
+    /// The success probability is its squared sine; π/2 means the state
+    /// lies entirely on the marked states, and past it the search
+    /// over-rotates.
+    pub fn state_angle_after(&self, k: usize) -> f64 {
+        (2 * k + 1) as f64 * self.rotation_angle()
+    }
+
     /// Calculate the optimal number of Grover iterations.
     ///
-    /// The formula is: k ≈ π/4 × √N
+    /// The formula is: k = ⌊π / 4θ⌋ with θ = arcsin(√(M/N))
+    ///
+    /// where N = 2^n_qubits is the search space size and M the number of
+    /// marked states. For a single target and large N this approaches the
+    /// familiar k ≈ π/4 × √N, but the exact form avoids over-rotating on
+    /// tiny search spaces (N = 4 needs exactly 1 iteration).
+    ///
+    /// This is one less than the rounded π/4 × √N for some sizes (N = 4,
+    /// 128, 256, ...), where rounding up would carry the state past the
+    /// marked axis.
     ///
-    /// where N = 2^n_qubits is the search space size.
+    /// Returns 0 when more than half the space is marked: any iteration
+    /// would lower the success probability below the uniform M/N. Exactly
+    /// half gives θ = π/4 and one iteration, which leaves M/N unchanged.
     pub fn optimal_iterations(&self) -> usize {
-        let n = (1 << self.n_qubits) as f64;
-        let optimal = (PI / 4.0 * n.sqrt()).round() as usize;
-        optimal.max(1)
+        let theta = self.rotation_angle();
+        (PI / (4.0 * theta) + ITERATION_SLACK).floor() as usize
+    }
+
+    /// Number of oracle calls the built circuit makes.
+    ///
+    /// One per iteration; each appears as an [`ORACLE_BLOCK`] block in
+    /// the circuit.
+    pub fn oracle_call_count(&self) -> usize {
+        self.iterations.unwrap_or_else(|| self.optimal_iterations())
+    }
+
+    /// Diagnose whether Grover's algorithm suits this search.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::GroverSearch;
+    ///
+    /// let analysis = GroverSearch::with_targets(2, &[0, 1, 2]).analysis();
+    /// assert!(analysis.too_many_solutions);
+    /// ```
+    pub fn analysis(&self) -> GroverAnalysis {
+        let search_space = 1 << self.n_qubits;
+        let num_marked = self.num_marked();
+
+        GroverAnalysis {
+            search_space,
+            num_marked,
+            iterations: self.oracle_call_count(),
+            success_probability: self.success_probability(),
+            too_many_solutions: 2 * num_marked > search_space,
+        }
     }
 
     /// Build the Grover search circuit.
     ///
     /// Returns a circuit that, when executed and measured,
-    /// will return the target item with high probability.
+    /// will return the target item with high probability. It has
+    /// [`total_qubits`](Self::total_qubits) qubits; search qubit i is
+    /// measured into classical bit i and the ancillas are not measured.
     pub fn build(&self) -> Circuit {
         let iterations = self.iterations.unwrap_or_else(|| self.optimal_iterations());
 
-        let mut circuit = Circuit::new(self.n_qubits);
-
         // Step 1: Create uniform superposition
-        // Apply H to all qubits: |0...0⟩ → |+...+⟩
-        for i in 0..self.n_qubits {
-            circuit = circuit.h(i);
-        }
+        // Apply H to all search qubits: |0...0⟩ → |+...+⟩
+        let mut circuit = match &self.initial_state {
+            Some(prep) => self.widen(prep),
+            None => (0..self.n_qubits).fold(Circuit::new(self.total_qubits()), |circuit, i| circuit.h(i)),
+        };
 
         // Step 2: Grover iterations
+        let oracle = self.oracle_circuit();
+        let diffusion = self.diffusion_circuit();
         for _ in 0..iterations {
             // Oracle: flip the phase of |target⟩
-            circuit = self.apply_oracle(circuit);
+            circuit = circuit
+                .compose_block(ORACLE_BLOCK, &oracle)
+                .expect("oracle has the search register's width");
 
             // Diffusion: amplify the marked state
-            circuit = self.apply_diffusion(circuit);
+            circuit = circuit.append(&diffusion);
         }
 
-        // Step 3: Measure all qubits
-        circuit.measure_all()
+        // Step 3: Measure the search register
+        circuit.measure_range(0..self.n_qubits)
+    }
+
+    /// `circuit` on the search register, padded with the ancillas.
+    fn widen(&self, circuit: &Circuit) -> Circuit {
+        Circuit::new(self.total_qubits())
+            .compose(circuit)
+            .expect("preparation has the search register's width")
     }
 
-    /// Apply the oracle that marks the target state.
+    /// Build the oracle on its own.
     ///
-    /// The oracle flips the sign of the |target⟩ amplitude:
-    /// |target⟩ → -|target⟩
+    /// The oracle flips the sign of every marked state:
+    /// |marked⟩ → -|marked⟩. Like [`build`](Self::build) it acts on
+    /// [`total_qubits`](Self::total_qubits) qubits, and expects the
+    /// ancillas in |0⟩.
+    pub fn oracle_circuit(&self) -> Circuit {
+        self.apply_oracle(Circuit::new(self.total_qubits()))
+    }
+
+    /// Build the diffuser D = 2|s⟩⟨s| - I on its own.
     ///
-    /// This is done using controlled-Z gates based on the binary
-    /// representation of the target.
-    fn apply_oracle(&self, mut circuit: Circuit) -> Circuit {
-        // Apply X gates to qubits that are 0 in the target
-        // This transforms |target⟩ → |11...1⟩
-        for i in 0..self.n_qubits {
-            if (self.target >> i) & 1 == 0 {
+    /// Independent of the marked states, so it can follow a custom oracle.
+    /// With [`with_initial_state`](Self::with_initial_state), |s⟩ is the
+    /// prepared state rather than the uniform one.
+    /// [`build`](Self::build) is H on every search qubit, then
+    /// [`oracle_circuit`](Self::oracle_circuit) and this circuit once per
+    /// iteration, then measurement of the search register.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::GroverSearch;
+    /// use homaya_core::Circuit;
+    ///
+    /// let grover = GroverSearch::new(2, 3);
+    /// let mut circuit = Circuit::new(2).h(0).h(1);
+    /// circuit = circuit.append(&grover.oracle_circuit()).append(&grover.diffusion_circuit());
+    /// assert_eq!(circuit.measure_range(0..2).instructions(), grover.build().instructions());
+    /// ```
+    pub fn diffusion_circuit(&self) -> Circuit {
+        match &self.initial_state {
+            Some(prep) => reflect_about_prepared_on(&self.widen(prep), self.n_qubits),
+            None => self.apply_diffusion(Circuit::new(self.total_qubits())),
+        }
+    }
+
+    /// Apply the oracle that marks the target state(s).
+    fn apply_oracle(&self, circuit: Circuit) -> Circuit {
+        match &self.marking {
+            Marking::Pattern { mask, value } => self.apply_pattern_oracle(circuit, *mask, *value),
+            // Lays out its ancillas like ours
+            Marking::States(states) => circuit.append(&marking_oracle(self.n_qubits, states)),
+        }
+    }
+
+    /// Flip the sign of states whose `mask` qubits equal `value`.
+    ///
+    /// This is done using a multi-controlled Z on the fixed qubits, with
+    /// X gates mapping the required values to all ones. At most n qubits
+    /// are fixed, so the n - 3 ancillas always suffice.
+    fn apply_pattern_oracle(&self, mut circuit: Circuit, mask: usize, value: usize) -> Circuit {
+        let fixed: Vec<usize> = (0..self.n_qubits)
+            .filter(|&i| (mask >> i) & 1 == 1)
+            .collect();
+
+        // Nothing fixed: every state is marked, a global phase only
+        if fixed.is_empty() {
+            return circuit;
+        }
+
+        // Apply X gates to fixed qubits that must be 0
+        // This transforms |target⟩ → |11...1⟩ on the fixed qubits
+        for &i in &fixed {
+            if (value >> i) & 1 == 0 {
                 circuit = circuit.x(i);
             }
         }
 
-        // Multi-controlled Z gate on all qubits
+        // Multi-controlled Z on the fixed qubits
         // This flips the sign of |11...1⟩
-        circuit = self.multi_controlled_z(circuit);
+        circuit = multi_controlled_z_with(circuit, &fixed, &self.ancillas());
 
         // Undo the X gates
-        for i in 0..self.n_qubits {
-            if (self.target >> i) & 1 == 0 {
+        for &i in &fixed {
+            if (value >> i) & 1 == 0 {
                 circuit = circuit.x(i);
             }
         }
@@ -173,27 +725,19 @@ impl GroverSearch {
     /// where |s⟩ is the uniform superposition state.
     ///
     /// This reflects amplitudes about their mean, amplifying
-    /// the marked state.
+    /// the marked state. It is exact at every width: the reflection uses
+    /// the same ancilla-backed multi-controlled Z as the oracle.
     fn apply_diffusion(&self, mut circuit: Circuit) -> Circuit {
-        // Apply H to all qubits
+        // Apply H to all search qubits
         for i in 0..self.n_qubits {
             circuit = circuit.h(i);
         }
 
-        // Apply X to all qubits (transforms |0...0⟩ → |1...1⟩)
-        for i in 0..self.n_qubits {
-            circuit = circuit.x(i);
-        }
-
-        // Multi-controlled Z
-        circuit = self.multi_controlled_z(circuit);
+        // Reflect about |0...0⟩
+        let search: Vec<usize> = (0..self.n_qubits).collect();
+        circuit = reflect_about_zero_on(circuit, &search, &self.ancillas());
 
-        // Undo X gates
-        for i in 0..self.n_qubits {
-            circuit = circuit.x(i);
-        }
-
-        // Apply H to all qubits
+        // Apply H to all search qubits
         for i in 0..self.n_qubits {
             circuit = circuit.h(i);
         }
@@ -201,33 +745,13 @@ impl GroverSearch {
         circuit
     }
 
-    /// Implement multi-controlled Z using decomposition.
+    /// Read the found item off a measurement histogram.
     ///
-    /// For 2 qubits: CZ
-    /// For 3+ qubits: decompose into Toffoli + controlled gates
-    fn multi_controlled_z(&self, mut circuit: Circuit) -> Circuit {
-        match self.n_qubits {
-            0 | 1 => circuit.z(0),
-            2 => {
-                // CZ gate: controlled-Z on qubits 0,1
-                circuit.h(1).cx(0, 1).h(1)
-            }
-            3 => {
-                // CCZ using H-Toffoli-H pattern
-                circuit.h(2).ccx(0, 1, 2).h(2)
-            }
-            _ => {
-                // For larger circuits, use a simplified pattern
-                // Apply Z to last qubit controlled by all others
-                // This is an approximation for demonstration
-                let last = self.n_qubits - 1;
-                circuit = circuit.h(last);
-                for i in 0..last {
-                    circuit = circuit.cx(i, last);
-                }
-                circuit.h(last)
-            }
-        }
+    /// Takes counts keyed by canonical bitstrings, e.g. from real hardware,
+    /// and returns the most frequent outcome. Returns `None` for an empty
+    /// histogram.
+    pub fn found_counts(counts: &HashMap<String, usize>) -> Option<usize> {
+        most_likely_outcome(counts)
     }
 
     /// Get the theoretical success probability.
@@ -235,11 +759,137 @@ impl GroverSearch {
     /// Returns the probability of measuring the target state
     /// after the optimal number of iterations.
     pub fn success_probability(&self) -> f64 {
-        let n = (1 << self.n_qubits) as f64;
-        let theta = (1.0 / n.sqrt()).asin();
-        let k = self.iterations.unwrap_or_else(|| self.optimal_iterations()) as f64;
-        let angle = (2.0 * k + 1.0) * theta;
-        angle.sin().powi(2)
+        self.success_probability_at(self.oracle_call_count())
+    }
+
+    /// Analytic success probability sin²((2k + 1)θ) after `k` iterations.
+    fn success_probability_at(&self, k: usize) -> f64 {
+        self.state_angle_after(k).sin().powi(2)
+    }
+
+    /// Success probability sin²((2k + 1)θ) for each k in 0..=max_k.
+    ///
+    /// θ accounts for every marked state, so multi-target searches rotate
+    /// faster. Past [`optimal_iterations`](Self::optimal_iterations) the
+    /// state over-rotates and the probability falls again; the sequence
+    /// oscillates with period ≈ π/2θ.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::GroverSearch;
+    ///
+    /// let probabilities = GroverSearch::new(2, 3).probability_by_iteration(2);
+    /// assert!((probabilities[1] - 1.0).abs() < 1e-10);
+    /// ```
+    pub fn probability_by_iteration(&self, max_k: usize) -> Vec<f64> {
+        (0..=max_k).map(|k| self.success_probability_at(k)).collect()
+    }
+
+    /// Tabulate success probability and circuit depth for 0..=max_iterations.
+    ///
+    /// Enable the `serde` feature to serialize the points as JSON, or use
+    /// [`CurvePoint::to_csv`] for CSV.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::{grover::CurvePoint, GroverSearch};
+    ///
+    /// let curve = GroverSearch::new(3, 5).curve_data(4);
+    /// let csv: Vec<String> = curve.iter().map(CurvePoint::to_csv).collect();
+    /// assert_eq!(csv.len(), 5);
+    /// ```
+    pub fn curve_data(&self, max_iterations: usize) -> Vec<CurvePoint> {
+        (0..=max_iterations)
+            .map(|k| CurvePoint {
+                iteration: k,
+                success_probability: self.success_probability_at(k),
+                depth: self.clone().with_iterations(k).build().depth(),
+            })
+            .collect()
+    }
+}
+
+/// Reflect about |0...0⟩ on `qubits`, borrowing clean `ancillas`.
+///
+/// Flips the sign of |0...0⟩ only: X on every qubit, multi-controlled Z,
+/// then undo the X gates. Up to a global phase this is 2|0⟩⟨0| - I. See
+/// [`multi_controlled_z_with`] for how the ancillas are used.
+pub(crate) fn reflect_about_zero_on(mut circuit: Circuit, qubits: &[usize], ancillas: &[usize]) -> Circuit {
+    // Apply X to all qubits (transforms |0...0⟩ → |1...1⟩)
+    for &q in qubits {
+        circuit = circuit.x(q);
+    }
+
+    // Multi-controlled Z
+    circuit = multi_controlled_z_with(circuit, qubits, ancillas);
+
+    // Undo X gates
+    for &q in qubits {
+        circuit = circuit.x(q);
+    }
+
+    circuit
+}
+
+/// Implement multi-controlled Z on the first `n_qubits` qubits using decomposition.
+pub(crate) fn multi_controlled_z(circuit: Circuit, n_qubits: usize) -> Circuit {
+    let qubits: Vec<usize> = (0..n_qubits.max(1)).collect();
+    multi_controlled_z_on(circuit, &qubits)
+}
+
+/// Multi-controlled Z on `qubits`, using clean `ancillas` when there are enough.
+///
+/// With at least n - 3 ancillas for n qubits this is [`Circuit::mcz`]: a
+/// Toffoli ladder of 2n - 5 CCX gates between two H, linear in n.
+/// Otherwise it falls back to the ancilla-free [`multi_controlled_z_on`].
+/// The ancillas are returned to |0⟩.
+pub(crate) fn multi_controlled_z_with(circuit: Circuit, qubits: &[usize], ancillas: &[usize]) -> Circuit {
+    if ancillas.len() >= qubits.len().saturating_sub(3) {
+        circuit.mcz(qubits, ancillas)
+    } else {
+        multi_controlled_z_on(circuit, qubits)
+    }
+}
+
+/// Implement multi-controlled Z on the given qubits using decomposition.
+///
+/// Up to 3 qubits: [`Circuit::mcz`] (Z, CZ or H-Toffoli-H)
+/// For 4+ qubits: multi-controlled phase P(π), see [`multi_controlled_phase`].
+/// No ancilla qubits are needed; callers with clean ancillas to spare can
+/// use [`Circuit::mcz`] directly for a linear-size Toffoli ladder.
+pub(crate) fn multi_controlled_z_on(circuit: Circuit, qubits: &[usize]) -> Circuit {
+    match *qubits {
+        [] | [_] | [_, _] | [_, _, _] => circuit.mcz(qubits, &[]),
+        [ref controls @ .., last] => multi_controlled_phase(circuit, PI, controls, last),
+    }
+}
+
+/// Apply P(λ) to `target` controlled on every qubit in `controls`.
+///
+/// Uses the V-gate recursion (Barenco et al. 1995, Lemma 7.5) with
+/// V = P(λ/2), so V² = P(λ). Writing c for the last control and C' for the
+/// rest:
+///
+/// C'c-P(λ) = CP(λ/2)(c, t) · C'-X(c) · CP(-λ/2)(c, t) · C'-X(c) · C'-P(λ/2)(t)
+///
+/// When all of C' is 1 the X gates flip c around the negative phase, so t
+/// picks up λ/2 from the first CP and λ/2 from C'-P - the full P(λ) - while
+/// every other branch cancels. C'-X is itself H · C'-P(π) · H, so the
+/// recursion bottoms out at CP. Exact and ancilla-free, at the cost of
+/// O(3^k) gates for k controls.
+pub(crate) fn multi_controlled_phase(mut circuit: Circuit, lambda: f64, controls: &[usize], target: usize) -> Circuit {
+    match *controls {
+        [] => circuit.p(lambda, target),
+        [c] => circuit.cp(lambda, c, target),
+        [ref rest @ .., c] => {
+            circuit = circuit.cp(lambda / 2.0, c, target);
+            circuit = multi_controlled_phase(circuit.h(c), PI, rest, c).h(c);
+            circuit = circuit.cp(-lambda / 2.0, c, target);
+            circuit = multi_controlled_phase(circuit.h(c), PI, rest, c).h(c);
+            multi_controlled_phase(circuit, lambda / 2.0, rest, target)
+        }
     }
 }
 
@@ -256,9 +906,52 @@ pub fn search(n_qubits: usize, target: usize) -> Circuit {
     GroverSearch::new(n_qubits, target).build()
 }
 
+/// Run Grover's search on the simulator and return the measured answer.
+///
+/// This is a demo helper: the target is already known, so calling it only
+/// verifies that Grover's algorithm amplifies it. The circuit is sampled
+/// `shots` times with a seeded simulator and the most frequent outcome is
+/// returned as an index, or `None` when `shots` is 0 and there is nothing
+/// to read.
+///
+/// # Panics
+///
+/// Panics if n_qubits is 0 or target >= 2^n_qubits (see
+/// [`GroverSearch::new`]), or if the search needs more qubits, ancillas
+/// included, than the simulator holds.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_algorithms::grover;
+///
+/// assert_eq!(grover::find(3, 5, 100, 42), Some(5));
+/// assert_eq!(grover::find(3, 5, 0, 42), None);
+/// ```
+pub fn find(n_qubits: usize, target: usize, shots: usize, seed: u64) -> Option<usize> {
+    let circuit = search(n_qubits, target);
+    let counts = Simulator::with_seed(seed)
+        .sample(&circuit, shots)
+        .expect("the search fits in the simulator");
+
+    GroverSearch::found_counts(&counts)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use homaya_core::Complex;
+    use homaya_sim::StateVector;
+
+    #[test]
+    fn test_try_new_errors() {
+        assert!(GroverSearch::try_new(4, 15).is_ok());
+        assert_eq!(GroverSearch::try_new(0, 0).unwrap_err(), AlgorithmError::ZeroQubits);
+        assert_eq!(
+            GroverSearch::try_new(4, 16).unwrap_err(),
+            AlgorithmError::TargetOutOfRange { target: 16, n_qubits: 4 }
+        );
+    }
 
     #[test]
     fn test_grover_creates_circuit() {
@@ -277,6 +970,131 @@ mod tests {
         assert!(grover.optimal_iterations() == 3);
     }
 
+    #[test]
+    fn test_optimal_iterations_small_space() {
+        // N=4: a single iteration lands exactly on the target
+        let grover = GroverSearch::new(2, 1);
+        assert_eq!(grover.optimal_iterations(), 1);
+        assert!((grover.success_probability() - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_optimal_iterations_half_marked() {
+        // M = N/2: θ = π/4 exactly, so π/4θ = 1 must not floor to 0
+        assert!((0.5f64).sqrt().asin() > PI / 4.0);
+        for n in 1..=6 {
+            let half: Vec<usize> = (0..1 << (n - 1)).collect();
+            let grover = GroverSearch::with_targets(n, &half);
+            assert_eq!(grover.optimal_iterations(), 1, "n = {}", n);
+            assert!((grover.success_probability() - 0.5).abs() < 1e-10, "n = {}", n);
+        }
+        assert_eq!(GroverSearch::new(1, 0).optimal_iterations(), 1);
+    }
+
+    #[test]
+    fn test_optimal_iterations_below_rounded_formula() {
+        // Rounding π/4 × √N would over-rotate at these sizes
+        for (n, iterations) in [(2, 1), (7, 8), (8, 12), (9, 17), (11, 35)] {
+            let grover = GroverSearch::new(n, 0);
+            assert_eq!(grover.optimal_iterations(), iterations, "n = {}", n);
+            let rounded = (PI / 4.0 * ((1usize << n) as f64).sqrt()).round() as usize;
+            assert_eq!(rounded, iterations + 1, "n = {}", n);
+            assert!(grover.success_probability() >= grover.success_probability_at(rounded), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_try_new_rejects_too_many_qubits() {
+        let wide = usize::BITS as usize;
+        assert_eq!(GroverSearch::try_new(wide, 0).unwrap_err(), AlgorithmError::TooManyQubits { n_qubits: wide });
+        assert!(GroverSearch::try_new(wide - 1, 0).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "Need at least 1 qubit")]
+    fn test_with_targets_rejects_zero_qubits() {
+        GroverSearch::with_targets(0, &[0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "64 qubits is too many")]
+    fn test_with_targets_rejects_too_many_qubits() {
+        GroverSearch::with_targets(64, &[0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Need at least 1 qubit")]
+    fn test_with_pattern_rejects_empty_pattern() {
+        GroverSearch::with_pattern(0, "");
+    }
+
+    #[test]
+    #[should_panic(expected = "64 qubits is too many")]
+    fn test_with_pattern_rejects_too_many_qubits() {
+        GroverSearch::with_pattern(64, &".".repeat(64));
+    }
+
+    #[test]
+    #[should_panic(expected = "Need at least 1 qubit")]
+    fn test_with_predicate_rejects_zero_qubits() {
+        GroverSearch::with_predicate(0, |_| true);
+    }
+
+    #[test]
+    #[should_panic(expected = "64 qubits is too many")]
+    fn test_with_predicate_rejects_too_many_qubits() {
+        GroverSearch::with_predicate(64, |_| unreachable!("the predicate must not be called"));
+    }
+
+    #[test]
+    fn test_find_without_shots_finds_nothing() {
+        // Not Some(0): target 0 is a real answer
+        assert_eq!(find(3, 5, 0, 1), None);
+        assert_eq!(find(3, 0, 50, 1), Some(0));
+    }
+
+    #[test]
+    fn test_find_returns_target() {
+        for n_qubits in 2..=3 {
+            for target in 0..(1 << n_qubits) {
+                assert_eq!(find(n_qubits, target, 200, 42), Some(target));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sampled_target_is_modal() {
+        let grover = GroverSearch::new(4, 0b1101);
+        let counts = Simulator::sample_seeded(&grover.build(), 10_000, 2024).unwrap();
+
+        let (modal, _) = counts.iter().max_by_key(|&(_, &count)| count).unwrap();
+        assert_eq!(modal, "1101");
+    }
+
+    #[test]
+    fn test_draw_snapshot() {
+        // Oracle for 101 (X on q1 around H·CCX·H), then the diffusion operator
+        let drawing = GroverSearch::new(3, 5).with_iterations(1).build().draw();
+        assert_eq!(
+            drawing,
+            "q0: ─H─────●──H──X────────●──X──H──M─────\n\
+             \x20          │              │\n\
+             q1: ─H──X──●──X──H──X─────●──X──H──M─────\n\
+             \x20          │              │\n\
+             q2: ─H──H──⊕──H──H──X──H──⊕──H──X──H──M──\n"
+        );
+    }
+
+    #[test]
+    fn test_found_counts() {
+        let counts = HashMap::from([
+            ("101".to_string(), 940),
+            ("000".to_string(), 30),
+            ("111".to_string(), 30),
+        ]);
+        assert_eq!(GroverSearch::found_counts(&counts), Some(5));
+    }
+
     #[test]
     fn test_success_probability() {
         let grover = GroverSearch::new(3, 5);
@@ -285,9 +1103,378 @@ mod tests {
         assert!(prob > 0.9, "Success probability {} too low", prob);
     }
 
+    #[test]
+    fn test_oracle_call_count() {
+        let grover = GroverSearch::new(3, 5);
+        assert_eq!(grover.oracle_call_count(), 2);
+        assert_eq!(grover.build().count_blocks(ORACLE_BLOCK), 2);
+
+        let grover = grover.with_iterations(5);
+        assert_eq!(grover.build().count_blocks(ORACLE_BLOCK), grover.oracle_call_count());
+    }
+
+    #[test]
+    fn test_multi_controlled_z_flips_only_all_ones() {
+        for n in 1..=6 {
+            let mut circuit = Circuit::new(n);
+            for i in 0..n {
+                circuit = circuit.h(i);
+            }
+            let state = Simulator::new().run(&multi_controlled_z(circuit, n)).unwrap();
+
+            // Uniform superposition with only |1...1⟩ negated
+            let mut expected = StateVector::uniform(n);
+            let all_ones = (1 << n) - 1;
+            expected.set(all_ones, -expected.get(all_ones));
+            assert!(state.approx_eq_up_to_global_phase(&expected, 1e-10), "n={}", n);
+        }
+    }
+
+    #[test]
+    fn test_diffuser_reflects_about_mean() {
+        // Includes n >= 4, where the MCZ borrows ancillas
+        for n in 1..=6 {
+            let dim = 1usize << n;
+            let amplitudes: Vec<Complex> = (0..dim).map(|x| Complex::new(1.0 + x as f64, 0.5 * (x % 3) as f64)).collect();
+            let norm = amplitudes.iter().map(|a| a.norm_sqr()).sum::<f64>().sqrt();
+            let amplitudes: Vec<Complex> = amplitudes.iter().map(|&a| a * (1.0 / norm)).collect();
+
+            // Ancillas in |0⟩: only the first 2^n amplitudes are populated
+            let grover = GroverSearch::new(n, 0);
+            let padded = |search: Vec<Complex>| {
+                let mut full = search;
+                full.resize(1 << grover.total_qubits(), Complex::ZERO);
+                StateVector::from_amplitudes(full).unwrap()
+            };
+            let state = Simulator::new().run_from_state(&grover.diffusion_circuit(), padded(amplitudes.clone())).unwrap();
+
+            // D = 2|s⟩⟨s| - I sends each amplitude a to 2·mean - a
+            let mean = amplitudes.iter().fold(Complex::ZERO, |sum, &a| sum + a) * (1.0 / dim as f64);
+            let expected = padded(amplitudes.iter().map(|&a| mean * 2.0 - a).collect());
+            assert!(state.approx_eq_up_to_global_phase(&expected, 1e-10), "n={}", n);
+        }
+    }
+
+    #[test]
+    fn test_five_qubit_search_hits_predicted_probability() {
+        let grover = GroverSearch::new(5, 19);
+        let predicted = grover.success_probability();
+        assert!(predicted > 0.99);
+
+        let shots = 2000;
+        let counts = Simulator::with_seed(17).sample(&grover.build(), shots).unwrap();
+        let hits = counts.get("10011").copied().unwrap_or(0);
+        let observed = hits as f64 / shots as f64;
+        assert!((observed - predicted).abs() < 0.02, "observed {} vs predicted {}", observed, predicted);
+    }
+
+    #[test]
+    fn test_curve_peaks_at_optimal_iterations() {
+        for n_qubits in 2..=6 {
+            let grover = GroverSearch::new(n_qubits, 1);
+            // Stay within the first period: the curve peaks again later
+            let curve = grover.curve_data(2 * grover.optimal_iterations());
+
+            let best = curve
+                .iter()
+                .max_by(|a, b| a.success_probability.total_cmp(&b.success_probability))
+                .unwrap();
+            assert_eq!(best.iteration, grover.optimal_iterations(), "n={}", n_qubits);
+        }
+    }
+
+    #[test]
+    fn test_try_with_iterations() {
+        let grover = GroverSearch::new(3, 5);
+        for k in 0..=2 {
+            assert_eq!(grover.clone().try_with_iterations(k).unwrap().oracle_call_count(), k);
+        }
+
+        match grover.try_with_iterations(3) {
+            Err(GroverError::Overshoot { optimal, success_probability, optimal_probability, .. }) => {
+                assert_eq!(optimal, 2);
+                assert!(success_probability < optimal_probability);
+            }
+            other => panic!("expected an overshoot, got {:?}", other),
+        }
+
+        let crowded = GroverSearch::with_targets(2, &[0, 1, 2]);
+        assert!(crowded.clone().try_with_iterations(0).is_ok());
+        assert_eq!(
+            crowded.try_with_iterations(1).unwrap_err(),
+            GroverError::TooManySolutions { num_marked: 3, search_space: 4 }
+        );
+    }
+
+    #[test]
+    fn test_probability_by_iteration_overshoots() {
+        let grover = GroverSearch::new(3, 5);
+        let optimal = grover.optimal_iterations();
+        let probabilities = grover.probability_by_iteration(optimal + 2);
+
+        assert!((probabilities[0] - 0.125).abs() < 1e-10);
+        assert!(probabilities[..=optimal].windows(2).all(|pair| pair[1] > pair[0]));
+        assert!(probabilities[optimal..].windows(2).all(|pair| pair[1] < pair[0]));
+
+        // Two of eight marked: θ = π/6, so one iteration lands exactly
+        let two = GroverSearch::with_targets(3, &[1, 6]).probability_by_iteration(1);
+        assert!((two[0] - 0.25).abs() < 1e-10);
+        assert!((two[1] - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_curve_depth_grows() {
+        let curve = GroverSearch::new(3, 5).curve_data(3);
+        assert!(curve.windows(2).all(|pair| pair[1].depth > pair[0].depth));
+        assert_eq!(curve[0].to_csv(), format!("0,{},{}", 0.125f64.sqrt().powi(2), curve[0].depth));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_curve_serializes_to_json() {
+        let curve = GroverSearch::new(2, 1).curve_data(1);
+        let json = serde_json::to_string(&curve).unwrap();
+        assert!(json.starts_with("[{\"iteration\":0,"), "{}", json);
+    }
+
+    #[test]
+    fn test_gate_budget() {
+        use homaya_core::BasisSet;
+
+        let basis = BasisSet::clifford_t();
+        let shallow = GroverSearch::new(3, 5).with_iterations(1).build();
+        let deep = GroverSearch::new(3, 5).with_iterations(2).build();
+
+        assert!(shallow.fits_budget(&basis, 80));
+        assert!(!deep.fits_budget(&basis, 80));
+    }
+
+    #[test]
+    fn test_pattern_marks_matching_states() {
+        use homaya_core::format_outcome;
+
+        let grover = GroverSearch::with_pattern(3, "1.0");
+        let circuit = Circuit::new(3).h(0).h(1).h(2).compose(&grover.oracle_circuit()).unwrap();
+        let state = Simulator::new().run(&circuit).unwrap();
+
+        let marked: Vec<String> = (0..8)
+            .filter(|&i| state.get(i).re < 0.0)
+            .map(|i| format_outcome(i, 3))
+            .collect();
+        assert_eq!(marked, ["100", "110"]);
+        assert_eq!(grover.num_marked(), 2);
+    }
+
+    #[test]
+    fn test_pattern_search_finds_a_match() {
+        // M = 2 of N = 8: θ = π/6, so one iteration gives sin²(3θ) = 1
+        let grover = GroverSearch::with_pattern(3, "1.0");
+        assert_eq!(grover.optimal_iterations(), 1);
+
+        let counts = Simulator::with_seed(5).sample(&grover.build(), 200).unwrap();
+        let found = GroverSearch::found_counts(&counts).unwrap();
+        assert!(found == 0b100 || found == 0b110, "found {}", found);
+    }
+
+    #[test]
+    fn test_with_targets_finds_one() {
+        let grover = GroverSearch::with_targets(3, &[2, 5, 5]);
+        assert_eq!(grover.num_marked(), 2);
+        assert!(!grover.analysis().too_many_solutions);
+
+        let counts = Simulator::with_seed(9).sample(&grover.build(), 200).unwrap();
+        let found = GroverSearch::found_counts(&counts).unwrap();
+        assert!(found == 2 || found == 5, "found {}", found);
+    }
+
+    #[test]
+    fn test_with_predicate_marks_evens() {
+        let grover = GroverSearch::with_predicate(3, |x| x % 2 == 0);
+        assert_eq!(grover.num_marked(), 4);
+
+        let expected = (PI / 4.0 * (8.0f64 / 4.0).sqrt()).floor() as usize;
+        assert_eq!(grover.optimal_iterations(), expected);
+        assert!((grover.success_probability() - 0.5).abs() < 1e-10);
+    }
+
+    #[test]
+    #[should_panic(expected = "nothing to find")]
+    fn test_with_predicate_matching_nothing() {
+        GroverSearch::with_predicate(3, |_| false);
+    }
+
+    #[test]
+    fn test_too_many_solutions() {
+        // M = 3N/4: iterating would rotate past the marked subspace
+        let grover = GroverSearch::with_targets(2, &[0, 1, 2]);
+        let analysis = grover.analysis();
+
+        assert!(analysis.too_many_solutions);
+        assert_eq!(analysis.num_marked, 3);
+        assert_eq!(grover.optimal_iterations(), 0);
+        assert!((analysis.success_probability - 0.75).abs() < 1e-10);
+    }
+
+    #[test]
+    #[should_panic(expected = "may only contain")]
+    fn test_invalid_pattern() {
+        GroverSearch::with_pattern(3, "1x0");
+    }
+
     #[test]
     #[should_panic(expected = "Target 16 is too large")]
     fn test_invalid_target() {
         GroverSearch::new(4, 16);  // Max is 15 for 4 qubits
     }
+
+    #[test]
+    fn test_rotation_angle() {
+        let grover = GroverSearch::new(4, 5);
+        assert!((grover.rotation_angle() - 0.25f64.asin()).abs() < 1e-12);
+
+        let optimal = grover.state_angle_after(grover.optimal_iterations());
+        assert!((optimal - PI / 2.0).abs() < grover.rotation_angle());
+        assert!((grover.state_angle_after(0) - grover.rotation_angle()).abs() < 1e-12);
+
+        // Four of sixteen marked: θ = arcsin(1/2) = π/6, one iteration lands on π/2
+        let four = GroverSearch::with_targets(4, &[1, 2, 3, 4]);
+        assert!((four.rotation_angle() - PI / 6.0).abs() < 1e-12);
+        assert!((four.state_angle_after(1) - PI / 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_build_is_oracle_then_diffusion() {
+        let searches = [
+            GroverSearch::new(4, 11),
+            GroverSearch::with_targets(5, &[3, 17]),
+            GroverSearch::with_pattern(4, "1.0."),
+        ];
+        for grover in searches {
+            let mut circuit = Circuit::new(grover.total_qubits());
+            for i in 0..grover.n_qubits {
+                circuit = circuit.h(i);
+            }
+            for _ in 0..grover.optimal_iterations() {
+                circuit = circuit.append(&grover.oracle_circuit()).append(&grover.diffusion_circuit());
+            }
+
+            assert_eq!(circuit.measure_range(0..grover.n_qubits).instructions(), grover.build().instructions());
+        }
+    }
+
+    #[test]
+    fn test_noise_washes_out_the_target() {
+        use homaya_sim::NoiseModel;
+
+        let circuit = search(3, 5);
+        let clean = Simulator::with_seed(8).run_noisy(&circuit, &NoiseModel::new(), 1000).unwrap();
+        assert!(clean["101"] > 900, "{:?}", clean);
+
+        let noise = NoiseModel::new().with_depolarizing(0.3);
+        let noisy = Simulator::with_seed(8).run_noisy(&circuit, &noise, 1000).unwrap();
+        assert!(noisy.get("101").copied().unwrap_or(0) < 300, "{:?}", noisy);
+    }
+
+    #[test]
+    fn test_simulated_success_matches_analytic() {
+        for target in 0..8 {
+            let grover = GroverSearch::new(3, target);
+            let bits = homaya_core::format_outcome(target, 3);
+            let simulated = Simulator::new().success_probability(&grover.build(), &bits).unwrap();
+            assert!((simulated - grover.success_probability()).abs() < 1e-9, "{}: {}", bits, simulated);
+        }
+    }
+
+    #[test]
+    fn test_optimize_shrinks_grover() {
+        // Targets with bit 2 set: the initial H(2) meets the oracle's H(2)
+        for target in [0b111, 0b101] {
+            let circuit = search(3, target);
+            let optimized = circuit.optimize();
+            assert!(optimized.len() < circuit.len(), "{}: {} vs {}", target, optimized.len(), circuit.len());
+
+            let before = Simulator::new().outcome_probabilities(&circuit).unwrap();
+            let after = Simulator::new().outcome_probabilities(&optimized).unwrap();
+            for (bits, p) in &before {
+                assert!((after.get(bits).copied().unwrap_or(0.0) - p).abs() < 1e-10, "{}", bits);
+            }
+        }
+    }
+
+    #[test]
+    fn test_biased_start_still_amplifies() {
+        // Every qubit leans slightly towards 1
+        let prep = (0..3).fold(Circuit::new(3), |circuit, q| circuit.ry(PI / 2.0 + 0.2, q));
+        let grover = GroverSearch::new(3, 0b101).with_initial_state(prep.clone()).with_iterations(2);
+
+        let mut sim = Simulator::new();
+        let before = sim.success_probability(&prep.measure_all(), "101").unwrap();
+        let after = sim.success_probability(&grover.build(), "101").unwrap();
+        assert!(before < 0.15, "{}", before);
+        assert!(after > 0.8, "{}", after);
+    }
+
+    #[test]
+    fn test_uniform_initial_state_matches_default() {
+        let uniform = (0..3).fold(Circuit::new(3), |circuit, q| circuit.h(q));
+        let custom = GroverSearch::new(3, 6).with_initial_state(uniform).build();
+        let default = GroverSearch::new(3, 6).build();
+
+        let mut sim = Simulator::new();
+        let a = sim.success_probability(&custom, "110").unwrap();
+        let b = sim.success_probability(&default, "110").unwrap();
+        assert!((a - b).abs() < 1e-10);
+    }
+
+    #[test]
+    #[should_panic(expected = "initial state prepares 2 qubits, the search has 3")]
+    fn test_initial_state_width_must_match() {
+        GroverSearch::new(3, 1).with_initial_state(Circuit::new(2).h(0));
+    }
+
+    #[test]
+    fn test_gate_count_growth() {
+        for n in 1..=16 {
+            // All ones: the oracle is a bare multi-controlled Z
+            let grover = GroverSearch::new(n, (1 << n) - 1);
+            let oracle = grover.oracle_circuit();
+            let diffusion = grover.diffusion_circuit();
+
+            // The Toffoli ladder is linear in n, with H on the target around it
+            let ccx = oracle.gates().filter(|gate| gate.gate_type == GateType::CCX).count();
+            if n >= 4 {
+                assert_eq!(ccx, 2 * n - 5, "n = {}", n);
+                assert_eq!(oracle.gate_count(), 2 * n - 3, "n = {}", n);
+            }
+            assert!(ccx <= 2 * n, "n = {}: {} CCX", n, ccx);
+
+            // Plus H and X layers on each side of the reflection
+            let per_iteration = oracle.gate_count() + diffusion.gate_count();
+            assert_eq!(diffusion.gate_count(), oracle.gate_count() + 4 * n, "n = {}", n);
+            assert!(per_iteration <= 8 * n, "n = {}: {} gates per iteration", n, per_iteration);
+
+            // O(√N) iterations of the above, after n H gates
+            let iterations = grover.optimal_iterations();
+            assert!(iterations as f64 <= PI / 4.0 * ((1usize << n) as f64).sqrt() + 1.0);
+            assert_eq!(grover.build().gate_count(), n + iterations * per_iteration, "n = {}", n);
+        }
+    }
+
+    use proptest::prelude::*;
+
+    proptest! {
+        #![proptest_config(crate::proptest_config())]
+
+        #[test]
+        fn prop_finds_any_target((n, target) in (1usize..=6).prop_flat_map(|n| (Just(n), 0..1usize << n))) {
+            let grover = GroverSearch::new(n, target);
+            let mut sim = Simulator::new();
+            let p = sim.success_probability(&grover.build(), &homaya_core::format_outcome(target, n)).unwrap();
+
+            // The simulation must match theory at the optimal iteration count,
+            // which is at least 0.9 from two qubits up (one qubit only reaches 1/2)
+            prop_assert!((p - grover.success_probability()).abs() < 1e-9, "n = {}, target = {}: {}", n, target, p);
+            prop_assert!(p >= if n == 1 { 0.5 - 1e-9 } else { 0.9 }, "n = {}, target = {}: {}", n, target, p);
+        }
+    }
 }
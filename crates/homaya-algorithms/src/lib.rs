@@ -7,6 +7,17 @@
 //! - [`grover`] - Grover's Search: Find a needle in a haystack with √N queries
 //! - [`deutsch`] - Deutsch-Jozsa: Determine if a function is constant or balanced
 //! - [`bernstein_vazirani`] - Find a hidden string in one query
+//! - [`simon`] - Simon's algorithm: Find a hidden XOR period
+//! - [`oracle`] - Diagonal phase oracles and marking oracles
+//! - [`amplification`] - Amplitude amplification: Grover for any state preparation
+//! - [`amplitude_estimation`] - Estimate the success probability of any state preparation
+//! - [`phase_estimation`] - Read off the eigenphase of a unitary
+//! - [`counting`] - Quantum counting: Estimate how many items an oracle marks
+//! - [`swap_test`] - Estimate the overlap of two states
+//! - [`teleport`] - Quantum teleportation of a single-qubit state
+//! - [`superdense`] - Superdense coding: two classical bits in one qubit
+//! - [`qft`] - Quantum Fourier Transform, the building block of phase estimation
+//! - [`shor`] - Shor's period finding: the order of a modulo N, and factors from it
 //!
 //! ## Example: Grover's Search
 //!
@@ -20,10 +31,57 @@
 #![deny(missing_docs)]
 #![deny(unsafe_code)]
 
+pub mod error;
 pub mod grover;
 pub mod deutsch;
 pub mod bernstein_vazirani;
+pub mod simon;
+pub mod oracle;
+pub mod amplification;
+pub mod amplitude_estimation;
+pub mod phase_estimation;
+pub mod counting;
+pub mod swap_test;
+pub mod qft;
+pub mod shor;
+pub mod teleport;
+pub mod superdense;
 
-pub use grover::GroverSearch;
+pub use error::AlgorithmError;
+pub use grover::{GroverAnalysis, GroverError, GroverSearch};
 pub use deutsch::DeutschJozsa;
 pub use bernstein_vazirani::BernsteinVazirani;
+pub use simon::SimonsAlgorithm;
+pub use amplitude_estimation::AmplitudeEstimation;
+pub use phase_estimation::PhaseEstimation;
+pub use counting::QuantumCounting;
+pub use swap_test::SwapTest;
+pub use qft::QuantumFourierTransform;
+pub use shor::ShorPeriodFinding;
+
+/// Configuration shared by the property tests.
+///
+/// Each property runs 32 cases from a fixed seed, so CI sees the same
+/// inputs every time and a failure reproduces on the next run; proptest
+/// prints the shrunk failing input. Set `PROPTEST_RNG_SEED` to try other
+/// inputs, and the same value to replay them. Failures are not persisted
+/// to `proptest-regressions/`, since the seed already pins them.
+#[cfg(test)]
+pub(crate) fn proptest_config() -> proptest::test_runner::Config {
+    use proptest::test_runner::{Config, RngSeed};
+
+    /// Seed used unless `PROPTEST_RNG_SEED` is set
+    const DEFAULT_SEED: u64 = 0x686f_6d61_7961;
+
+    let config = Config::default();
+    let rng_seed = match config.rng_seed {
+        RngSeed::Random => RngSeed::Fixed(DEFAULT_SEED),
+        seed => seed,
+    };
+    Config {
+        cases: 32,
+        rng_seed,
+        failure_persistence: None,
+        ..config
+    }
+}
@@ -0,0 +1,197 @@
+//! # Phase Oracles
+//!
+//! Diagonal unitaries |x⟩ → e^{iφ(x)}|x⟩, the common core of Grover's
+//! search, amplitude amplification and quantum counting.
+//!
+//! ## Cost
+//!
+//! Both builders are exact. [`marking_oracle`] spends one n-qubit
+//! multi-controlled Z per marked state, a Toffoli ladder of O(n) gates on
+//! n - 3 clean ancillas. [`phase_oracle`] is ancilla-free but exponential
+//! in general: it writes φ as a sum over subsets S of the qubits,
+//! φ(x) = φ(0) + Σ_{S ⊆ x} θ_S, and spends one multi-controlled phase per
+//! nonzero θ_S: up to 2^n - 1 of them for a dense φ, each costing O(3^k)
+//! gates for k controls. It is meant for small registers.
+
+use homaya_core::{Circuit, PI};
+
+use crate::grover::multi_controlled_phase;
+
+/// Angles closer than this to a multiple of 2π are left out.
+const PHASE_EPSILON: f64 = 1e-12;
+
+/// A diagonal oracle applying e^{iφ(x)} to each basis state |x⟩.
+///
+/// `phases` is evaluated once on each of the 2^n inputs. The circuit is
+/// exact up to the global phase e^{iφ(0)}; see the [module docs](self) for
+/// its exponential gate count.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_algorithms::oracle::phase_oracle;
+/// use homaya_core::PI;
+///
+/// // φ(x) = π/4 · x on two qubits needs only single-qubit phases
+/// let oracle = phase_oracle(2, |x| PI / 4.0 * x as f64);
+/// assert_eq!(oracle.len(), 2);
+/// ```
+pub fn phase_oracle(n_qubits: usize, phases: impl Fn(usize) -> f64) -> Circuit {
+    // θ_S by Möbius inversion of φ over the subset lattice
+    let mut theta: Vec<f64> = (0..1usize << n_qubits).map(phases).collect();
+    for i in 0..n_qubits {
+        for x in 0..theta.len() {
+            if (x >> i) & 1 == 1 {
+                theta[x] -= theta[x ^ (1 << i)];
+            }
+        }
+    }
+
+    let mut circuit = Circuit::new(n_qubits);
+    for (subset, &angle) in theta.iter().enumerate().skip(1) {
+        let angle = angle.rem_euclid(2.0 * PI);
+        if !(PHASE_EPSILON..=2.0 * PI - PHASE_EPSILON).contains(&angle) {
+            continue;
+        }
+        let qubits: Vec<usize> = (0..n_qubits).filter(|&i| (subset >> i) & 1 == 1).collect();
+        let (&target, controls) = qubits.split_last().expect("subset is nonempty");
+        circuit = multi_controlled_phase(circuit, angle, controls, target);
+    }
+    circuit
+}
+
+/// A phase oracle flipping the sign of every state in `marked`.
+///
+/// The boolean case of [`phase_oracle`] with φ = π on the marked states,
+/// built directly: each state's zero bits are mapped to one with X, then an
+/// n-qubit multi-controlled Z ([`Circuit::mcz`]) flips it. Duplicates are
+/// ignored. This is the oracle [`GroverSearch`](crate::GroverSearch) uses
+/// for explicit targets.
+///
+/// From four qubits up the multi-controlled Z borrows n - 3 clean
+/// ancillas, appended after the search qubits: the circuit has
+/// `n_qubits + n_qubits.saturating_sub(3)` qubits, and the ancillas must
+/// start in |0⟩, where they are left.
+///
+/// # Panics
+///
+/// Panics if a marked state is >= 2^n_qubits.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_algorithms::{oracle::marking_oracle, GroverSearch};
+///
+/// let oracle = marking_oracle(3, &[5]);
+/// assert_eq!(oracle.instructions(), GroverSearch::new(3, 5).oracle_circuit().instructions());
+///
+/// // One ancilla on four qubits
+/// assert_eq!(marking_oracle(4, &[5]).num_qubits(), 5);
+/// ```
+pub fn marking_oracle(n_qubits: usize, marked: &[usize]) -> Circuit {
+    let max_state = 1 << n_qubits;
+    let mut states = marked.to_vec();
+    for &state in &states {
+        assert!(
+            state < max_state,
+            "Marked state {} is too large for {} qubits (max: {})",
+            state,
+            n_qubits,
+            max_state - 1
+        );
+    }
+    states.sort_unstable();
+    states.dedup();
+
+    let qubits: Vec<usize> = (0..n_qubits).collect();
+    let ancillas: Vec<usize> = (n_qubits..n_qubits + n_qubits.saturating_sub(3)).collect();
+    let mut circuit = Circuit::new(n_qubits + ancillas.len());
+    for state in states {
+        let zeros: Vec<usize> = (0..n_qubits).filter(|&i| (state >> i) & 1 == 0).collect();
+        for &i in &zeros {
+            circuit = circuit.x(i);
+        }
+        circuit = circuit.mcz(&qubits, &ancillas);
+        for &i in &zeros {
+            circuit = circuit.x(i);
+        }
+    }
+    circuit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_core::Complex;
+    use homaya_sim::{Simulator, StateVector};
+
+    /// The oracle applied to the uniform superposition on its first `n` qubits.
+    fn on_uniform(oracle: &Circuit, n: usize) -> StateVector {
+        let mut circuit = Circuit::new(oracle.num_qubits());
+        for i in 0..n {
+            circuit = circuit.h(i);
+        }
+        Simulator::new().run(&circuit.append(oracle)).unwrap()
+    }
+
+    #[test]
+    fn test_marking_oracle_flips_only_marked() {
+        let marked = [0, 5, 6, 15, 31];
+        let oracle = marking_oracle(5, &marked);
+        assert_eq!(oracle.num_qubits(), 7);
+        let state = on_uniform(&oracle, 5);
+
+        // Ancillas back in |0⟩: nothing outside the first 32 amplitudes
+        let amplitude = 32f64.sqrt().recip();
+        for x in 0..128 {
+            let expected = match x {
+                32.. => 0.0,
+                _ if marked.contains(&x) => -amplitude,
+                _ => amplitude,
+            };
+            assert!(state.amplitude(x).approx_eq(Complex::from_real(expected), 1e-10), "{}", x);
+        }
+    }
+
+    #[test]
+    fn test_marking_oracle_is_linear_in_n() {
+        // X around one ladder: 2n - 3 gates for the Z, at most 2n X gates
+        for n in 4..=12 {
+            let oracle = marking_oracle(n, &[0]);
+            assert_eq!(oracle.gate_count(), 2 * n - 3 + 2 * n, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_marking_oracle_ignores_duplicates() {
+        assert_eq!(marking_oracle(3, &[2, 2]).instructions(), marking_oracle(3, &[2]).instructions());
+        assert!(marking_oracle(3, &[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Marked state 8 is too large for 3 qubits")]
+    fn test_marking_oracle_rejects_large_state() {
+        marking_oracle(3, &[8]);
+    }
+
+    #[test]
+    fn test_phase_oracle_matches_phases() {
+        let phases = |x: usize| 0.3 * (x * x) as f64 + if x == 5 { PI } else { 0.0 };
+        let state = on_uniform(&phase_oracle(3, phases), 3);
+
+        let expected: Vec<Complex> = (0..8).map(|x| Complex::from_polar(8f64.sqrt().recip(), phases(x))).collect();
+        let expected = StateVector::from_amplitudes(expected).unwrap();
+        assert!(state.approx_eq_up_to_global_phase(&expected, 1e-10));
+    }
+
+    #[test]
+    fn test_phase_oracle_of_marking_phases() {
+        let oracle = phase_oracle(3, |x| if x == 3 || x == 4 { PI } else { 0.0 });
+        let a = on_uniform(&oracle, 3);
+        let b = on_uniform(&marking_oracle(3, &[3, 4]), 3);
+        assert!(a.approx_eq_up_to_global_phase(&b, 1e-10));
+
+        // A constant phase is global: nothing to do
+        assert!(phase_oracle(3, |_| 1.0).is_empty());
+    }
+}
@@ -0,0 +1,299 @@
+//! # Quantum Phase Estimation
+//!
+//! Read off the eigenphase of a unitary.
+//!
+//! ## The Problem
+//!
+//! A unitary U has an eigenstate |ψ⟩ with U|ψ⟩ = e^(2πiφ)|ψ⟩.
+//! Given a circuit for U and a way to prepare |ψ⟩, find φ ∈ [0, 1).
+//!
+//! ## How It Works
+//!
+//! 1. Put t counting qubits in uniform superposition
+//! 2. Prepare |ψ⟩ on the target register
+//! 3. Counting qubit j controls U^(2^j) - each kicks back phase 2^j·φ
+//! 4. Apply the inverse QFT to the counting register
+//! 5. Measure: the counting register holds φ as a t-bit binary fraction
+//!
+//! ## Precision
+//!
+//! With t counting qubits the estimate is a multiple of 1/2^t. A phase that
+//! is exactly such a multiple is measured with certainty. Otherwise the
+//! nearest multiple, within 1/2^(t+1) of φ, comes out with probability at
+//! least 4/π² ≈ 0.405, and the error stays below 1/2^t with probability at
+//! least 8/π² ≈ 0.81. Each extra counting qubit halves the resolution but
+//! doubles the controlled applications of U.
+//!
+//! ## Controlled Powers
+//!
+//! By default U^(2^j) is built by repeating controlled-U 2^j times, so the
+//! circuit grows exponentially with t. When U has a cheap closed form for its
+//! powers (a phase gate, modular exponentiation) supply it with
+//! [`PhaseEstimation::with_controlled_power`] instead.
+
+use std::fmt;
+use std::rc::Rc;
+
+use homaya_core::{Circuit, Result};
+use homaya_sim::Simulator;
+
+use crate::qft::inverse_qft;
+
+/// Builds the circuit applying controlled-U^(2^j) for counting qubit j.
+type ControlledPower = Rc<dyn Fn(usize) -> Circuit>;
+
+/// Quantum Phase Estimation builder.
+///
+/// Qubits `0..counting_qubits` form the counting register, the unitary acts
+/// on the qubits after it.
+#[derive(Clone)]
+pub struct PhaseEstimation {
+    /// Number of counting qubits (bits of precision)
+    counting_qubits: usize,
+    /// The unitary U, acting on its own qubits 0..m
+    unitary: Circuit,
+    /// Preparation of the eigenstate on the target register
+    eigenstate: Option<Circuit>,
+    /// User-supplied controlled-U^(2^j) builder
+    controlled_power: Option<ControlledPower>,
+}
+
+impl fmt::Debug for PhaseEstimation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PhaseEstimation")
+            .field("counting_qubits", &self.counting_qubits)
+            .field("unitary", &self.unitary)
+            .field("eigenstate", &self.eigenstate)
+            .field("controlled_power", &self.controlled_power.is_some())
+            .finish()
+    }
+}
+
+impl PhaseEstimation {
+    /// Create a new phase estimation instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `counting_qubits` - Bits of precision in the phase estimate
+    /// * `unitary` - Circuit for U on its own qubits
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::PhaseEstimation;
+    /// use homaya_core::Circuit;
+    ///
+    /// // T|1⟩ = e^(iπ/4)|1⟩, so φ = 1/8
+    /// let qpe = PhaseEstimation::new(3, Circuit::new(1).t(0))
+    ///     .with_eigenstate(Circuit::new(1).x(0));
+    /// assert_eq!(qpe.estimate().unwrap(), 0.125);
+    /// ```
+    pub fn new(counting_qubits: usize, unitary: Circuit) -> Self {
+        assert!(counting_qubits >= 1, "Need at least 1 counting qubit");
+        Self {
+            counting_qubits,
+            unitary,
+            eigenstate: None,
+            controlled_power: None,
+        }
+    }
+
+    /// Prepare the eigenstate on the target register before estimation.
+    ///
+    /// Without one the target register starts in |0...0⟩.
+    pub fn with_eigenstate(mut self, prep: Circuit) -> Self {
+        self.eigenstate = Some(prep);
+        self
+    }
+
+    /// Supply an efficient builder for controlled-U^(2^j).
+    ///
+    /// `controlled_u_power(j)` must return a circuit over the full register
+    /// (`total_qubits()` wide) applying U^(2^j) to the target qubits,
+    /// controlled on counting qubit `j`.
+    pub fn with_controlled_power(mut self, controlled_u_power: impl Fn(usize) -> Circuit + 'static) -> Self {
+        self.controlled_power = Some(Rc::new(controlled_u_power));
+        self
+    }
+
+    /// Total number of qubits: counting register plus target register.
+    pub fn total_qubits(&self) -> usize {
+        self.counting_qubits + self.unitary.num_qubits()
+    }
+
+    /// Build the phase estimation circuit, measuring the counting register.
+    ///
+    /// Counting qubit j is measured into classical bit j.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the default controlled-power path meets a gate
+    /// [`Circuit::controlled`] cannot control.
+    pub fn build(&self) -> Result<Circuit> {
+        let mut circuit = self.build_unmeasured()?;
+        circuit = circuit.measure_range(0..self.counting_qubits);
+        Ok(circuit)
+    }
+
+    /// Simulate the circuit and return the most likely phase φ.
+    pub fn estimate(&self) -> Result<f64> {
+        let state = Simulator::new().run(&self.build_unmeasured()?)?;
+
+        let counting: Vec<usize> = (0..self.counting_qubits).collect();
+        let marginal = state.marginal_probabilities(&counting);
+
+        let measured = marginal
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+            .unwrap_or(0);
+
+        Ok(self.phase_from_measurement(measured))
+    }
+
+    /// Convert a measured counting-register value into the phase φ = m / 2^t.
+    pub fn phase_from_measurement(&self, measured: usize) -> f64 {
+        measured as f64 / (1usize << self.counting_qubits) as f64
+    }
+
+    /// Build the circuit up to (but excluding) measurement.
+    fn build_unmeasured(&self) -> Result<Circuit> {
+        let t = self.counting_qubits;
+        let mut circuit = Circuit::new(self.total_qubits());
+
+        // Step 1: Uniform superposition on the counting register
+        for i in 0..t {
+            circuit = circuit.h(i);
+        }
+
+        // Step 2: Prepare the eigenstate on the target register
+        if let Some(prep) = &self.eigenstate {
+            circuit = circuit.compose(&Circuit::new(t).tensor(prep))?;
+        }
+
+        // Step 3: Counting qubit j controls U^(2^j)
+        let shifted = Circuit::new(t).tensor(&self.unitary);
+        for j in 0..t {
+            circuit = match &self.controlled_power {
+                Some(controlled_u_power) => circuit.compose(&controlled_u_power(j))?,
+                None => {
+                    let controlled_u = shifted.controlled(j)?;
+                    for _ in 0..(1usize << j) {
+                        circuit = circuit.compose(&controlled_u)?;
+                    }
+                    circuit
+                }
+            };
+        }
+
+        // Step 4: Inverse QFT on the counting register
+        circuit.compose(&inverse_qft(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_core::{HomayaError, PI};
+
+    #[test]
+    fn test_circuit_width() {
+        let qpe = PhaseEstimation::new(4, Circuit::new(2).cz(0, 1));
+        let circuit = qpe.build().unwrap();
+        assert_eq!(circuit.num_qubits(), 6);
+        assert_eq!(circuit.num_clbits(), 4);
+    }
+
+    #[test]
+    fn test_naive_powers_recover_phase() {
+        // S|1⟩ = e^(iπ/2)|1⟩ → φ = 1/4
+        let qpe = PhaseEstimation::new(3, Circuit::new(1).s(0)).with_eigenstate(Circuit::new(1).x(0));
+        assert_eq!(qpe.estimate().unwrap(), 0.25);
+    }
+
+    #[test]
+    fn test_efficient_powers_match_naive() {
+        // P(2π·3/8)|1⟩ → φ = 0.375 = 0.011₂
+        let phi = 0.375;
+        let t = 3;
+        let unitary = Circuit::new(1).p(2.0 * PI * phi, 0);
+        let eigenstate = Circuit::new(1).x(0);
+
+        let naive = PhaseEstimation::new(t, unitary.clone()).with_eigenstate(eigenstate.clone());
+
+        // U^(2^j) = P(2π·φ·2^j): one gate per counting qubit instead of 2^j
+        let efficient = PhaseEstimation::new(t, unitary)
+            .with_eigenstate(eigenstate)
+            .with_controlled_power(move |j| {
+                let angle = 2.0 * PI * phi * (1 << j) as f64;
+                Circuit::new(t + 1).cp(angle, j, t)
+            });
+
+        assert_eq!(naive.estimate().unwrap(), phi);
+        assert_eq!(efficient.estimate().unwrap(), naive.estimate().unwrap());
+        assert!(efficient.build().unwrap().len() < naive.build().unwrap().len());
+    }
+
+    #[test]
+    fn test_iterative_estimation_reuses_ancilla() {
+        // φ = 0.10₂: one ancilla (qubit 0) measured and reset each round,
+        // least significant bit first. The second round's phase correction
+        // for a 0 first bit is the identity, so no feedforward is needed.
+        let phi = 0.5;
+        let mut circuit = Circuit::new(2).x(1);
+        for (round, power) in [2.0, 1.0].into_iter().enumerate() {
+            circuit = circuit
+                .h(0)
+                .cp(2.0 * PI * phi * power, 0, 1)
+                .h(0)
+                .measure_reset(0, round);
+        }
+
+        let counts = Simulator::with_seed(11).sample(&circuit, 50).unwrap();
+        assert_eq!(counts.len(), 1);
+
+        // Clbit 0 holds φ₂, clbit 1 holds φ₁
+        let (bits, _) = counts.into_iter().next().unwrap();
+        let measured = homaya_core::parse_outcome(&bits).unwrap();
+        assert_eq!(PhaseEstimation::new(2, Circuit::new(1)).phase_from_measurement(measured), phi);
+    }
+
+    #[test]
+    fn test_rz_kickback_within_resolution() {
+        // Rz(θ)|1⟩ = e^(iθ/2)|1⟩ → φ = θ/4π = 0.3, not a multiple of 1/16
+        let phi = 0.3;
+        let t = 4;
+        let qpe = PhaseEstimation::new(t, Circuit::new(1).rz(4.0 * PI * phi, 0))
+            .with_eigenstate(Circuit::new(1).x(0));
+
+        let resolution = 1.0 / (1 << t) as f64;
+        assert!((qpe.estimate().unwrap() - phi).abs() <= resolution / 2.0);
+
+        let counts = Simulator::with_seed(13).sample(&qpe.build().unwrap(), 500).unwrap();
+        let measured = homaya_core::most_likely_outcome(&counts).unwrap();
+        assert!((qpe.phase_from_measurement(measured) - phi).abs() <= resolution / 2.0);
+    }
+
+    #[test]
+    fn test_uncontrollable_gate_errors() {
+        let qpe = PhaseEstimation::new(2, Circuit::new(3).cswap(0, 1, 2));
+        assert!(matches!(qpe.build(), Err(HomayaError::NotSupported { .. })));
+    }
+
+    #[test]
+    fn test_toffoli_kickback() {
+        // CCX on |11−⟩ = -|11−⟩ → φ = 1/2
+        let eigenstate = Circuit::new(3).x(0).x(1).x(2).h(2);
+        let qpe = PhaseEstimation::new(2, Circuit::new(3).ccx(0, 1, 2)).with_eigenstate(eigenstate);
+        assert_eq!(qpe.estimate().unwrap(), 0.5);
+    }
+
+    #[test]
+    fn test_u_gate_kickback() {
+        // U(0, 0, λ) = P(λ), so |1⟩ picks up φ = λ/2π
+        let qpe = PhaseEstimation::new(3, Circuit::new(1).u(0.0, 0.0, 2.0 * PI * 0.625, 0))
+            .with_eigenstate(Circuit::new(1).x(0));
+        assert_eq!(qpe.estimate().unwrap(), 0.625);
+    }
+}
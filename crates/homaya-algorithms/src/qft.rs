@@ -0,0 +1,172 @@
+//! # Quantum Fourier Transform
+//!
+//! The discrete Fourier transform on the amplitudes of a register.
+//!
+//! ## What It Does
+//!
+//! On n qubits with N = 2^n, the QFT maps each basis state
+//!
+//! |x⟩ → (1/√N) Σ_y e^(2πi·xy/N) |y⟩
+//!
+//! It is the workhorse behind phase estimation, Shor's algorithm and
+//! quantum counting: it turns a phase that grows linearly with x into a
+//! basis state you can measure.
+//!
+//! ## How It Works
+//!
+//! Working down from the most significant qubit, each qubit gets a Hadamard
+//! followed by controlled-phase rotations P(π/2^k) from every less
+//! significant qubit. That leaves the output bit-reversed, so a final row of
+//! SWAPs puts qubit 0 back as the least significant bit.
+//!
+//! Prerequisites: the controlled-phase ([`Circuit::cp`]) and
+//! [`Circuit::swap`] gates. The circuit uses n(n-1)/2 controlled phases and
+//! ⌊n/2⌋ swaps.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use homaya_algorithms::qft;
+//!
+//! let forward = qft::qft(3);
+//! let round_trip = forward.compose(&qft::inverse_qft(3)).unwrap();
+//! assert_eq!(round_trip.len(), 2 * qft::qft(3).len());
+//! ```
+
+use homaya_core::{Circuit, PI};
+
+/// Quantum Fourier Transform builder.
+///
+/// Acts on qubits `0..n_qubits`, with qubit 0 the least significant bit.
+#[derive(Debug, Clone)]
+pub struct QuantumFourierTransform {
+    /// Number of qubits in the register
+    n_qubits: usize,
+    /// Whether to undo the bit reversal with SWAPs
+    swaps: bool,
+}
+
+impl QuantumFourierTransform {
+    /// Create a QFT on `n_qubits` qubits, including the final swaps.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::QuantumFourierTransform;
+    ///
+    /// let circuit = QuantumFourierTransform::new(4).build();
+    /// assert_eq!(circuit.num_qubits(), 4);
+    /// ```
+    pub fn new(n_qubits: usize) -> Self {
+        Self { n_qubits, swaps: true }
+    }
+
+    /// Choose whether to apply the bit-reversal SWAPs.
+    ///
+    /// Without them the output is bit-reversed: qubit n-1 holds the least
+    /// significant bit. Skipping them saves gates when a later step can
+    /// just relabel the qubits instead.
+    pub fn with_swaps(mut self, swaps: bool) -> Self {
+        self.swaps = swaps;
+        self
+    }
+
+    /// Number of qubits in the register.
+    pub fn num_qubits(&self) -> usize {
+        self.n_qubits
+    }
+
+    /// Build the QFT circuit.
+    pub fn build(&self) -> Circuit {
+        let n = self.n_qubits;
+        let mut circuit = Circuit::new(n);
+
+        for j in (0..n).rev() {
+            circuit = circuit.h(j);
+            for k in (0..j).rev() {
+                circuit = circuit.cp(PI / (1usize << (j - k)) as f64, k, j);
+            }
+        }
+
+        if self.swaps {
+            for i in 0..n / 2 {
+                circuit = circuit.swap(i, n - 1 - i);
+            }
+        }
+
+        circuit
+    }
+
+    /// Build the inverse QFT circuit.
+    pub fn build_inverse(&self) -> Circuit {
+        self.build().inverse()
+    }
+}
+
+/// Convenience function: the QFT on `n_qubits`, including the swaps.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_algorithms::qft;
+///
+/// let circuit = qft::qft(3);  // 3 H, 3 CP, 1 SWAP
+/// assert_eq!(circuit.len(), 7);
+/// ```
+pub fn qft(n_qubits: usize) -> Circuit {
+    QuantumFourierTransform::new(n_qubits).build()
+}
+
+/// Convenience function: the inverse QFT on `n_qubits`, including the swaps.
+pub fn inverse_qft(n_qubits: usize) -> Circuit {
+    QuantumFourierTransform::new(n_qubits).build_inverse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_core::Complex;
+    use homaya_sim::Simulator;
+
+    #[test]
+    fn test_qft_of_basis_state() {
+        let n = 3;
+        let dim = 1usize << n;
+        let x = 5;
+
+        let prep = Circuit::new(n).x(0).x(2);
+        let state = Simulator::new().run(&prep.compose(&qft(n)).unwrap()).unwrap();
+
+        for y in 0..dim {
+            let expected = Complex::from_polar(1.0 / (dim as f64).sqrt(), 2.0 * PI * (x * y) as f64 / dim as f64);
+            assert!(state.amplitude(y).approx_eq(expected, 1e-10), "amplitude {}", y);
+        }
+    }
+
+    #[test]
+    fn test_inverse_undoes_qft() {
+        // An arbitrary, non-basis input state
+        let prep = Circuit::new(3).h(0).ry(0.4, 1).cx(0, 2).t(2).rx(1.1, 1);
+        let round_trip = prep.clone().compose(&qft(3)).unwrap().compose(&inverse_qft(3)).unwrap();
+
+        let mut sim = Simulator::new();
+        let input = sim.run(&prep).unwrap();
+        let output = sim.run(&round_trip).unwrap();
+        assert!((input.inner_product(&output).norm_sqr() - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_without_swaps() {
+        let with = QuantumFourierTransform::new(4).build();
+        let without = QuantumFourierTransform::new(4).with_swaps(false).build();
+        assert_eq!(with.len() - without.len(), 2);
+
+        // Skipping the swaps leaves the Fourier basis bit-reversed
+        let reversed = without.swap(0, 3).swap(1, 2);
+        let mut sim = Simulator::new();
+        let prep = Circuit::new(4).x(1).h(3);
+        let a = sim.run(&prep.clone().compose(&with).unwrap()).unwrap();
+        let b = sim.run(&prep.compose(&reversed).unwrap()).unwrap();
+        assert!((a.inner_product(&b).norm_sqr() - 1.0).abs() < 1e-10);
+    }
+}
@@ -0,0 +1,428 @@
+//! # Shor's Period Finding
+//!
+//! Find the multiplicative order of a modulo N, the quantum core of Shor's
+//! factoring algorithm.
+//!
+//! ## The Problem
+//!
+//! Given a coprime to N, find the smallest r > 0 with a^r ≡ 1 (mod N).
+//! When r is even and a^(r/2) ≢ -1 (mod N), gcd(a^(r/2) ± 1, N) are
+//! nontrivial factors of N. Classically finding r takes time exponential in
+//! the number of digits of N; the quantum part below does it in polynomial
+//! time given an efficient modular multiplier.
+//!
+//! ## How It Works
+//!
+//! U|y⟩ = |a·y mod N⟩ has eigenvalues e^(2πi·s/r) for s = 0..r, and |1⟩ is
+//! an equal mix of their eigenvectors. So this is
+//! [phase estimation](crate::phase_estimation) of U starting from |1⟩:
+//!
+//! 1. Put t counting qubits in uniform superposition, the work register in |1⟩
+//! 2. Counting qubit j controls U^(2^j), multiplication by a^(2^j) mod N
+//! 3. Apply the inverse QFT to the counting register and measure m ≈ 2^t·s/r
+//! 4. Classically expand m/2^t as a continued fraction to get r
+//!    ([`estimate_period`])
+//!
+//! With 2^t ≥ N² the expansion recovers r whenever s is coprime to r;
+//! otherwise it finds a divisor of r, which is why
+//! [`ShorPeriodFinding::find_period`] checks multiples against a^r ≡ 1.
+//!
+//! ## Limitations
+//!
+//! Modular multiplication is a table-driven permutation oracle: the table
+//! y → b·y mod N is split into transpositions of basis states, each a
+//! multi-controlled X on the m = ⌈log₂ N⌉ work qubits. That is exact, but
+//! it takes O(N·3^m) gates and a classical table of size 2^m where real
+//! Shor uses polynomial-size arithmetic circuits. It is meant for small
+//! moduli (N up to a few dozen) to show the algorithm's structure, not to
+//! factor anything hard.
+
+use homaya_core::{parse_outcome, Circuit, PI};
+use homaya_sim::Simulator;
+
+use crate::grover::multi_controlled_phase;
+use crate::{AlgorithmError, PhaseEstimation};
+
+/// Order-finding circuit for a^x mod N.
+///
+/// Qubits `0..n_count` form the counting register, the work register of
+/// [`work_qubits`](Self::work_qubits) qubits follows it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShorPeriodFinding {
+    /// Counting qubits (bits of precision in s/r)
+    n_count: usize,
+    /// The base a
+    a: usize,
+    /// The modulus N
+    modulus: usize,
+}
+
+impl ShorPeriodFinding {
+    /// Create a new period-finding instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `n_count` - Counting qubits; 2·⌈log₂ N⌉ guarantees the period can
+    ///   be read off
+    /// * `a` - Base whose order is wanted, in 2..N and coprime to N
+    /// * `modulus` - The modulus N, at least 3
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid arguments; see [`try_new`](Self::try_new) for a
+    /// non-panicking version.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::ShorPeriodFinding;
+    ///
+    /// // 7^4 = 2401 = 160·15 + 1
+    /// let shor = ShorPeriodFinding::new(8, 7, 15);
+    /// assert_eq!(shor.find_period(50, 42), Some(4));
+    /// ```
+    pub fn new(n_count: usize, a: usize, modulus: usize) -> Self {
+        Self::try_new(n_count, a, modulus).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Create a new period-finding instance, validating the arguments.
+    ///
+    /// # Errors
+    ///
+    /// [`AlgorithmError::ZeroQubits`] without counting qubits,
+    /// [`AlgorithmError::TooManyQubits`] if 2^n_count overflows a `usize`,
+    /// [`AlgorithmError::InvalidModulus`] for N < 3 and
+    /// [`AlgorithmError::InvalidBase`] unless 2 ≤ a < N with gcd(a, N) = 1.
+    pub fn try_new(n_count: usize, a: usize, modulus: usize) -> Result<Self, AlgorithmError> {
+        if n_count == 0 {
+            return Err(AlgorithmError::ZeroQubits);
+        }
+        if n_count >= usize::BITS as usize {
+            return Err(AlgorithmError::TooManyQubits { n_qubits: n_count });
+        }
+        if modulus < 3 {
+            return Err(AlgorithmError::InvalidModulus { modulus });
+        }
+        if a < 2 || a >= modulus || gcd(a, modulus) != 1 {
+            return Err(AlgorithmError::InvalidBase { a, modulus });
+        }
+        Ok(Self { n_count, a, modulus })
+    }
+
+    /// Number of work qubits, enough to hold 0..N.
+    pub fn work_qubits(&self) -> usize {
+        bit_length(self.modulus - 1)
+    }
+
+    /// Total number of qubits: counting register plus work register.
+    pub fn total_qubits(&self) -> usize {
+        self.n_count + self.work_qubits()
+    }
+
+    /// Build the circuit, measuring the counting register.
+    ///
+    /// Counting qubit j is measured into classical bit j, so the measured
+    /// integer m approximates 2^n_count·s/r.
+    pub fn build(&self) -> Circuit {
+        let (t, m) = (self.n_count, self.work_qubits());
+        let work: Vec<usize> = (0..m).collect();
+        let unitary = apply_multiplication(Circuit::new(m), self.a, self.modulus, &work, None);
+
+        let Self { a, modulus, .. } = *self;
+        PhaseEstimation::new(t, unitary)
+            .with_eigenstate(Circuit::new(m).x(0))
+            .with_controlled_power(move |j| {
+                let work: Vec<usize> = (t..t + m).collect();
+                let multiplier = pow_mod(a, 1 << j, modulus);
+                apply_multiplication(Circuit::new(t + m), multiplier, modulus, &work, Some(j))
+            })
+            .build()
+            .expect("permutation oracles have the work register's width")
+    }
+
+    /// Sample the circuit and return the smallest verified period.
+    ///
+    /// Every sampled outcome goes through [`estimate_period`]; a candidate
+    /// r, or the first of its multiples up to N, counts once a^r ≡ 1
+    /// (mod N). Returns `None` if no outcome yields one, e.g. with too few
+    /// shots or counting qubits.
+    pub fn find_period(&self, shots: usize, seed: u64) -> Option<usize> {
+        let counts = Simulator::with_seed(seed).sample(&self.build(), shots).ok()?;
+
+        counts
+            .keys()
+            .filter_map(|bits| estimate_period(parse_outcome(bits)?, self.n_count))
+            .filter_map(|r| (r..=self.modulus).step_by(r).find(|&k| pow_mod(self.a, k, self.modulus) == 1))
+            .min()
+    }
+
+    /// Split N using the period r of a, as in Shor's classical post-processing.
+    ///
+    /// Returns the two factors in increasing order, or `None` when r is odd
+    /// or a^(r/2) ≡ -1 (mod N) and another base must be tried.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::ShorPeriodFinding;
+    ///
+    /// // 7^2 = 49 ≡ 4 (mod 15): gcd(3, 15) = 3, gcd(5, 15) = 5
+    /// assert_eq!(ShorPeriodFinding::new(8, 7, 15).factors_from_period(4), Some((3, 5)));
+    /// ```
+    pub fn factors_from_period(&self, r: usize) -> Option<(usize, usize)> {
+        if r == 0 || r % 2 == 1 {
+            return None;
+        }
+        let half = pow_mod(self.a, r / 2, self.modulus);
+        if half == self.modulus - 1 {
+            return None;
+        }
+
+        let factor = gcd(half + 1, self.modulus);
+        if factor == 1 || factor == self.modulus {
+            return None;
+        }
+        let other = self.modulus / factor;
+        Some((factor.min(other), factor.max(other)))
+    }
+}
+
+/// Guess the period r from a measured counting register value.
+///
+/// Expands m/2^n_count as a continued fraction and returns the largest
+/// convergent denominator that is at most 2^⌈n_count/2⌉, the bound on r
+/// when 2^n_count ≥ N². If s and r share a factor this is a divisor of r.
+/// Returns `None` for m = 0, which carries no information.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_algorithms::shor::estimate_period;
+///
+/// // 192/256 = 3/4
+/// assert_eq!(estimate_period(192, 8), Some(4));
+/// assert_eq!(estimate_period(0, 8), None);
+/// ```
+pub fn estimate_period(measured: usize, n_count: usize) -> Option<usize> {
+    if measured == 0 || n_count >= usize::BITS as usize || measured >= 1 << n_count {
+        return None;
+    }
+    let limit = 1usize << n_count.div_ceil(2);
+
+    // Convergent denominators: k_i = a_i·k_(i-1) + k_(i-2)
+    let (mut numerator, mut denominator) = (measured, 1usize << n_count);
+    let (mut k_prev, mut k) = (1usize, 0usize);
+    let mut best = None;
+    while denominator != 0 {
+        let term = numerator / denominator;
+        let next = term.checked_mul(k).and_then(|x| x.checked_add(k_prev))?;
+        if next > limit {
+            break;
+        }
+        (k_prev, k) = (k, next);
+        best = Some(next);
+        (numerator, denominator) = (denominator, numerator - term * denominator);
+    }
+
+    best.filter(|&r| r > 1)
+}
+
+/// Permute `work` by y → multiplier·y mod N, leaving y ≥ N alone.
+///
+/// Each cycle (c0 c1 ... ) of the table becomes the transpositions
+/// (c0 c1), (c0 c2), ..., applied in that order.
+fn apply_multiplication(
+    mut circuit: Circuit,
+    multiplier: usize,
+    modulus: usize,
+    work: &[usize],
+    control: Option<usize>,
+) -> Circuit {
+    let mut visited = vec![false; modulus];
+    for start in 0..modulus {
+        if visited[start] {
+            continue;
+        }
+        let mut y = start;
+        loop {
+            visited[y] = true;
+            y = (y * multiplier) % modulus;
+            if y == start {
+                break;
+            }
+            circuit = apply_transposition(circuit, start, y, work, control);
+        }
+    }
+    circuit
+}
+
+/// Swap basis states |u⟩ and |v⟩ of `work`, optionally controlled.
+///
+/// CX gates from a bit p where they differ make the two states differ in
+/// p alone; a multi-controlled X on p, conditioned on the remaining bits
+/// and `control`, swaps them; the CX gates are then undone.
+fn apply_transposition(mut circuit: Circuit, u: usize, v: usize, work: &[usize], control: Option<usize>) -> Circuit {
+    let diff = u ^ v;
+    let p = diff.trailing_zeros() as usize;
+    let spread: Vec<usize> = (0..work.len()).filter(|&k| k != p && (diff >> k) & 1 == 1).collect();
+    // After the CX gates both states carry the bits of the one with p = 0
+    let reference = if (u >> p) & 1 == 0 { u } else { v };
+    let zeros: Vec<usize> = (0..work.len()).filter(|&k| k != p && (reference >> k) & 1 == 0).collect();
+
+    for &k in &spread {
+        circuit = circuit.cx(work[p], work[k]);
+    }
+    for &k in &zeros {
+        circuit = circuit.x(work[k]);
+    }
+
+    let controls: Vec<usize> = control
+        .into_iter()
+        .chain((0..work.len()).filter(|&k| k != p).map(|k| work[k]))
+        .collect();
+    circuit = multi_controlled_phase(circuit.h(work[p]), PI, &controls, work[p]).h(work[p]);
+
+    for &k in &zeros {
+        circuit = circuit.x(work[k]);
+    }
+    for &k in &spread {
+        circuit = circuit.cx(work[p], work[k]);
+    }
+    circuit
+}
+
+/// base^exponent mod modulus, by repeated squaring.
+fn pow_mod(base: usize, mut exponent: usize, modulus: usize) -> usize {
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result as usize
+}
+
+/// Bits needed to write `x`.
+fn bit_length(x: usize) -> usize {
+    (usize::BITS - x.leading_zeros()) as usize
+}
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Convenience function to find the order of `a` modulo `modulus`.
+///
+/// Uses 2·⌈log₂ N⌉ counting qubits and 100 shots from a seeded simulator.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_algorithms::shor;
+///
+/// assert_eq!(shor::find_period(2, 15), Some(4));
+/// ```
+pub fn find_period(a: usize, modulus: usize) -> Option<usize> {
+    ShorPeriodFinding::new(2 * bit_length(modulus.saturating_sub(1)), a, modulus).find_period(100, 42)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_new_errors() {
+        assert_eq!(ShorPeriodFinding::try_new(0, 7, 15).unwrap_err(), AlgorithmError::ZeroQubits);
+        assert_eq!(
+            ShorPeriodFinding::try_new(4, 2, 2).unwrap_err(),
+            AlgorithmError::InvalidModulus { modulus: 2 }
+        );
+        assert_eq!(
+            ShorPeriodFinding::try_new(4, 5, 15).unwrap_err(),
+            AlgorithmError::InvalidBase { a: 5, modulus: 15 }
+        );
+        assert_eq!(
+            ShorPeriodFinding::try_new(4, 15, 15).unwrap_err(),
+            AlgorithmError::InvalidBase { a: 15, modulus: 15 }
+        );
+    }
+
+    #[test]
+    fn test_register_sizes() {
+        let shor = ShorPeriodFinding::new(8, 7, 15);
+        assert_eq!(shor.work_qubits(), 4);
+        assert_eq!(shor.total_qubits(), 12);
+
+        let circuit = shor.build();
+        assert_eq!(circuit.num_qubits(), 12);
+        assert_eq!(circuit.num_clbits(), 8);
+        assert_eq!(ShorPeriodFinding::new(4, 2, 16 + 1).work_qubits(), 5);
+    }
+
+    #[test]
+    fn test_multiplication_permutes_basis_states() {
+        let work = [0, 1, 2, 3];
+        for y in 0..16 {
+            let mut circuit = Circuit::new(4);
+            for (k, &q) in work.iter().enumerate() {
+                if (y >> k) & 1 == 1 {
+                    circuit = circuit.x(q);
+                }
+            }
+            let circuit = apply_multiplication(circuit, 7, 15, &work, None);
+            let state = Simulator::new().run(&circuit).unwrap();
+
+            let expected = if y < 15 { 7 * y % 15 } else { y };
+            let (index, p) = state.most_likely();
+            assert_eq!(index, expected, "7·{} mod 15", y);
+            assert!((p - 1.0).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_estimate_period() {
+        assert_eq!(estimate_period(64, 8), Some(4));
+        assert_eq!(estimate_period(192, 8), Some(4));
+        // s = 2 shares a factor with r = 4
+        assert_eq!(estimate_period(128, 8), Some(2));
+        // 171/1024 ≈ 1/6
+        assert_eq!(estimate_period(171, 10), Some(6));
+        assert_eq!(estimate_period(0, 8), None);
+        assert_eq!(estimate_period(256, 8), None);
+    }
+
+    #[test]
+    fn test_factor_fifteen() {
+        let shor = ShorPeriodFinding::new(8, 7, 15);
+        let period = shor.find_period(100, 7).unwrap();
+
+        assert_eq!(period, 4);
+        assert_eq!(shor.factors_from_period(period), Some((3, 5)));
+    }
+
+    #[test]
+    fn test_counting_register_peaks_at_multiples_of_quarter() {
+        let counts = Simulator::with_seed(3).sample(&ShorPeriodFinding::new(8, 7, 15).build(), 200).unwrap();
+        for bits in counts.keys() {
+            assert_eq!(parse_outcome(bits).unwrap() % 64, 0, "{}", bits);
+        }
+    }
+
+    #[test]
+    fn test_factors_need_even_period() {
+        // 4 has order 2 mod 15, but 4^1 + 1 = 5 still splits 15
+        let shor = ShorPeriodFinding::new(8, 4, 15);
+        assert_eq!(shor.factors_from_period(2), Some((3, 5)));
+        assert_eq!(shor.factors_from_period(3), None);
+
+        // 2 has order 2 mod 3, and 2^1 ≡ -1
+        assert_eq!(ShorPeriodFinding::new(4, 2, 3).factors_from_period(2), None);
+    }
+}
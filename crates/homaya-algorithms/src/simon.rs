@@ -0,0 +1,238 @@
+//! # Simon's Algorithm
+//!
+//! Find a hidden XOR period with exponentially fewer queries.
+//!
+//! ## The Problem
+//!
+//! A black-box function f on n-bit strings hides a secret s: f(x) = f(y)
+//! exactly when y = x or y = x ⊕ s. Find s.
+//!
+//! Classically: Need about 2^(n/2) queries to find a collision.
+//! Quantum: About n queries, each giving one linear equation on s.
+//!
+//! ## How It Works
+//!
+//! 1. Start with two n-qubit registers in |0⟩^n |0⟩^n
+//! 2. Apply H to the first register
+//! 3. Apply the oracle |x⟩|0⟩ → |x⟩|f(x)⟩
+//! 4. Apply H to the first register
+//! 5. Measure the first register → a random y with y · s = 0 (mod 2)
+//!
+//! ## Classical Post-Processing
+//!
+//! Unlike Bernstein-Vazirani, one run does not reveal the secret. Each
+//! measurement is a random string orthogonal to s; collect n - 1 linearly
+//! independent ones and solve the linear system mod 2.
+//! [`SimonsAlgorithm::recover_secret`] does that step.
+
+use homaya_core::Circuit;
+
+/// Simon's algorithm builder.
+///
+/// Qubits `0..n` form the query register, `n..2n` hold f(x).
+#[derive(Debug, Clone)]
+pub struct SimonsAlgorithm {
+    /// Number of bits in the secret
+    n_qubits: usize,
+    /// The hidden period s
+    secret: usize,
+}
+
+impl SimonsAlgorithm {
+    /// Create a new Simon's algorithm instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `n_qubits` - Number of bits in the secret string
+    /// * `secret` - The hidden period as an integer (0 to 2^n - 1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if secret >= 2^n_qubits
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::SimonsAlgorithm;
+    ///
+    /// let simon = SimonsAlgorithm::new(3, 0b110);
+    /// assert_eq!(simon.build().num_qubits(), 6);
+    /// ```
+    pub fn new(n_qubits: usize, secret: usize) -> Self {
+        let max_secret = 1 << n_qubits;
+        assert!(
+            secret < max_secret,
+            "Secret {} is too large for {} qubits (max: {})",
+            secret,
+            n_qubits,
+            max_secret - 1
+        );
+
+        Self { n_qubits, secret }
+    }
+
+    /// Total qubits: the query register plus the output register.
+    pub fn total_qubits(&self) -> usize {
+        2 * self.n_qubits
+    }
+
+    /// Build the circuit for one round.
+    ///
+    /// Measures the query register into classical bits `0..n`. Every outcome
+    /// y satisfies y · s = 0 (mod 2); run it several times and pass the
+    /// outcomes to [`recover_secret`](Self::recover_secret).
+    pub fn build(&self) -> Circuit {
+        let n = self.n_qubits;
+        let mut circuit = Circuit::new(self.total_qubits());
+
+        // Step 1: Superposition over the query register
+        for i in 0..n {
+            circuit = circuit.h(i);
+        }
+
+        // Step 2: Oracle
+        circuit = self.oracle(circuit);
+
+        // Step 3: Interfere the query register
+        for i in 0..n {
+            circuit = circuit.h(i);
+        }
+
+        // Step 4: Measure the query register
+        circuit = circuit.measure_range(0..n);
+
+        circuit
+    }
+
+    /// Append the oracle |x⟩|0⟩ → |x⟩|f(x)⟩.
+    ///
+    /// Copies x into the output register, then XORs in s whenever bit j of x
+    /// is set, where j is the lowest set bit of s. x and x ⊕ s differ in bit
+    /// j, so exactly one of them gets s added and both land on the same
+    /// output.
+    fn oracle(&self, mut circuit: Circuit) -> Circuit {
+        let n = self.n_qubits;
+
+        for i in 0..n {
+            circuit = circuit.cx(i, n + i);
+        }
+
+        if self.secret != 0 {
+            let j = self.secret.trailing_zeros() as usize;
+            for k in 0..n {
+                if (self.secret >> k) & 1 == 1 {
+                    circuit = circuit.cx(j, n + k);
+                }
+            }
+        }
+
+        circuit
+    }
+
+    /// Solve for the secret from measured outcomes.
+    ///
+    /// Runs Gaussian elimination mod 2 on the outcomes. Returns `None` while
+    /// they span fewer than n - 1 dimensions, since several candidates are
+    /// still possible; sample more rounds and try again. Outcomes spanning
+    /// all n dimensions mean s = 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::SimonsAlgorithm;
+    ///
+    /// // Three independent strings orthogonal to s = 1001
+    /// let outcomes = [0b0110, 0b0010, 0b1001];
+    /// assert_eq!(SimonsAlgorithm::recover_secret(4, &outcomes), Some(0b1001));
+    /// ```
+    pub fn recover_secret(n_qubits: usize, outcomes: &[usize]) -> Option<usize> {
+        // Reduced row echelon form, one row per pivot bit
+        let mut rows: Vec<(usize, usize)> = Vec::new();
+        for &outcome in outcomes {
+            let mut row = outcome;
+            for &(pivot, basis) in &rows {
+                if (row >> pivot) & 1 == 1 {
+                    row ^= basis;
+                }
+            }
+            if row == 0 {
+                continue;
+            }
+            let pivot = row.trailing_zeros() as usize;
+            for (_, basis) in rows.iter_mut() {
+                if (*basis >> pivot) & 1 == 1 {
+                    *basis ^= row;
+                }
+            }
+            rows.push((pivot, row));
+        }
+
+        if rows.len() == n_qubits {
+            return Some(0);
+        }
+        if rows.len() + 1 < n_qubits {
+            return None;
+        }
+
+        // One free bit: set it, and each pivot bit must cancel its row
+        let free = (0..n_qubits).find(|&bit| rows.iter().all(|&(pivot, _)| pivot != bit))?;
+        let mut secret = 1 << free;
+        for &(pivot, basis) in &rows {
+            if (basis >> free) & 1 == 1 {
+                secret |= 1 << pivot;
+            }
+        }
+        Some(secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_core::parse_outcome;
+    use homaya_sim::Simulator;
+
+    #[test]
+    fn test_circuit_width() {
+        let circuit = SimonsAlgorithm::new(3, 0b101).build();
+        assert_eq!(circuit.num_qubits(), 6);
+        assert_eq!(circuit.num_clbits(), 3);
+    }
+
+    #[test]
+    fn test_outcomes_orthogonal_to_secret() {
+        for secret in [0b0001, 0b0110, 0b1011, 0b1111] {
+            let simon = SimonsAlgorithm::new(4, secret);
+            let counts = Simulator::with_seed(11).sample(&simon.build(), 200).unwrap();
+
+            let outcomes: Vec<usize> = counts.keys().map(|key| parse_outcome(key).unwrap()).collect();
+            for &y in &outcomes {
+                assert_eq!((y & secret).count_ones() % 2, 0, "y = {:04b}, s = {:04b}", y, secret);
+            }
+            assert_eq!(SimonsAlgorithm::recover_secret(4, &outcomes), Some(secret));
+        }
+    }
+
+    #[test]
+    fn test_zero_secret_is_one_to_one() {
+        // f is injective, so every outcome is equally likely
+        let counts = Simulator::with_seed(5).sample(&SimonsAlgorithm::new(3, 0).build(), 400).unwrap();
+        assert_eq!(counts.len(), 8);
+
+        let outcomes: Vec<usize> = counts.keys().map(|key| parse_outcome(key).unwrap()).collect();
+        assert_eq!(SimonsAlgorithm::recover_secret(3, &outcomes), Some(0));
+    }
+
+    #[test]
+    fn test_recover_needs_enough_equations() {
+        assert_eq!(SimonsAlgorithm::recover_secret(3, &[0b011]), None);
+        assert_eq!(SimonsAlgorithm::recover_secret(3, &[0b011, 0b011, 0]), None);
+        assert_eq!(SimonsAlgorithm::recover_secret(3, &[0b011, 0b100]), Some(0b011));
+    }
+
+    #[test]
+    #[should_panic(expected = "Secret 8 is too large")]
+    fn test_invalid_secret() {
+        SimonsAlgorithm::new(3, 8);
+    }
+}
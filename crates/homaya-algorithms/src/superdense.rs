@@ -0,0 +1,96 @@
+//! # Superdense Coding
+//!
+//! Send two classical bits by transmitting one qubit of a shared Bell pair.
+//!
+//! ## The Protocol
+//!
+//! Alice holds qubit 0 and Bob qubit 1 of the Bell pair |Φ+⟩.
+//!
+//! 1. Alice encodes bits (b1, b0) by applying X if b1 is set and Z if b0
+//!    is set to her qubit, giving one of the four Bell states
+//! 2. She sends her qubit to Bob
+//! 3. Bob runs CX(0, 1) and H(0), rotating the Bell basis onto the
+//!    computational basis, and measures both qubits
+//!
+//! The Bell states are orthogonal, so Bob reads "b1b0" with certainty. It
+//! is the reverse of [teleportation](crate::teleport): there one Bell pair
+//! and two bits move a qubit, here one Bell pair and one qubit move two
+//! bits.
+
+use homaya_core::states::{bell, BellState};
+use homaya_core::Circuit;
+
+/// Alice's half: the shared Bell pair, then her encoding of `(b1, b0)`.
+///
+/// Applies I, X, Z or X then Z to qubit 0 for 00, 10, 01 and 11.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_algorithms::superdense::encode;
+///
+/// // Bell pair (H, CX) plus Alice's X and Z
+/// assert_eq!(encode((true, true)).len(), 4);
+/// ```
+pub fn encode(two_bits: (bool, bool)) -> Circuit {
+    let (b1, b0) = two_bits;
+    let mut circuit = bell(BellState::PhiPlus);
+    if b1 {
+        circuit = circuit.x(0);
+    }
+    if b0 {
+        circuit = circuit.z(0);
+    }
+    circuit
+}
+
+/// Bob's half: the Bell-basis measurement.
+///
+/// Qubit i is measured into classical bit i, so the outcome string reads
+/// "b1b0".
+pub fn decode() -> Circuit {
+    Circuit::new(2).cx(0, 1).h(0).measure_range(0..2)
+}
+
+/// The full protocol: [`encode`] followed by [`decode`].
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_algorithms::superdense::superdense_circuit;
+/// use homaya_sim::Simulator;
+///
+/// let counts = Simulator::new().sample(&superdense_circuit((true, false)), 10).unwrap();
+/// assert_eq!(counts.get("10"), Some(&10));
+/// ```
+pub fn superdense_circuit(two_bits: (bool, bool)) -> Circuit {
+    encode(two_bits).append(&decode())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_sim::Simulator;
+
+    #[test]
+    fn test_recovers_all_four_messages() {
+        for (b1, b0) in [(false, false), (false, true), (true, false), (true, true)] {
+            let expected = format!("{}{}", b1 as u8, b0 as u8);
+            let counts = Simulator::with_seed(11).sample(&superdense_circuit((b1, b0)), 100).unwrap();
+            assert_eq!(counts.len(), 1, "{}: {:?}", expected, counts);
+            assert_eq!(counts.get(&expected), Some(&100), "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_encodings_are_orthogonal_bell_states() {
+        let messages = [(false, false), (false, true), (true, false), (true, true)];
+        let states: Vec<_> = messages.iter().map(|&bits| Simulator::new().run(&encode(bits)).unwrap()).collect();
+        for (i, a) in states.iter().enumerate() {
+            for (j, b) in states.iter().enumerate() {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((a.fidelity(b) - expected).abs() < 1e-10, "{} vs {}", i, j);
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! # Swap Test
+//!
+//! Estimate how much two quantum states overlap.
+//!
+//! ## The Problem
+//!
+//! Given circuits preparing |a⟩ and |b⟩ on the same number of qubits, find
+//! the overlap |⟨a|b⟩|² without reconstructing either state.
+//!
+//! ## How It Works
+//!
+//! 1. Prepare |a⟩ and |b⟩ on two registers, ancilla in |0⟩
+//! 2. Apply H to the ancilla
+//! 3. Controlled on the ancilla, swap the registers qubit by qubit
+//! 4. Apply H to the ancilla and measure it
+//!
+//! The ancilla reads 0 with probability P(0) = (1 + |⟨a|b⟩|²) / 2:
+//! identical states always give 0, orthogonal states give 0 half the time.
+
+use homaya_core::Circuit;
+use homaya_sim::Simulator;
+
+/// Swap test builder.
+///
+/// Qubit 0 is the ancilla, register A follows it, then register B.
+#[derive(Debug, Clone)]
+pub struct SwapTest {
+    /// Preparation of |a⟩ on its own qubits 0..m
+    prep_a: Circuit,
+    /// Preparation of |b⟩ on its own qubits 0..m
+    prep_b: Circuit,
+}
+
+impl SwapTest {
+    /// Create a new swap test comparing the states two circuits prepare.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the circuits act on different numbers of qubits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::SwapTest;
+    /// use homaya_core::Circuit;
+    ///
+    /// // |0⟩ vs |+⟩: |⟨0|+⟩|² = 1/2
+    /// let test = SwapTest::new(Circuit::new(1), Circuit::new(1).h(0));
+    /// assert!((test.overlap() - 0.5).abs() < 1e-10);
+    /// ```
+    pub fn new(prep_a: Circuit, prep_b: Circuit) -> Self {
+        assert_eq!(
+            prep_a.num_qubits(),
+            prep_b.num_qubits(),
+            "Both states must have the same number of qubits"
+        );
+        Self { prep_a, prep_b }
+    }
+
+    /// Total number of qubits: the ancilla plus both registers.
+    pub fn total_qubits(&self) -> usize {
+        1 + 2 * self.prep_a.num_qubits()
+    }
+
+    /// Build the swap test circuit, measuring the ancilla into classical bit 0.
+    pub fn build(&self) -> Circuit {
+        self.build_unmeasured().measure(0, 0)
+    }
+
+    /// Simulate the circuit and return the exact overlap |⟨a|b⟩|².
+    pub fn overlap(&self) -> f64 {
+        let state = Simulator::new()
+            .run(&self.build_unmeasured())
+            .expect("swap test circuits only use simulable gates");
+
+        let p0: f64 = state
+            .probabilities()
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| index & 1 == 0)
+            .map(|(_, p)| p)
+            .sum();
+
+        Self::overlap_from_probability(p0)
+    }
+
+    /// Convert P(ancilla = 0) into the overlap |⟨a|b⟩|² = 2·P(0) - 1.
+    ///
+    /// Estimates below 1/2 from finite shots are clamped to an overlap of 0.
+    pub fn overlap_from_probability(p0: f64) -> f64 {
+        (2.0 * p0 - 1.0).clamp(0.0, 1.0)
+    }
+
+    /// Build the circuit up to (but excluding) measurement.
+    fn build_unmeasured(&self) -> Circuit {
+        let m = self.prep_a.num_qubits();
+
+        // Step 1: Ancilla, then |a⟩, then |b⟩
+        let mut circuit = Circuit::new(1).tensor(&self.prep_a).tensor(&self.prep_b);
+
+        // Step 2: Superpose the ancilla
+        circuit = circuit.h(0);
+
+        // Step 3: Controlled swap of the registers
+        for i in 1..=m {
+            circuit = circuit.cswap(0, i, i + m);
+        }
+
+        // Step 4: Interfere
+        circuit.h(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_width() {
+        let test = SwapTest::new(Circuit::new(3), Circuit::new(3));
+        let circuit = test.build();
+        assert_eq!(circuit.num_qubits(), 7);
+        assert_eq!(circuit.num_clbits(), 1);
+    }
+
+    #[test]
+    fn test_identical_states_overlap_one() {
+        let prep = Circuit::new(2).h(0).cx(0, 1).ry(0.3, 1);
+        let test = SwapTest::new(prep.clone(), prep);
+        assert!((test.overlap() - 1.0).abs() < 1e-10);
+
+        // The ancilla never reads 1
+        let counts = Simulator::with_seed(3).sample(&test.build(), 100).unwrap();
+        assert_eq!(counts.get("1"), None);
+    }
+
+    #[test]
+    fn test_orthogonal_states_overlap_zero() {
+        // |Φ+⟩ vs |Ψ+⟩
+        let phi = Circuit::new(2).h(0).cx(0, 1);
+        let psi = Circuit::new(2).h(0).cx(0, 1).x(1);
+        assert!(SwapTest::new(phi, psi).overlap().abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_overlap_from_probability() {
+        assert_eq!(SwapTest::overlap_from_probability(1.0), 1.0);
+        assert_eq!(SwapTest::overlap_from_probability(0.75), 0.5);
+        assert_eq!(SwapTest::overlap_from_probability(0.45), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "same number of qubits")]
+    fn test_mismatched_widths() {
+        SwapTest::new(Circuit::new(1), Circuit::new(2));
+    }
+}
@@ -0,0 +1,93 @@
+//! # Quantum Teleportation
+//!
+//! Move an unknown qubit state using one Bell pair and two classical bits.
+//!
+//! ## The Protocol
+//!
+//! Alice holds |ψ⟩ on qubit 0 and shares a Bell pair with Bob on qubits 1
+//! and 2.
+//!
+//! 1. Alice runs CX(0, 1) and H(0), turning a Bell measurement into an
+//!    ordinary one
+//! 2. Alice measures qubits 0 and 1 and sends Bob the two bits
+//! 3. Bob applies X if bit 1 is set and Z if bit 0 is set
+//! 4. Qubit 2 is now in |ψ⟩; qubit 0 no longer is (no cloning!)
+//!
+//! Bob's corrections are classically controlled with [`Circuit::c_if`], so
+//! the circuit really does branch on Alice's measurement.
+
+use homaya_core::states::{bell, BellState};
+use homaya_core::{Circuit, ConditionalGate};
+
+/// Build a teleportation circuit for the state `state_prep` makes from |0⟩.
+///
+/// Qubit 0 holds the input, qubits 1 and 2 the Bell pair. Qubits 0 and 1
+/// are measured into classical bits 0 and 1, which condition the X and Z
+/// corrections on qubit 2; it ends in the prepared state whatever they
+/// read.
+///
+/// # Panics
+///
+/// Panics if `state_prep` does not act on exactly one qubit.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_algorithms::teleport::teleportation_circuit;
+/// use homaya_core::Circuit;
+///
+/// let circuit = teleportation_circuit(&Circuit::new(1).h(0));
+/// assert_eq!(circuit.num_qubits(), 3);
+/// assert_eq!(circuit.num_clbits(), 2);
+/// ```
+pub fn teleportation_circuit(state_prep: &Circuit) -> Circuit {
+    assert_eq!(state_prep.num_qubits(), 1, "teleportation moves a single-qubit state");
+
+    Circuit::new(3)
+        // Step 1: The state to send, and the shared Bell pair
+        .append_on(state_prep, &[0])
+        .append_on(&bell(BellState::PhiPlus), &[1, 2])
+        // Step 2: Rotate the Bell basis onto the computational basis
+        .cx(0, 1)
+        .h(0)
+        // Step 3: Alice's measurement
+        .measure(0, 0)
+        .measure(1, 1)
+        // Step 4: Bob's corrections, fed forward from her bits
+        .c_if(1, ConditionalGate::x(2))
+        .c_if(0, ConditionalGate::z(2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_core::PI;
+    use homaya_sim::Simulator;
+
+    #[test]
+    fn test_teleports_ry_state() {
+        let prep = Circuit::new(1).ry(PI / 3.0, 0).p(0.4, 0);
+        let original = Simulator::new().run(&prep).unwrap();
+        let circuit = teleportation_circuit(&prep);
+
+        // Every measurement branch leaves qubit 2 in the original state
+        let mut sim = Simulator::with_seed(7);
+        let mut branches = std::collections::HashSet::new();
+        for _ in 0..32 {
+            let state = sim.run(&circuit).unwrap();
+            let alice = (0..4).find(|&bits| state.probability(bits) + state.probability(bits | 4) > 0.5).unwrap();
+            branches.insert(alice);
+
+            let bob = [state.amplitude(alice), state.amplitude(alice | 4)];
+            let overlap = original.amplitude(0).conj() * bob[0] + original.amplitude(1).conj() * bob[1];
+            assert!((overlap.norm_sqr() - 1.0).abs() < 1e-10, "branch {:02b}", alice);
+        }
+        assert_eq!(branches.len(), 4, "{:?}", branches);
+    }
+
+    #[test]
+    #[should_panic(expected = "single-qubit state")]
+    fn test_rejects_wide_prep() {
+        teleportation_circuit(&Circuit::new(2));
+    }
+}
@@ -0,0 +1,178 @@
+//! Automatic backend selection.
+//!
+//! Backends are picked by circuit width:
+//!
+//! - Below [`SPARSE_MIN_QUBITS`] the dense state-vector simulator, which is
+//!   fastest while all 2^n amplitudes fit comfortably in memory.
+//! - From there up to [`SparseState::MAX_QUBITS`] the sparse simulator. It
+//!   stores only the populated basis states, so GHZ preparation, classical
+//!   oracles and Bernstein-Vazirani with each query qubit's H, CNOT, H run
+//!   in a few entries however wide they are. A circuit that fills the state
+//!   switches to dense after [`AUTO_DENSE_FRACTION`] of 2^n, so the sparse
+//!   phase costs a small fraction of the dense memory.
+//!
+//! Past [`StateVector::MAX_QUBITS`] there is no dense fallback; a circuit
+//! that fills the state there fails when it outgrows memory.
+
+use std::collections::HashMap;
+
+use homaya_core::{Circuit, HomayaError, Result};
+use homaya_sim::{SparseState, StateVector};
+
+use crate::{Backend, DenseBackend, SparseBackend};
+
+/// Width from which [`select_backend`] picks the sparse backend.
+///
+/// Below it a dense state is at most 1 MiB.
+pub const SPARSE_MIN_QUBITS: usize = 16;
+
+/// Occupancy, as a fraction of 2^n, at which an automatic sparse run
+/// switches to dense.
+///
+/// Lower than [`SparseSimulator::DEFAULT_DENSE_FRACTION`](homaya_sim::SparseSimulator::DEFAULT_DENSE_FRACTION):
+/// a run that was only sent to the sparse backend for its width gives up
+/// early rather than building a hash map as large as the dense state.
+pub const AUTO_DENSE_FRACTION: f64 = 1.0 / 64.0;
+
+/// The execution backends [`select_backend`] can choose from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BackendKind {
+    /// Dense state-vector simulation (all 2^n amplitudes in memory)
+    Dense,
+    /// Sparse simulation of the non-zero amplitudes, turning dense on demand
+    Sparse,
+}
+
+impl BackendKind {
+    /// Largest circuit, in qubits, this backend can run.
+    pub const fn max_qubits(self) -> usize {
+        match self {
+            Self::Dense => StateVector::MAX_QUBITS,
+            Self::Sparse => SparseState::MAX_QUBITS,
+        }
+    }
+}
+
+/// Pick the backend for a circuit.
+///
+/// # Errors
+///
+/// Returns [`HomayaError::BackendError`] if no backend can hold the circuit.
+pub fn select_backend(circuit: &Circuit) -> Result<BackendKind> {
+    let n = circuit.num_qubits();
+    if n < SPARSE_MIN_QUBITS {
+        return Ok(BackendKind::Dense);
+    }
+    if n <= BackendKind::Sparse.max_qubits() {
+        return Ok(BackendKind::Sparse);
+    }
+
+    Err(HomayaError::BackendError {
+        backend: "auto",
+        message: format!(
+            "circuit has {} qubits but the largest backend (sparse, {} qubits) cannot hold it; \
+             split the circuit or reduce its width",
+            n,
+            BackendKind::Sparse.max_qubits()
+        ),
+    })
+}
+
+/// Sample a circuit on whichever backend fits it.
+///
+/// Counts are keyed by canonical bitstrings (classical bit 0 rightmost).
+pub fn run_auto(circuit: &Circuit, shots: usize, seed: u64) -> Result<HashMap<String, usize>> {
+    match select_backend(circuit)? {
+        BackendKind::Dense => DenseBackend::with_seed(seed).execute(circuit, shots),
+        BackendKind::Sparse => SparseBackend::with_seed(seed)
+            .with_dense_fraction(AUTO_DENSE_FRACTION)
+            .execute(circuit, shots),
+    }
+}
+
+/// Run a circuit without choosing a backend by hand.
+pub trait RunAuto {
+    /// Sample `shots` times on the backend [`select_backend`] picks.
+    fn run_auto(&self, shots: usize, seed: u64) -> Result<HashMap<String, usize>>;
+}
+
+impl RunAuto for Circuit {
+    fn run_auto(&self, shots: usize, seed: u64) -> Result<HashMap<String, usize>> {
+        run_auto(self, shots, seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_algorithms::{bernstein_vazirani, grover};
+    use homaya_core::format_outcome;
+
+    /// Bernstein-Vazirani with each query qubit's H, CNOT, H in turn.
+    ///
+    /// Equivalent to [`bernstein_vazirani::find_secret`], but every query
+    /// qubit is back on a basis state before the next one starts, so the
+    /// state never holds more than four amplitudes.
+    fn sequential_bernstein_vazirani(n: usize, secret: usize) -> Circuit {
+        let ancilla = n;
+        let mut circuit = Circuit::new(n + 1).x(ancilla).h(ancilla);
+        for q in 0..n {
+            circuit = circuit.h(q);
+            if (secret >> q) & 1 == 1 {
+                circuit = circuit.cx(q, ancilla);
+            }
+            circuit = circuit.h(q);
+        }
+        circuit.measure_range(0..n)
+    }
+
+    #[test]
+    fn test_grover_runs_auto() {
+        let circuit = grover::search(4, 9);
+        assert_eq!(select_backend(&circuit).unwrap(), BackendKind::Dense);
+
+        let counts = circuit.run_auto(20, 1).unwrap();
+        assert_eq!(counts.values().sum::<usize>(), 20);
+    }
+
+    #[test]
+    fn test_sparse_bernstein_vazirani_runs_auto() {
+        let secret = 0b1010_0110_0001_1101;
+        let circuit = sequential_bernstein_vazirani(16, secret);
+        assert_eq!(select_backend(&circuit).unwrap(), BackendKind::Sparse);
+
+        let counts = circuit.run_auto(5, 1).unwrap();
+        assert_eq!(counts.get(&format_outcome(secret, 16)), Some(&5));
+    }
+
+    #[test]
+    fn test_wide_bernstein_vazirani_runs_auto() {
+        // Fills the state, so the sparse run switches to dense
+        let secret = 0b1010_0110_0001_1101;
+        let circuit = bernstein_vazirani::find_secret(16, secret);
+        assert_eq!(select_backend(&circuit).unwrap(), BackendKind::Sparse);
+
+        let counts = circuit.run_auto(5, 1).unwrap();
+        assert_eq!(counts.get(&format_outcome(secret, 16)), Some(&5));
+    }
+
+    #[test]
+    fn test_bernstein_vazirani_beyond_dense_runs_auto() {
+        // 41 qubits: far past what a dense state vector can hold
+        let secret = 0xAB_CDEF_0123;
+        let circuit = sequential_bernstein_vazirani(40, secret);
+        assert!(circuit.num_qubits() > BackendKind::Dense.max_qubits());
+        assert_eq!(select_backend(&circuit).unwrap(), BackendKind::Sparse);
+
+        let counts = circuit.run_auto(3, 1).unwrap();
+        assert_eq!(counts.get(&format_outcome(secret, 40)), Some(&3));
+    }
+
+    #[test]
+    fn test_too_wide_circuit_errors() {
+        let circuit = Circuit::new(SparseState::MAX_QUBITS + 1);
+        let err = circuit.run_auto(1, 1).unwrap_err();
+        assert!(err.to_string().contains("64 qubits"), "{}", err);
+    }
+}
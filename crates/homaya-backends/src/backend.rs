@@ -0,0 +1,185 @@
+//! The backend interface.
+//!
+//! A [`Backend`] executes circuits and reports measurement histograms keyed
+//! by canonical bitstrings (classical bit 0 rightmost).
+
+use std::collections::HashMap;
+
+use homaya_core::{Circuit, Result};
+use homaya_sim::{Simulator, SparseSimulator};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Something that can execute circuits.
+pub trait Backend: Sync {
+    /// Short backend name for diagnostics.
+    fn name(&self) -> &'static str;
+
+    /// Execute one circuit `shots` times and return its histogram.
+    ///
+    /// `index` is the circuit's position within a batch (0 when run alone),
+    /// so seeded backends can give each batch entry its own reproducible
+    /// random stream.
+    fn execute_indexed(&self, circuit: &Circuit, shots: usize, index: usize) -> Result<HashMap<String, usize>>;
+
+    /// Execute one circuit `shots` times and return its histogram.
+    fn execute(&self, circuit: &Circuit, shots: usize) -> Result<HashMap<String, usize>> {
+        self.execute_indexed(circuit, shots, 0)
+    }
+
+    /// Execute a slice of circuits, one histogram per circuit in order.
+    ///
+    /// Each entry is the whole `shots`-sample histogram, keyed like
+    /// [`Simulator::sample`], rather than a [`MeasurementResult`], which
+    /// holds the classical bits of a single shot. The histogram is what a
+    /// sweep reads its estimate from.
+    ///
+    /// With the `parallel` feature the circuits run concurrently; results
+    /// are identical either way.
+    ///
+    /// [`MeasurementResult`]: homaya_sim::MeasurementResult
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error any circuit hits.
+    fn execute_batch(&self, circuits: &[Circuit], shots: usize) -> Result<Vec<HashMap<String, usize>>> {
+        #[cfg(feature = "parallel")]
+        let iter = circuits.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let iter = circuits.iter();
+
+        iter.enumerate()
+            .map(|(index, circuit)| self.execute_indexed(circuit, shots, index))
+            .collect()
+    }
+}
+
+/// Dense state-vector simulation on the local machine.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DenseBackend {
+    /// Base seed; batch entry i uses `seed + i`
+    seed: Option<u64>,
+}
+
+impl DenseBackend {
+    /// Create an unseeded dense backend.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a dense backend with reproducible sampling.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed: Some(seed) }
+    }
+}
+
+impl Backend for DenseBackend {
+    fn name(&self) -> &'static str {
+        "dense"
+    }
+
+    fn execute_indexed(&self, circuit: &Circuit, shots: usize, index: usize) -> Result<HashMap<String, usize>> {
+        let mut sim = match self.seed {
+            Some(seed) => Simulator::with_seed(seed.wrapping_add(index as u64)),
+            None => Simulator::new(),
+        };
+        sim.sample(circuit, shots)
+    }
+}
+
+/// Sparse state simulation, switching to dense once the state fills up.
+///
+/// Runs registers up to [`SparseState::MAX_QUBITS`](homaya_sim::SparseState::MAX_QUBITS)
+/// wide as long as only a few basis states are populated.
+#[derive(Clone, Copy, Debug)]
+pub struct SparseBackend {
+    /// Base seed; batch entry i uses `seed + i`
+    seed: Option<u64>,
+    /// Occupancy, as a fraction of 2^n, above which a run turns dense
+    dense_fraction: f64,
+}
+
+impl Default for SparseBackend {
+    fn default() -> Self {
+        Self {
+            seed: None,
+            dense_fraction: SparseSimulator::DEFAULT_DENSE_FRACTION,
+        }
+    }
+}
+
+impl SparseBackend {
+    /// Create an unseeded sparse backend.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a sparse backend with reproducible sampling.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            seed: Some(seed),
+            ..Self::default()
+        }
+    }
+
+    /// Set the occupancy fraction of 2^n above which a run turns dense.
+    ///
+    /// See [`SparseSimulator::with_dense_fraction`].
+    pub fn with_dense_fraction(mut self, fraction: f64) -> Self {
+        self.dense_fraction = fraction;
+        self
+    }
+}
+
+impl Backend for SparseBackend {
+    fn name(&self) -> &'static str {
+        "sparse"
+    }
+
+    fn execute_indexed(&self, circuit: &Circuit, shots: usize, index: usize) -> Result<HashMap<String, usize>> {
+        let sim = match self.seed {
+            Some(seed) => SparseSimulator::with_seed(seed.wrapping_add(index as u64)),
+            None => SparseSimulator::new(),
+        };
+        sim.with_dense_fraction(self.dense_fraction).sample(circuit, shots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_algorithms::GroverSearch;
+
+    #[test]
+    fn test_batch_of_grover_searches() {
+        let circuits: Vec<Circuit> = (0..16).map(|target| GroverSearch::new(4, target).build()).collect();
+
+        let results = DenseBackend::with_seed(42).execute_batch(&circuits, 100).unwrap();
+
+        assert_eq!(results.len(), 16);
+        for (target, counts) in results.iter().enumerate() {
+            assert_eq!(GroverSearch::found_counts(counts), Some(target));
+        }
+    }
+
+    #[test]
+    fn test_batch_matches_individual_runs() {
+        let backend = DenseBackend::with_seed(7);
+        let circuits = vec![Circuit::new(1).h(0).measure_all(); 3];
+
+        let batch = backend.execute_batch(&circuits, 50).unwrap();
+        for (index, (circuit, counts)) in circuits.iter().zip(&batch).enumerate() {
+            assert_eq!(&backend.execute_indexed(circuit, 50, index).unwrap(), counts);
+        }
+    }
+
+    #[test]
+    fn test_sparse_backend_matches_dense() {
+        let circuits: Vec<Circuit> = (0..4).map(|target| GroverSearch::new(3, target).build()).collect();
+
+        let dense = DenseBackend::with_seed(3).execute_batch(&circuits, 200).unwrap();
+        let sparse = SparseBackend::with_seed(3).execute_batch(&circuits, 200).unwrap();
+        assert_eq!(dense, sparse);
+    }
+}
@@ -1 +1,29 @@
-// homaya-backends placeholder
+//! HOMAYA Backends - Pick where a circuit runs.
+//!
+//! This crate chooses an execution backend for a circuit so callers don't
+//! have to:
+//!
+//! - [`Backend`] - Execute circuits, one at a time or in batches
+//! - [`select_backend`] - Pick a backend from the circuit's width
+//! - [`RunAuto`] - `circuit.run_auto(shots, seed)` on whichever backend fits
+//!
+//! # Example
+//!
+//! ```rust
+//! use homaya_backends::RunAuto;
+//! use homaya_core::Circuit;
+//!
+//! let circuit = Circuit::new(2).h(0).cx(0, 1).measure_all();
+//! let counts = circuit.run_auto(100, 42).unwrap();
+//!
+//! assert_eq!(counts.values().sum::<usize>(), 100);
+//! ```
+
+#![deny(missing_docs)]
+#![deny(unsafe_code)]
+
+mod auto;
+mod backend;
+
+pub use auto::{run_auto, select_backend, BackendKind, RunAuto};
+pub use backend::{Backend, DenseBackend, SparseBackend};
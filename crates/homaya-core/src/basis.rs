@@ -0,0 +1,171 @@
+//! Native gate sets and gate-count estimation.
+//!
+//! Real hardware only runs a handful of native gates. A [`BasisSet`] lists
+//! them and estimates how many native gates each [`GateType`] expands into
+//! once decomposed, so a circuit's cost can be checked before transpiling.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::GateType;
+
+/// A set of native gates a circuit must be expressed in.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_core::{BasisSet, GateType};
+///
+/// let basis = BasisSet::clifford_t();
+/// assert_eq!(basis.gate_cost(GateType::H), Some(1));
+/// assert_eq!(basis.gate_cost(GateType::CCX), Some(15)); // 6 CX + 7 T/T† + 2 H
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BasisSet {
+    /// Natively supported gates
+    native: BTreeSet<GateType>,
+    /// Native gate cost for every gate type expressible in this basis
+    costs: BTreeMap<GateType, usize>,
+}
+
+impl BasisSet {
+    /// Create a basis from a list of native gates.
+    ///
+    /// Measurement, reset and barriers are always available on top of these.
+    pub fn new(native: &[GateType]) -> Self {
+        use GateType::*;
+
+        let native: BTreeSet<GateType> = native.iter().copied().collect();
+        let mut costs = BTreeMap::new();
+        for &gate in &native {
+            costs.insert(gate, 1);
+        }
+        costs.insert(Measure, 1);
+        costs.insert(Reset, 1);
+        costs.insert(Barrier, 0);
+        costs.entry(I).or_insert(0);
+
+        // Relax decompositions until no cost improves (the table has cycles,
+        // e.g. CX ↔ CZ, so a single pass is not enough)
+        loop {
+            let mut changed = false;
+            for &(gate, parts) in DECOMPOSITIONS {
+                let cost: Option<usize> = parts.iter().map(|part| costs.get(part).copied()).sum();
+                if let Some(cost) = cost {
+                    if costs.get(&gate).is_none_or(|&current| cost < current) {
+                        costs.insert(gate, cost);
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        Self { native, costs }
+    }
+
+    /// The Clifford+T basis: H, S, S†, T, T†, Paulis and CX.
+    pub fn clifford_t() -> Self {
+        use GateType::*;
+        Self::new(&[H, S, Sdg, T, Tdg, X, Y, Z, CX])
+    }
+
+    /// A rotation basis: Rx, Ry, Rz and CX.
+    pub fn rotations_cx() -> Self {
+        use GateType::*;
+        Self::new(&[Rx, Ry, Rz, CX])
+    }
+
+    /// Check whether a gate is natively supported.
+    #[inline]
+    pub fn contains(&self, gate_type: GateType) -> bool {
+        self.native.contains(&gate_type)
+    }
+
+    /// Estimated number of native gates needed for one `gate_type`.
+    ///
+    /// Returns `None` if the gate cannot be expressed exactly in this basis
+    /// (for example arbitrary rotations in Clifford+T).
+    #[inline]
+    pub fn gate_cost(&self, gate_type: GateType) -> Option<usize> {
+        self.costs.get(&gate_type).copied()
+    }
+}
+
+/// Standard decompositions, in time order, each exact up to global phase.
+///
+/// A gate may appear several times; the cheapest expressible one wins.
+const DECOMPOSITIONS: &[(GateType, &[GateType])] = {
+    use GateType::*;
+    &[
+        // Single-qubit
+        (X, &[H, Z, H]),
+        (X, &[Rx]),
+        (Y, &[S, X, Sdg]),
+        (Y, &[Ry]),
+        (Z, &[S, S]),
+        (Z, &[P]),
+        (S, &[T, T]),
+        (S, &[P]),
+        (Sdg, &[Tdg, Tdg]),
+        (Sdg, &[P]),
+        (T, &[P]),
+        (Tdg, &[P]),
+        (P, &[Rz]),
+        (Rz, &[P]),
+        (H, &[Ry, X]),
+        (Rx, &[H, Rz, H]),
+        (Ry, &[Sdg, Rx, S]),
+        (U, &[Rz, Ry, Rz]),
+        // Two-qubit
+        (CX, &[H, CZ, H]),
+        (CZ, &[H, CX, H]),
+        (CY, &[Sdg, CX, S]),
+        (CH, &[Ry, CX, Ry]),
+        (CP, &[P, CX, P, CX, P]),
+        (CU, &[P, Rz, CX, U, CX, U]),
+        (Swap, &[CX, CX, CX]),
+        (ISwap, &[S, S, H, CX, CX, H]),
+        // Three-qubit
+        (CCX, &[H, CX, Tdg, CX, T, CX, Tdg, CX, T, T, H, CX, T, Tdg, CX]),
+        (CSwap, &[CX, CCX, CX]),
+    ]
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_native_gates_cost_one() {
+        let basis = BasisSet::clifford_t();
+        assert!(basis.contains(GateType::CX));
+        assert!(!basis.contains(GateType::CZ));
+        assert_eq!(basis.gate_cost(GateType::T), Some(1));
+        assert_eq!(basis.gate_cost(GateType::Measure), Some(1));
+    }
+
+    #[test]
+    fn test_decomposed_costs() {
+        let basis = BasisSet::clifford_t();
+        assert_eq!(basis.gate_cost(GateType::CZ), Some(3));
+        assert_eq!(basis.gate_cost(GateType::Swap), Some(3));
+        assert_eq!(basis.gate_cost(GateType::CCX), Some(15));
+        assert_eq!(basis.gate_cost(GateType::CSwap), Some(17));
+    }
+
+    #[test]
+    fn test_inexpressible_gate() {
+        // Arbitrary rotations are not exact in Clifford+T
+        let basis = BasisSet::clifford_t();
+        assert_eq!(basis.gate_cost(GateType::Rx), None);
+
+        // ...but are native in a rotation basis
+        let basis = BasisSet::rotations_cx();
+        assert_eq!(basis.gate_cost(GateType::Rx), Some(1));
+        assert_eq!(basis.gate_cost(GateType::Z), Some(1));
+        assert_eq!(basis.gate_cost(GateType::H), Some(2));
+        assert!(!basis.contains(GateType::P));
+    }
+}
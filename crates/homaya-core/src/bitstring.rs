@@ -0,0 +1,152 @@
+//! Canonical bitstring format for measurement outcomes.
+//!
+//! Every bitstring HOMAYA prints or uses as a key follows one rule:
+//! bit 0 is the **rightmost** character, and the string is zero-padded to
+//! the number of measured bits. This matches how the integer value of the
+//! outcome is written in binary, so `"110"` is the outcome 6.
+//!
+//! That is [`BitOrder::LittleEndian`], the same convention as Qiskit.
+//! Textbooks usually write kets the other way round, |q₀q₁…⟩ with qubit 0
+//! leftmost; [`BitOrder::BigEndian`] formats and parses that form for
+//! callers who want it. The order only changes how bits are printed: which
+//! qubit is measured into which classical bit is up to the circuit.
+
+use std::collections::HashMap;
+
+/// Format a measurement outcome as a canonical bitstring.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_core::format_outcome;
+///
+/// // Bits 0 and 2 set, 4 bits measured
+/// assert_eq!(format_outcome(0b0101, 4), "0101");
+/// ```
+pub fn format_outcome(value: usize, width: usize) -> String {
+    format!("{:0width$b}", value, width = width)
+}
+
+/// Parse a canonical bitstring back into its outcome value.
+///
+/// Returns `None` if the string contains anything but `0` and `1`, or
+/// doesn't fit in a `usize`.
+pub fn parse_outcome(bits: &str) -> Option<usize> {
+    if bits.is_empty() || !bits.bytes().all(|b| b == b'0' || b == b'1') {
+        return None;
+    }
+    usize::from_str_radix(bits, 2).ok()
+}
+
+/// Which end of a bitstring holds bit 0.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_core::BitOrder;
+///
+/// assert_eq!(BitOrder::LittleEndian.format(0b001, 3), "001");
+/// assert_eq!(BitOrder::BigEndian.format(0b001, 3), "100");
+/// assert_eq!(BitOrder::BigEndian.parse("100"), Some(1));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BitOrder {
+    /// Bit 0 rightmost: the canonical format, as in Qiskit. `"110"` is 6.
+    #[default]
+    LittleEndian,
+    /// Bit 0 leftmost, as in textbook kets |q₀q₁…⟩. `"110"` is 3.
+    BigEndian,
+}
+
+impl BitOrder {
+    /// Format `value` as a `width`-bit string in this order.
+    pub fn format(self, value: usize, width: usize) -> String {
+        let canonical = format_outcome(value, width);
+        match self {
+            Self::LittleEndian => canonical,
+            Self::BigEndian => canonical.chars().rev().collect(),
+        }
+    }
+
+    /// Parse a bitstring written in this order.
+    ///
+    /// Returns `None` under the same conditions as [`parse_outcome`].
+    pub fn parse(self, bits: &str) -> Option<usize> {
+        match self {
+            Self::LittleEndian => parse_outcome(bits),
+            Self::BigEndian => parse_outcome(&bits.chars().rev().collect::<String>()),
+        }
+    }
+}
+
+/// The most frequent outcome in a histogram of canonical bitstrings.
+///
+/// Ties go to the smaller outcome so the answer doesn't depend on hash
+/// order. Keys that aren't valid bitstrings are ignored; returns `None` if
+/// none are left.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use homaya_core::most_likely_outcome;
+///
+/// let counts = HashMap::from([("101".to_string(), 90), ("001".to_string(), 10)]);
+/// assert_eq!(most_likely_outcome(&counts), Some(0b101));
+/// ```
+pub fn most_likely_outcome(counts: &HashMap<String, usize>) -> Option<usize> {
+    counts
+        .iter()
+        .filter_map(|(bits, &count)| parse_outcome(bits).map(|value| (value, count)))
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(value, _)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_zero_is_rightmost() {
+        assert_eq!(format_outcome(1, 3), "001");
+        assert_eq!(format_outcome(0b110, 3), "110");
+    }
+
+    #[test]
+    fn test_zero_padded_to_width() {
+        assert_eq!(format_outcome(0, 4), "0000");
+        assert_eq!(format_outcome(5, 8), "00000101");
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        for value in 0..16 {
+            assert_eq!(parse_outcome(&format_outcome(value, 4)), Some(value));
+        }
+        assert_eq!(parse_outcome("10a"), None);
+        assert_eq!(parse_outcome(""), None);
+    }
+
+    #[test]
+    fn test_bit_orders_mirror_each_other() {
+        assert_eq!(BitOrder::default(), BitOrder::LittleEndian);
+        for value in 0..16 {
+            let little = BitOrder::LittleEndian.format(value, 4);
+            let big = BitOrder::BigEndian.format(value, 4);
+            assert_eq!(little.chars().rev().collect::<String>(), big);
+            assert_eq!(BitOrder::BigEndian.parse(&big), Some(value));
+        }
+        assert_eq!(BitOrder::BigEndian.parse("1x"), None);
+    }
+
+    #[test]
+    fn test_most_likely_breaks_ties_low() {
+        let counts = HashMap::from([
+            ("11".to_string(), 5),
+            ("01".to_string(), 5),
+            ("junk".to_string(), 9),
+        ]);
+        assert_eq!(most_likely_outcome(&counts), Some(1));
+        assert_eq!(most_likely_outcome(&HashMap::new()), None);
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! Fluent API for constructing quantum circuits.
 
-use crate::{Gate, GateType, HomayaError, Result};
+use crate::{BasisSet, ControlFlowGraph, Gate, GateParams, GateType, HomayaError, QubitRegister, Result, PI};
 
 /// A quantum instruction: gate + target qubits.
 #[derive(Clone, Debug, PartialEq)]
@@ -14,6 +14,9 @@ pub struct Instruction {
     pub qubits: Vec<usize>,
     /// Classical bit indices (for measurement)
     pub clbits: Vec<usize>,
+    /// Classical bit that must read 1 for this instruction to apply
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub condition: Option<usize>,
 }
 
 impl Instruction {
@@ -24,16 +27,79 @@ impl Instruction {
             gate,
             qubits,
             clbits: Vec::new(),
+            condition: None,
         }
     }
 
     /// Create an instruction with classical bits.
     #[inline]
     pub fn with_clbits(gate: Gate, qubits: Vec<usize>, clbits: Vec<usize>) -> Self {
-        Self { gate, qubits, clbits }
+        Self {
+            gate,
+            qubits,
+            clbits,
+            condition: None,
+        }
+    }
+
+    /// Make this instruction conditional on a classical bit reading 1.
+    #[inline]
+    pub fn conditioned_on(mut self, clbit: usize) -> Self {
+        self.condition = Some(clbit);
+        self
+    }
+
+    /// Check whether this instruction is classically conditioned.
+    #[inline]
+    pub const fn is_conditional(&self) -> bool {
+        self.condition.is_some()
     }
 }
 
+/// A gate for [`Circuit::c_if`] to apply only when a classical bit reads 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConditionalGate {
+    /// The gate to apply
+    pub gate: Gate,
+    /// Target qubit indices
+    pub qubits: Vec<usize>,
+}
+
+impl ConditionalGate {
+    /// Wrap any gate and its qubits.
+    #[inline]
+    pub fn new(gate: Gate, qubits: &[usize]) -> Self {
+        Self {
+            gate,
+            qubits: qubits.to_vec(),
+        }
+    }
+
+    /// Pauli-X on `q`, the usual bit-flip correction.
+    #[inline]
+    pub fn x(q: usize) -> Self {
+        Self::new(Gate::x(), &[q])
+    }
+
+    /// Pauli-Z on `q`, the usual phase-flip correction.
+    #[inline]
+    pub fn z(q: usize) -> Self {
+        Self::new(Gate::z(), &[q])
+    }
+}
+
+/// A labeled span of instructions, e.g. one oracle call.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Block {
+    /// Block label
+    label: std::string::String,
+    /// First instruction index
+    start: usize,
+    /// One past the last instruction index
+    end: usize,
+}
+
 /// A quantum circuit.
 ///
 /// # Example
@@ -60,6 +126,12 @@ pub struct Circuit {
     instructions: Vec<Instruction>,
     /// Optional name
     name: Option<std::string::String>,
+    /// Labeled instruction spans
+    #[cfg_attr(feature = "serde", serde(default))]
+    blocks: Vec<Block>,
+    /// Named qubit registers, in qubit order
+    #[cfg_attr(feature = "serde", serde(default))]
+    registers: Vec<QubitRegister>,
 }
 
 impl Circuit {
@@ -71,6 +143,8 @@ impl Circuit {
             num_clbits: 0,
             instructions: Vec::new(),
             name: None,
+            blocks: Vec::new(),
+            registers: Vec::new(),
         }
     }
 
@@ -82,6 +156,8 @@ impl Circuit {
             num_clbits,
             instructions: Vec::new(),
             name: None,
+            blocks: Vec::new(),
+            registers: Vec::new(),
         }
     }
 
@@ -92,6 +168,65 @@ impl Circuit {
         self
     }
 
+    /// Get the circuit name, if set.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Add `size` fresh qubits as a register called `name`.
+    ///
+    /// The qubits go after all existing ones; the returned handle maps
+    /// `reg[i]` to their global indices. [`to_qasm`](Self::to_qasm) writes
+    /// one `qreg` per register when the registers cover every qubit in
+    /// order, and a single `q` otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0, if `name` is not an OpenQASM identifier (a
+    /// lowercase letter, then letters, digits or `_`) or if a register of
+    /// that name already exists.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let mut circuit = Circuit::new(0);
+    /// let counting = circuit.add_register("counting", 2);
+    /// let work = circuit.add_register("work", 1);
+    /// let circuit = circuit.h(counting[0]).cp(0.5, counting[0], work[0]);
+    ///
+    /// assert_eq!(circuit.num_qubits(), 3);
+    /// assert!(circuit.to_qasm().contains("qreg counting[2];\nqreg work[1];\n"));
+    /// ```
+    pub fn add_register(&mut self, name: impl Into<std::string::String>, size: usize) -> QubitRegister {
+        let name = name.into();
+        assert!(size > 0, "register `{}` has no qubits", name);
+        assert!(
+            crate::register::is_identifier(&name),
+            "register name `{}` is not an OpenQASM identifier",
+            name
+        );
+        assert!(self.register(&name).is_none(), "register `{}` already exists", name);
+
+        let register = QubitRegister::new(name, self.num_qubits, size);
+        self.num_qubits += size;
+        self.registers.push(register.clone());
+        register
+    }
+
+    /// Get the named registers, in the order they were added.
+    #[inline]
+    pub fn registers(&self) -> &[QubitRegister] {
+        &self.registers
+    }
+
+    /// Look up a register by name.
+    pub fn register(&self, name: &str) -> Option<&QubitRegister> {
+        self.registers.iter().find(|reg| reg.name() == name)
+    }
+
     /// Get the number of qubits.
     #[inline]
     pub const fn num_qubits(&self) -> usize {
@@ -99,6 +234,10 @@ impl Circuit {
     }
 
     /// Get the number of classical bits.
+    ///
+    /// At least one past the highest bit any [`measure`](Self::measure) or
+    /// [`c_if`](Self::c_if) has used, and never less than requested in
+    /// [`with_clbits`](Self::with_clbits).
     #[inline]
     pub const fn num_clbits(&self) -> usize {
         self.num_clbits
@@ -110,6 +249,23 @@ impl Circuit {
         &self.instructions
     }
 
+    /// Iterate over the gates in program order, without their operands.
+    ///
+    /// Use [`instructions`](Self::instructions) when the qubits matter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{Circuit, GateType};
+    ///
+    /// let circuit = Circuit::new(2).h(0).cx(0, 1);
+    /// let kinds: Vec<GateType> = circuit.gates().map(|gate| gate.gate_type).collect();
+    /// assert_eq!(kinds, [GateType::H, GateType::CX]);
+    /// ```
+    pub fn gates(&self) -> impl Iterator<Item = &Gate> + '_ {
+        self.instructions.iter().map(|inst| &inst.gate)
+    }
+
     /// Get the number of instructions.
     #[inline]
     pub fn len(&self) -> usize {
@@ -122,7 +278,26 @@ impl Circuit {
         self.instructions.is_empty()
     }
 
+    /// Number of unitary gates, excluding measurements, resets and barriers.
+    pub fn gate_count(&self) -> usize {
+        self.instructions.iter().filter(|inst| inst.gate.is_unitary()).count()
+    }
+
+    /// Number of unitary gates acting on exactly two qubits.
+    ///
+    /// These dominate error rates on current hardware, so this is usually
+    /// the first number to compare between two versions of a circuit.
+    pub fn two_qubit_gate_count(&self) -> usize {
+        self.instructions
+            .iter()
+            .filter(|inst| inst.gate.is_unitary() && inst.qubits.len() == 2)
+            .count()
+    }
+
     /// Calculate circuit depth (critical path length).
+    ///
+    /// Gates on disjoint qubits share a layer; a gate sharing a qubit with
+    /// an earlier one goes in a later layer. Barriers add no depth.
     pub fn depth(&self) -> usize {
         if self.instructions.is_empty() {
             return 0;
@@ -157,11 +332,356 @@ impl Circuit {
         counts
     }
 
+    /// Estimate the gate count after decomposing into `basis`.
+    ///
+    /// Each instruction is costed with [`BasisSet::gate_cost`]. Returns `None`
+    /// if some gate cannot be expressed exactly in the basis.
+    pub fn estimated_gate_count(&self, basis: &BasisSet) -> Option<usize> {
+        self.instructions
+            .iter()
+            .map(|inst| basis.gate_cost(inst.gate.gate_type))
+            .sum()
+    }
+
+    /// Check whether the circuit fits a gate budget once expressed in `basis`.
+    ///
+    /// Handy for NISQ feasibility screening before transpiling: a circuit
+    /// that cannot be expressed in the basis never fits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{BasisSet, Circuit};
+    ///
+    /// // A Toffoli costs 15 Clifford+T gates
+    /// let circuit = Circuit::new(3).ccx(0, 1, 2);
+    /// assert!(circuit.fits_budget(&BasisSet::clifford_t(), 15));
+    /// assert!(!circuit.fits_budget(&BasisSet::clifford_t(), 14));
+    /// ```
+    pub fn fits_budget(&self, basis: &BasisSet, max_gates: usize) -> bool {
+        self.estimated_gate_count(basis)
+            .is_some_and(|count| count <= max_gates)
+    }
+
+    /// Describe which instructions are conditioned on which classical bits.
+    ///
+    /// See [`ControlFlowGraph`].
+    pub fn control_flow(&self) -> ControlFlowGraph {
+        ControlFlowGraph::new(self)
+    }
+
+    /// Export as an OpenQASM 2.0 program.
+    ///
+    /// Targets Qiskit's `qelib1.inc`. The classical register is sized by
+    /// the highest classical bit actually used, and omitted when none is.
+    /// Conditioned instructions need a register per bit (`c0`, `c1`, ...)
+    /// since OpenQASM 2.0 can only test whole registers. Qubits go in one
+    /// `q` register unless [named registers](Self::add_register) cover them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let qasm = Circuit::new(2).h(0).cx(0, 1).to_qasm();
+    /// assert!(qasm.ends_with("qreg q[2];\nh q[0];\ncx q[0],q[1];\n"));
+    /// ```
+    pub fn to_qasm(&self) -> std::string::String {
+        crate::qasm::to_qasm(self)
+    }
+
+    /// Parse an OpenQASM 2.0 program.
+    ///
+    /// Reads back everything [`to_qasm`](Self::to_qasm) writes: `qreg` and
+    /// `creg` declarations, the `qelib1.inc` gates this crate supports,
+    /// `measure`, `reset`, `barrier` and single-bit `if(c==1)` conditions.
+    /// Custom `gate` definitions and `opaque` are not supported. Quantum
+    /// registers other than a lone `q` are kept as
+    /// [`registers`](Self::registers).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HomayaError::ParseError`] with the 1-based line number of
+    /// the first malformed or unsupported statement.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let circuit = Circuit::from_qasm("qreg q[2];\nh q[0];\ncx q[0],q[1];").unwrap();
+    /// assert_eq!(circuit.len(), 2);
+    /// ```
+    pub fn from_qasm(source: &str) -> Result<Self> {
+        crate::qasm::from_qasm(source)
+    }
+
+    /// Serialize as JSON.
+    ///
+    /// See the schema below; it is stable across versions, unlike the
+    /// serde derive on `Circuit` itself, which mirrors the internal layout.
+    ///
+    /// ```json
+    /// {"qubits": 2, "clbits": 0, "gates": [
+    ///   {"op": "h", "targets": [0]},
+    ///   {"op": "cx", "controls": [0], "targets": [1]}
+    /// ]}
+    /// ```
+    ///
+    /// Op names match [`to_qasm`](Self::to_qasm); `params`, `clbits` and a
+    /// `condition` bit appear when present. Labeled blocks and registers are
+    /// not stored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let json = Circuit::new(2).h(0).cx(0, 1).to_json();
+    /// assert_eq!(Circuit::from_json(&json).unwrap().len(), 2);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> std::string::String {
+        crate::json::to_json(self)
+    }
+
+    /// Parse a circuit from the JSON schema written by [`to_json`](Self::to_json).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HomayaError::ParseError`] for malformed JSON or an unknown
+    /// op, and a range or parameter error for a gate that does not fit.
+    #[cfg(feature = "serde")]
+    pub fn from_json(source: &str) -> Result<Self> {
+        crate::json::from_json(source)
+    }
+
+    /// Render a text diagram of the circuit.
+    ///
+    /// One wire per qubit with time flowing left to right. Gates that touch
+    /// disjoint wires share a column; controls are drawn as `●` joined by
+    /// `│` to their target (`⊕` for X), and measurements as `M`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let drawing = Circuit::new(2).h(0).cx(0, 1).draw();
+    /// assert!(drawing.starts_with("q0: ─H──●─"));
+    /// ```
+    pub fn draw(&self) -> std::string::String {
+        crate::draw::draw(self)
+    }
+
+    /// Render the circuit as a self-contained SVG image.
+    ///
+    /// Uses the same column layout as [`draw`](Self::draw). Gates are
+    /// labeled boxes, controls filled dots joined to their target by a
+    /// vertical line, X targets `⊕`, and measurements boxes with a meter.
+    /// Elements carry a `class` (`gate`, `control`, `connector`, ...) for
+    /// styling.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let svg = Circuit::new(2).h(0).cx(0, 1).to_svg();
+    /// assert!(svg.starts_with("<svg"));
+    /// ```
+    pub fn to_svg(&self) -> std::string::String {
+        crate::draw::svg(self)
+    }
+
+    /// Check the circuit for likely mistakes.
+    ///
+    /// See [`LintWarning`](crate::LintWarning) for what is flagged. An empty list means nothing
+    /// looked suspicious, not that the circuit is correct.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{Circuit, LintWarning};
+    ///
+    /// let warnings = Circuit::new(2).h(0).measure(0, 0).lint();
+    /// assert_eq!(warnings, vec![LintWarning::UnusedQubit { qubit: 1 }]);
+    /// ```
+    pub fn lint(&self) -> Vec<crate::LintWarning> {
+        crate::lint::lint(self)
+    }
+
+    /// Remove adjacent pairs of mutually inverse gates.
+    ///
+    /// Two gates cancel when nothing else touches their qubits in between,
+    /// they act on the same qubits in the same order, and one is the
+    /// [inverse](Gate::inverse) of the other: H·H, X·X, CX·CX, S·S†,
+    /// Rz(θ)·Rz(-θ) and so on. Cancelling a pair can make the gates around
+    /// it adjacent, and those are removed too, until no such pair is left.
+    /// The unitary is unchanged exactly; no gates are merged or rewritten.
+    ///
+    /// Measurements, resets, barriers and classically conditioned gates are
+    /// kept and block cancellation across them. Block labels are kept and
+    /// shrink with their contents.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let circuit = Circuit::new(2).x(0).h(1).cx(0, 1).cx(0, 1).h(1).z(0);
+    /// assert_eq!(circuit.optimize().len(), 2); // X(0) and Z(0)
+    /// assert!(Circuit::new(1).h(0).h(0).optimize().is_empty());
+    /// ```
+    pub fn optimize(&self) -> Self {
+        let kept = crate::optimize::kept_instructions(self);
+        let mut kept_before = Vec::with_capacity(kept.len() + 1);
+        kept_before.push(0);
+        for &keep in &kept {
+            kept_before.push(kept_before.last().copied().unwrap_or(0) + keep as usize);
+        }
+
+        let mut result = self.clone();
+        result.instructions = self
+            .instructions
+            .iter()
+            .zip(&kept)
+            .filter(|(_, &keep)| keep)
+            .map(|(inst, _)| inst.clone())
+            .collect();
+        for block in &mut result.blocks {
+            block.start = kept_before[block.start];
+            block.end = kept_before[block.end];
+        }
+        result
+    }
+
+    /// Replace every Toffoli with H, T, T† and CX gates.
+    ///
+    /// Each `ccx(c1, c2, t)` becomes the standard exact decomposition with
+    /// 6 CX, 7 T/T† and 2 H gates, for backends without a native Toffoli.
+    /// A condition on the CCX is copied to each replacement gate and block
+    /// labels grow to cover them. Other gates, CSWAP included, are kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{Circuit, GateType};
+    ///
+    /// let circuit = Circuit::new(3).ccx(0, 1, 2).decompose_ccx();
+    /// assert_eq!(circuit.len(), 15);
+    /// assert_eq!(circuit.count_gates().get(&GateType::CCX), None);
+    /// ```
+    pub fn decompose_ccx(&self) -> Self {
+        let mut result = self.clone();
+        result.instructions = Vec::with_capacity(self.instructions.len());
+        let mut start_of = Vec::with_capacity(self.instructions.len() + 1);
+
+        for inst in &self.instructions {
+            start_of.push(result.instructions.len());
+            if inst.gate.gate_type != GateType::CCX {
+                result.instructions.push(inst.clone());
+                continue;
+            }
+            let (c1, c2, t) = (inst.qubits[0], inst.qubits[1], inst.qubits[2]);
+
+            let sequence = [
+                (Gate::h(), std::vec![t]),
+                (Gate::cx(), std::vec![c2, t]),
+                (Gate::tdg(), std::vec![t]),
+                (Gate::cx(), std::vec![c1, t]),
+                (Gate::t(), std::vec![t]),
+                (Gate::cx(), std::vec![c2, t]),
+                (Gate::tdg(), std::vec![t]),
+                (Gate::cx(), std::vec![c1, t]),
+                (Gate::t(), std::vec![c2]),
+                (Gate::t(), std::vec![t]),
+                (Gate::h(), std::vec![t]),
+                (Gate::cx(), std::vec![c1, c2]),
+                (Gate::tdg(), std::vec![c2]),
+                (Gate::cx(), std::vec![c1, c2]),
+                (Gate::t(), std::vec![c1]),
+            ];
+            for (gate, qubits) in sequence {
+                let mut replacement = Instruction::new(gate, qubits);
+                replacement.condition = inst.condition;
+                result.instructions.push(replacement);
+            }
+        }
+        start_of.push(result.instructions.len());
+
+        for block in &mut result.blocks {
+            block.start = start_of[block.start];
+            block.end = start_of[block.end];
+        }
+        result
+    }
+
     /// Add a raw instruction.
-    fn push(&mut self, inst: Instruction) {
+    ///
+    /// # Panics
+    ///
+    /// Panics if a qubit is out of range or appears twice, so builder
+    /// mistakes like `cx(0, 0)` fail where they are made.
+    pub(crate) fn push(&mut self, inst: Instruction) {
+        for (i, &q) in inst.qubits.iter().enumerate() {
+            assert!(
+                q < self.num_qubits,
+                "qubit index {} out of range for {}-qubit circuit",
+                q,
+                self.num_qubits
+            );
+            assert!(
+                !inst.qubits[..i].contains(&q),
+                "{} acts on qubit {} more than once",
+                crate::qasm::qasm_name(inst.gate.gate_type),
+                q
+            );
+        }
         self.instructions.push(inst);
     }
 
+    /// Apply `gate` to `qubits`, reporting bad indices instead of panicking.
+    ///
+    /// The gate methods (`h`, `cx`, ...) panic on an out-of-range or
+    /// repeated qubit; use this when the indices come from input.
+    ///
+    /// # Errors
+    ///
+    /// [`HomayaError::QubitOutOfRange`] or [`HomayaError::DuplicateQubit`]
+    /// for a bad index, [`HomayaError::InvalidGateParams`] if the number
+    /// of qubits doesn't match the gate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{Circuit, Gate, HomayaError};
+    ///
+    /// let circuit = Circuit::new(2).try_apply(Gate::cx(), &[0, 1]).unwrap();
+    /// assert_eq!(circuit.len(), 1);
+    ///
+    /// let err = Circuit::new(2).try_apply(Gate::cx(), &[1, 1]).unwrap_err();
+    /// assert_eq!(err, HomayaError::DuplicateQubit { qubit: 1 });
+    /// ```
+    pub fn try_apply(mut self, gate: Gate, qubits: &[usize]) -> Result<Self> {
+        if gate.gate_type != GateType::Barrier && qubits.len() != gate.num_qubits() {
+            return Err(HomayaError::InvalidGateParams {
+                gate: crate::qasm::qasm_name(gate.gate_type),
+                message: "wrong number of qubits",
+            });
+        }
+        for (i, &q) in qubits.iter().enumerate() {
+            if q >= self.num_qubits {
+                return Err(HomayaError::QubitOutOfRange { qubit: q, max: self.num_qubits });
+            }
+            if qubits[..i].contains(&q) {
+                return Err(HomayaError::DuplicateQubit { qubit: q });
+            }
+        }
+        self.push(Instruction::new(gate, qubits.to_vec()));
+        Ok(self)
+    }
+
     // ========== Single-qubit gates ==========
 
     /// Apply identity gate.
@@ -248,7 +768,9 @@ impl Circuit {
         self
     }
 
-    /// Apply phase gate.
+    /// Apply phase gate P(θ) = diag(1, e^(iθ)).
+    ///
+    /// Generalizes the fixed phase gates: P(π) = Z, P(π/2) = S, P(π/4) = T.
     #[inline]
     pub fn p(mut self, theta: f64, q: usize) -> Self {
         self.push(Instruction::new(Gate::p(theta), std::vec![q]));
@@ -306,6 +828,15 @@ impl Circuit {
         self
     }
 
+    /// Apply SWAP as three CX gates.
+    ///
+    /// Same unitary as [`swap`](Self::swap), for export to hardware whose
+    /// native two-qubit gate is CX.
+    #[inline]
+    pub fn swap_decomposed(self, q1: usize, q2: usize) -> Self {
+        self.cx(q1, q2).cx(q2, q1).cx(q1, q2)
+    }
+
     // ========== Three-qubit gates ==========
 
     /// Apply Toffoli (CCX) gate.
@@ -322,9 +853,97 @@ impl Circuit {
         self
     }
 
+    // ========== Multi-controlled gates ==========
+
+    /// Apply X to `target` when every control is |1⟩.
+    ///
+    /// Up to two controls this is a single X, CX or CCX. With n ≥ 3
+    /// controls it is the standard Toffoli ladder: the AND of the controls
+    /// is computed into n − 2 ancillas, one CCX onto the target reads it
+    /// off, and the ladder is run backwards to uncompute. That costs
+    /// 2(n − 2) + 1 CCX gates.
+    ///
+    /// The ancillas must be *clean*: in |0⟩ on entry. They are returned to
+    /// |0⟩. Extra ancillas beyond n − 2 are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than n − 2 ancillas are given, or a qubit appears
+    /// twice among the controls, target and ancillas used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// // 4 controls (0-3), target 4, ancillas 5 and 6
+    /// let circuit = Circuit::new(7).mcx(&[0, 1, 2, 3], 4, &[5, 6]);
+    /// assert_eq!(circuit.len(), 5);
+    /// ```
+    pub fn mcx(self, controls: &[usize], target: usize, ancillas: &[usize]) -> Self {
+        let needed = controls.len().saturating_sub(2);
+        assert!(
+            ancillas.len() >= needed,
+            "{} controls need {} clean ancillas, got {}",
+            controls.len(),
+            needed,
+            ancillas.len()
+        );
+        let ancillas = &ancillas[..needed];
+        let used: std::vec::Vec<usize> = controls.iter().chain(ancillas).copied().chain([target]).collect();
+        for (i, q) in used.iter().enumerate() {
+            assert!(!used[..i].contains(q), "qubit {} is listed twice", q);
+        }
+
+        match *controls {
+            [] => self.x(target),
+            [c] => self.cx(c, target),
+            [c1, c2] => self.ccx(c1, c2, target),
+            [c1, c2, ref rest @ ..] => {
+                // ancillas[i] holds the AND of controls 0..=i+1
+                let ladder = |circuit: Circuit, i: usize| circuit.ccx(rest[i - 1], ancillas[i - 1], ancillas[i]);
+                let steps = 1..needed;
+
+                let mut circuit = steps.clone().fold(self.ccx(c1, c2, ancillas[0]), ladder);
+                circuit = circuit.ccx(rest[needed - 1], ancillas[needed - 1], target);
+                let circuit = steps.rev().fold(circuit, ladder);
+                circuit.ccx(c1, c2, ancillas[0])
+            }
+        }
+    }
+
+    /// Apply Z to the last qubit when all others are |1⟩.
+    ///
+    /// The gate is symmetric: it flips the sign of |1...1⟩ on `qubits`,
+    /// whichever is called the target. Built as H·MCX·H on the last qubit,
+    /// so n qubits need n − 3 clean ancillas (see [`mcx`](Self::mcx)).
+    ///
+    /// # Panics
+    ///
+    /// Same as [`mcx`](Self::mcx).
+    pub fn mcz(self, qubits: &[usize], ancillas: &[usize]) -> Self {
+        match *qubits {
+            [] => self,
+            [q] => self.z(q),
+            [a, b] => self.cz(a, b),
+            [ref controls @ .., target] => self.h(target).mcx(controls, target, ancillas).h(target),
+        }
+    }
+
     // ========== Special operations ==========
 
-    /// Measure a qubit.
+    /// Measure a qubit into classical bit `c`.
+    ///
+    /// Measurements may appear anywhere, not just at the end: the simulator
+    /// samples an outcome (reproducibly with a seeded simulator), collapses
+    /// the state onto it, and later gates act on the collapsed state. The
+    /// bit is then visible to [`c_if`](Self::c_if).
+    ///
+    /// [`num_clbits`](Self::num_clbits) grows to `c + 1` if needed, so any
+    /// bit index is accepted. Measuring into a bit that already holds a
+    /// result overwrites it: the last write wins, in the simulator and in
+    /// the exported QASM alike. [`lint`](Self::lint) flags an overwrite
+    /// that happens before any `c_if` has read the bit.
     #[inline]
     pub fn measure(mut self, q: usize, c: usize) -> Self {
         // Ensure we have enough classical bits
@@ -335,7 +954,47 @@ impl Circuit {
         self
     }
 
-    /// Measure all qubits.
+    /// Measure a qubit, then reset it to |0⟩ for reuse.
+    ///
+    /// Iterative algorithms use this to recycle one ancilla across rounds.
+    #[inline]
+    pub fn measure_reset(self, q: usize, c: usize) -> Self {
+        self.measure(q, c).reset(q)
+    }
+
+    /// Measure each qubit of `qubits` into the classical bit at its position.
+    ///
+    /// `qubits[i]` goes to classical bit i, and the classical register
+    /// grows to `qubits.len()` bits if smaller. Unlisted qubits (ancillas)
+    /// are left unmeasured.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let circuit = Circuit::new(4).measure_qubits(&[3, 1]);
+    /// assert_eq!(circuit.num_clbits(), 2);
+    /// assert_eq!(circuit.instructions()[0].qubits, vec![3]);
+    /// assert_eq!(circuit.instructions()[0].clbits, vec![0]);
+    /// ```
+    pub fn measure_qubits(self, qubits: &[usize]) -> Self {
+        qubits.iter().enumerate().fold(self, |circuit, (c, &q)| circuit.measure(q, c))
+    }
+
+    /// Measure the qubits in `qubits` into classical bits 0, 1, ...
+    ///
+    /// Shorthand for [`measure_qubits`](Self::measure_qubits) over a range;
+    /// `measure_range(0..n)` measures qubit i into classical bit i.
+    pub fn measure_range(self, qubits: core::ops::Range<usize>) -> Self {
+        qubits.enumerate().fold(self, |circuit, (c, q)| circuit.measure(q, c))
+    }
+
+    /// Measure all qubits, qubit i into classical bit i.
+    ///
+    /// The mapping is the same under either [`BitOrder`](crate::BitOrder):
+    /// the order only decides whether bit 0 is printed rightmost (the
+    /// default) or leftmost.
     pub fn measure_all(mut self) -> Self {
         // Ensure we have enough classical bits
         if self.num_clbits < self.num_qubits {
@@ -347,13 +1006,56 @@ impl Circuit {
         self
     }
 
+    /// Measure the parity (XOR) of several qubits into one classical bit.
+    ///
+    /// A fresh ancilla qubit is appended at index `num_qubits()`, every listed
+    /// qubit is CX-ed into it, and the ancilla is measured into `c`. Only the
+    /// joint parity is revealed - the individual qubits are not measured.
+    pub fn measure_parity(mut self, qubits: &[usize], c: usize) -> Self {
+        let ancilla = self.num_qubits;
+        self.num_qubits += 1;
+        for &q in qubits {
+            self.push(Instruction::new(Gate::cx(), std::vec![q, ancilla]));
+        }
+        self.measure(ancilla, c)
+    }
+
     /// Reset a qubit to |0⟩.
+    ///
+    /// Not unitary: the simulator measures the qubit, discards the outcome
+    /// and flips it back to |0⟩ if it read 1. Entangled partners collapse
+    /// accordingly. No classical bit is written, so nothing is visible to
+    /// [`c_if`](Self::c_if).
     #[inline]
     pub fn reset(mut self, q: usize) -> Self {
         self.push(Instruction::new(Gate::reset(), std::vec![q]));
         self
     }
 
+    /// Apply `op` only if classical bit `clbit` reads 1.
+    ///
+    /// The bit holds whatever the most recent measurement into it wrote
+    /// (0 before any). The simulator collapses the state at each
+    /// measurement and samples the outcome, so the condition sees one
+    /// concrete bit per run rather than a superposition of branches.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{Circuit, ConditionalGate};
+    ///
+    /// // Feed-forward: copy a measured bit onto qubit 1
+    /// let circuit = Circuit::new(2).h(0).measure(0, 0).c_if(0, ConditionalGate::x(1));
+    /// assert!(circuit.control_flow().is_branching());
+    /// ```
+    pub fn c_if(mut self, clbit: usize, op: ConditionalGate) -> Self {
+        if self.num_clbits <= clbit {
+            self.num_clbits = clbit + 1;
+        }
+        self.push(Instruction::new(op.gate, op.qubits).conditioned_on(clbit));
+        self
+    }
+
     /// Add a barrier (synchronization point).
     pub fn barrier(mut self, qubits: &[usize]) -> Self {
         self.push(Instruction::new(Gate::barrier(), qubits.to_vec()));
@@ -377,42 +1079,696 @@ impl Circuit {
                 got: other.num_qubits,
             });
         }
+        self.extend_mapped(other, |q| q);
+        Ok(self)
+    }
+
+    /// Append another circuit of the same width.
+    ///
+    /// Like [`compose`](Self::compose), but for pieces known to fit, such as
+    /// an oracle followed by a diffuser.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the qubit counts differ.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let layer = Circuit::new(2).h(0).h(1);
+    /// let circuit = layer.clone().append(&layer);
+    /// assert_eq!(circuit.len(), 4);
+    /// ```
+    pub fn append(mut self, other: &Circuit) -> Self {
+        assert_eq!(
+            self.num_qubits, other.num_qubits,
+            "cannot append a {}-qubit circuit to a {}-qubit circuit",
+            other.num_qubits, self.num_qubits
+        );
+        self.extend_mapped(other, |q| q);
+        self
+    }
+
+    /// Append another circuit with its qubit `i` relocated to `qubits[i]`.
+    ///
+    /// Lets a small block, written on its own qubits 0..k, act on any k
+    /// qubits of a larger circuit. Classical bits are kept as they are.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `qubits` does not have one entry per qubit of `other`, has
+    /// duplicates, or names a qubit outside this circuit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let bell = Circuit::new(2).h(0).cx(0, 1);
+    /// let circuit = Circuit::new(4).append_on(&bell, &[1, 3]);
+    /// assert_eq!(circuit.instructions()[1].qubits, vec![1, 3]);
+    /// ```
+    pub fn append_on(mut self, other: &Circuit, qubits: &[usize]) -> Self {
+        assert_eq!(
+            qubits.len(),
+            other.num_qubits,
+            "need one target qubit per qubit of the appended circuit"
+        );
+        for (i, &q) in qubits.iter().enumerate() {
+            assert!(q < self.num_qubits, "qubit {} is out of range for {} qubits", q, self.num_qubits);
+            assert!(!qubits[..i].contains(&q), "qubit {} is listed twice", q);
+        }
+        self.extend_mapped(other, |q| qubits[q]);
+        self
+    }
+
+    /// Push `other`'s instructions and blocks, relabeling qubits with `map`.
+    fn extend_mapped(&mut self, other: &Circuit, map: impl Fn(usize) -> usize) {
+        self.append_blocks(other);
+        self.num_clbits = self.num_clbits.max(other.num_clbits);
         for inst in &other.instructions {
-            self.push(inst.clone());
+            let mut inst = inst.clone();
+            for q in &mut inst.qubits {
+                *q = map(*q);
+            }
+            self.push(inst);
         }
-        Ok(self)
+    }
+
+    /// Append another circuit as a labeled block.
+    ///
+    /// Works like [`compose`](Self::compose) but remembers where the block
+    /// went, so it can be counted later with [`count_blocks`](Self::count_blocks).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let oracle = Circuit::new(2).cz(0, 1);
+    /// let circuit = Circuit::new(2)
+    ///     .compose_block("oracle", &oracle).unwrap()
+    ///     .h(0)
+    ///     .compose_block("oracle", &oracle).unwrap();
+    ///
+    /// assert_eq!(circuit.count_blocks("oracle"), 2);
+    /// ```
+    pub fn compose_block(self, label: &str, block: &Circuit) -> Result<Self> {
+        let start = self.instructions.len();
+        let mut result = self.compose(block)?;
+        result.blocks.push(Block {
+            label: label.into(),
+            start,
+            end: result.instructions.len(),
+        });
+        Ok(result)
+    }
+
+    /// Count the blocks added under `label`.
+    pub fn count_blocks(&self, label: &str) -> usize {
+        self.blocks.iter().filter(|block| block.label == label).count()
+    }
+
+    /// Each block's label, first instruction index and instructions.
+    pub(crate) fn block_spans(&self) -> impl Iterator<Item = (&str, usize, &[Instruction])> {
+        self.blocks
+            .iter()
+            .map(|block| (block.label.as_str(), block.start, &self.instructions[block.start..block.end]))
+    }
+
+    /// Copy `other`'s blocks, shifted to start after this circuit's instructions.
+    fn append_blocks(&mut self, other: &Circuit) {
+        let offset = self.instructions.len();
+        self.blocks.extend(other.blocks.iter().map(|block| Block {
+            label: block.label.clone(),
+            start: block.start + offset,
+            end: block.end + offset,
+        }));
+    }
+
+    /// Place another circuit side by side on fresh qubits.
+    ///
+    /// Unlike [`compose`](Self::compose), which overlaps qubits, `other`'s
+    /// qubits and classical bits are shifted up past this circuit's, so the
+    /// result acts as the tensor product `other ⊗ self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let bell = Circuit::new(2).h(0).cx(0, 1);
+    /// let both = bell.tensor(&Circuit::new(1).h(0));
+    ///
+    /// assert_eq!(both.num_qubits(), 3);
+    /// assert_eq!(both.instructions()[2].qubits, vec![2]);
+    /// ```
+    pub fn tensor(&self, other: &Circuit) -> Self {
+        let mut result = self.clone();
+        result.num_qubits += other.num_qubits;
+        result.num_clbits += other.num_clbits;
+        result.append_blocks(other);
+
+        for inst in &other.instructions {
+            let mut inst = inst.clone();
+            for q in &mut inst.qubits {
+                *q += self.num_qubits;
+            }
+            for c in &mut inst.clbits {
+                *c += self.num_clbits;
+            }
+            result.push(inst);
+        }
+        result
     }
 
     /// Repeat the circuit n times.
     pub fn repeat(self, n: usize) -> Self {
-        let original = self.instructions.clone();
+        let original = self.clone();
         let mut result = self;
         for _ in 1..n {
-            for inst in &original {
+            result.append_blocks(&original);
+            for inst in &original.instructions {
                 result.push(inst.clone());
             }
         }
         result
     }
 
+    /// The circuit applied `k` times in a row, U^k.
+    ///
+    /// `power(0)` is an empty circuit of the same width (the identity).
+    /// Together with [`controlled`](Self::controlled) this gives the
+    /// controlled-U^(2^j) blocks of phase estimation.
+    ///
+    /// # Errors
+    ///
+    /// [`HomayaError::NotSupported`] if the circuit measures or resets a
+    /// qubit: a power is only meaningful for a unitary.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let u = Circuit::new(2).cx(0, 1).t(1);
+    /// let cu4 = u.power(4).unwrap().controlled(2).unwrap();
+    /// assert_eq!(cu4.num_qubits(), 3);
+    /// ```
+    pub fn power(&self, k: usize) -> Result<Self> {
+        let non_unitary = self
+            .instructions
+            .iter()
+            .any(|inst| matches!(inst.gate.gate_type, GateType::Measure | GateType::Reset));
+        if non_unitary {
+            return Err(HomayaError::NotSupported {
+                operation: "only unitary circuits can be raised to a power",
+            });
+        }
+
+        let mut result = Circuit::with_clbits(self.num_qubits, self.num_clbits);
+        for _ in 0..k {
+            result = result.append(self);
+        }
+        Ok(result)
+    }
+
     /// Get the inverse of this circuit.
-    pub fn inverse(self) -> Self {
+    ///
+    /// Instructions are reversed and each gate replaced by its inverse:
+    /// self-inverse gates stay, rotations and phases negate their angles,
+    /// S/T swap with S†/T†, and iSWAP and √SWAP expand to the sequences from
+    /// [`Gate::inverse_sequence`]. Measurements and resets have no inverse
+    /// and are dropped; barriers are kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let prep = Circuit::new(2).ry(0.4, 0).cx(0, 1).t(1);
+    /// let round_trip = prep.clone().append(&prep.inverse());
+    /// assert_eq!(round_trip.len(), 6);
+    /// ```
+    pub fn inverse(&self) -> Self {
         let mut result = Circuit::new(self.num_qubits);
         result.num_clbits = self.num_clbits;
 
         // Reverse order and invert each gate
-        for inst in self.instructions.into_iter().rev() {
-            // For now, just reverse (TODO: proper gate inversion)
-            result.push(inst);
+        let reversed: Vec<&Instruction> = self.instructions.iter().rev().collect();
+        let mut kept_before = Vec::with_capacity(reversed.len() + 1);
+        kept_before.push(0);
+        for inst in &reversed {
+            match inst.gate.gate_type {
+                GateType::Barrier => result.push((*inst).clone()),
+                GateType::Measure | GateType::Reset => {}
+                _ => {
+                    let gates = inst.gate.inverse_sequence().expect("unitary gates have an inverse");
+                    for gate in gates {
+                        result.push(Instruction { gate, ..(*inst).clone() });
+                    }
+                }
+            }
+            kept_before.push(result.instructions.len());
         }
+
+        // Blocks keep their labels but mirror their positions
+        let len = self.instructions.len();
+        result.blocks = self
+            .blocks
+            .iter()
+            .rev()
+            .map(|block| Block {
+                label: block.label.clone(),
+                start: kept_before[len - block.end],
+                end: kept_before[len - block.start],
+            })
+            .collect();
+
         result
     }
+
+    /// Add a control qubit to every gate.
+    ///
+    /// The result applies this circuit's unitary when qubit `control` is
+    /// |1⟩ and does nothing when it is |0⟩. `control` may be a qubit the
+    /// circuit never acts on, or a new one: the result has
+    /// `max(num_qubits, control + 1)` qubits, so `controlled(num_qubits())`
+    /// adds the control as the last qubit. Other qubits keep their indices.
+    ///
+    /// Gates are promoted to their controlled forms: X, Y, Z, H and SWAP to
+    /// CX, CY, CZ, CH and CSWAP; S, T, P and their inverses to CP; CX to
+    /// CCX. Rotations, U, CY, CZ, CH, CP and CCX become short decompositions
+    /// into those gates; Rz is P(-θ/2) on the control and CP(θ), and CCX is
+    /// a controlled CCZ between H gates on its target. Identities are
+    /// dropped and barriers gain the control. Blocks are not carried over.
+    ///
+    /// # Errors
+    ///
+    /// [`HomayaError::DuplicateQubit`] if a gate already acts on `control`,
+    /// and [`HomayaError::NotSupported`] for measurements, resets,
+    /// classically conditioned gates, CSWAP and the remaining two-qubit
+    /// gates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{Circuit, GateType};
+    ///
+    /// let cx = Circuit::new(1).x(0).controlled(1).unwrap();
+    /// assert_eq!(cx.num_qubits(), 2);
+    /// assert_eq!(cx.instructions()[0].gate.gate_type, GateType::CX);
+    /// assert_eq!(cx.instructions()[0].qubits, vec![1, 0]);
+    /// ```
+    pub fn controlled(&self, control: usize) -> Result<Self> {
+        use GateType::*;
+
+        let mut result = Circuit::with_clbits(self.num_qubits.max(control + 1), self.num_clbits);
+        for inst in &self.instructions {
+            if inst.is_conditional() {
+                return Err(HomayaError::NotSupported {
+                    operation: "classically conditioned gates cannot be controlled",
+                });
+            }
+            if inst.qubits.contains(&control) {
+                return Err(HomayaError::DuplicateQubit { qubit: control });
+            }
+
+            let q = &inst.qubits;
+            let angle = match inst.gate.params {
+                GateParams::Angle(theta) => theta,
+                _ => 0.0,
+            };
+            result = match inst.gate.gate_type {
+                I => result,
+                X => result.cx(control, q[0]),
+                Y => result.cy(control, q[0]),
+                Z => result.cz(control, q[0]),
+                H => result.ch(control, q[0]),
+                S => result.cp(PI / 2.0, control, q[0]),
+                Sdg => result.cp(-PI / 2.0, control, q[0]),
+                T => result.cp(PI / 4.0, control, q[0]),
+                Tdg => result.cp(-PI / 4.0, control, q[0]),
+                P => result.cp(angle, control, q[0]),
+                // Rz(θ) = e^(-iθ/2)·P(θ): the relative phase lands on the control
+                Rz => result.p(-angle / 2.0, control).cp(angle, control, q[0]),
+                Ry => result.ry(angle / 2.0, q[0]).cx(control, q[0]).ry(-angle / 2.0, q[0]).cx(control, q[0]),
+                Rx => result
+                    .h(q[0])
+                    .rz(angle / 2.0, q[0])
+                    .cx(control, q[0])
+                    .rz(-angle / 2.0, q[0])
+                    .cx(control, q[0])
+                    .h(q[0]),
+                U => {
+                    let GateParams::Angles3(theta, phi, lambda) = inst.gate.params else {
+                        return Err(HomayaError::InvalidGateParams { gate: "u", message: "expected 3 angles" });
+                    };
+                    result
+                        .p((lambda + phi) / 2.0, control)
+                        .p((lambda - phi) / 2.0, q[0])
+                        .cx(control, q[0])
+                        .u(-theta / 2.0, 0.0, -(phi + lambda) / 2.0, q[0])
+                        .cx(control, q[0])
+                        .u(theta / 2.0, phi, 0.0, q[0])
+                }
+                CX => result.ccx(control, q[0], q[1]),
+                CY => result.sdg(q[1]).ccx(control, q[0], q[1]).s(q[1]),
+                CZ => result.h(q[1]).ccx(control, q[0], q[1]).h(q[1]),
+                // CH = S·H·T · CX · T†·H·S† on the target
+                CH => result
+                    .s(q[1])
+                    .h(q[1])
+                    .t(q[1])
+                    .ccx(control, q[0], q[1])
+                    .tdg(q[1])
+                    .h(q[1])
+                    .sdg(q[1]),
+                // Doubly controlled phase from square roots of the phase
+                CP => result
+                    .cp(angle / 2.0, q[0], q[1])
+                    .cx(control, q[0])
+                    .cp(-angle / 2.0, q[0], q[1])
+                    .cx(control, q[0])
+                    .cp(angle / 2.0, control, q[1]),
+                // CCZ as CP and CX gates (the CP rule below), each controlled in turn
+                CCX => {
+                    let ccz = Circuit::new(result.num_qubits)
+                        .cp(PI / 2.0, q[1], q[2])
+                        .cx(q[0], q[1])
+                        .cp(-PI / 2.0, q[1], q[2])
+                        .cx(q[0], q[1])
+                        .cp(PI / 2.0, q[0], q[2])
+                        .controlled(control)?;
+                    result.h(q[2]).compose(&ccz)?.h(q[2])
+                }
+                Swap => result.cswap(control, q[0], q[1]),
+                Barrier => {
+                    let mut qubits = q.clone();
+                    qubits.push(control);
+                    result.barrier(&qubits)
+                }
+                Measure | Reset => {
+                    return Err(HomayaError::NotSupported {
+                        operation: "measurements and resets cannot be controlled",
+                    })
+                }
+                _ => {
+                    return Err(HomayaError::NotSupported {
+                        operation: "no controlled form for this gate",
+                    })
+                }
+            };
+        }
+
+        Ok(result)
+    }
+
+    /// Stable 64-bit hash of the circuit's structure, for caching and dedup.
+    ///
+    /// Hashes the width and the canonicalized instruction list: gate types,
+    /// qubits, classical bits, conditions and parameters rounded to 1e-9.
+    /// Canonicalization places every instruction in its earliest layer and
+    /// sorts each layer by qubit, so circuits that only differ in the order
+    /// of gates on disjoint qubits hash equally. The name and block labels
+    /// are ignored. The hash uses FNV-1a and does not change between runs
+    /// or Rust versions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let a = Circuit::new(2).h(0).x(1).cx(0, 1);
+    /// let b = Circuit::new(2).x(1).h(0).cx(0, 1);
+    /// assert_eq!(a.structural_hash(), b.structural_hash());
+    /// ```
+    pub fn structural_hash(&self) -> u64 {
+        use crate::GateParams;
+
+        let mut hash = FNV_OFFSET;
+        let mut write = |value: u64| {
+            for byte in value.to_le_bytes() {
+                hash = (hash ^ byte as u64).wrapping_mul(FNV_PRIME);
+            }
+        };
+        let round = |angle: f64| (angle * 1e9).round() as i64 as u64;
+
+        write(self.num_qubits as u64);
+        write(self.num_clbits as u64);
+        for inst in self.canonical_order() {
+            write(inst.gate.gate_type as u64);
+            match inst.gate.params {
+                GateParams::None => write(0),
+                GateParams::Angle(theta) => {
+                    write(1);
+                    write(round(theta));
+                }
+                GateParams::Angles3(theta, phi, lambda) => {
+                    write(3);
+                    write(round(theta));
+                    write(round(phi));
+                    write(round(lambda));
+                }
+            }
+            write(inst.qubits.len() as u64);
+            inst.qubits.iter().for_each(|&q| write(q as u64));
+            write(inst.clbits.len() as u64);
+            inst.clbits.iter().for_each(|&c| write(c as u64));
+            write(inst.condition.map_or(0, |c| c as u64 + 1));
+        }
+
+        hash
+    }
+
+    /// Instructions in canonical order: earliest layer first, then by qubit.
+    ///
+    /// Classical bits count as wires too, so measurements into the same bit
+    /// and the conditions reading it keep their relative order.
+    fn canonical_order(&self) -> Vec<&Instruction> {
+        let clbit_wires = self
+            .instructions
+            .iter()
+            .flat_map(|inst| inst.clbits.iter().chain(&inst.condition))
+            .max()
+            .map_or(0, |&c| c + 1);
+        let mut qubit_layer = std::vec![0usize; self.num_qubits];
+        let mut clbit_layer = std::vec![0usize; clbit_wires];
+
+        let mut layered: Vec<(usize, &Instruction)> = self
+            .instructions
+            .iter()
+            .map(|inst| {
+                let clbits: Vec<usize> = inst.clbits.iter().chain(&inst.condition).copied().collect();
+                let layer = inst
+                    .qubits
+                    .iter()
+                    .map(|&q| qubit_layer[q])
+                    .chain(clbits.iter().map(|&c| clbit_layer[c]))
+                    .max()
+                    .unwrap_or(0);
+                for &q in &inst.qubits {
+                    qubit_layer[q] = layer + 1;
+                }
+                for &c in &clbits {
+                    clbit_layer[c] = layer + 1;
+                }
+                (layer, inst)
+            })
+            .collect();
+
+        // Instructions sharing a layer touch disjoint wires, so this order is total
+        layered.sort_by(|(la, a), (lb, b)| la.cmp(lb).then_with(|| a.qubits.cmp(&b.qubits)));
+        layered.into_iter().map(|(_, inst)| inst).collect()
+    }
 }
 
+/// FNV-1a 64-bit offset basis.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a 64-bit prime.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_mcx_gate_counts() {
+        assert_eq!(Circuit::new(2).mcx(&[0], 1, &[]).len(), 1);
+        assert_eq!(Circuit::new(3).mcx(&[0, 1], 2, &[]).len(), 1);
+        // 2(n - 2) + 1 Toffolis
+        let circuit = Circuit::new(11).mcx(&[0, 1, 2, 3, 4, 5], 6, &[7, 8, 9, 10]);
+        assert_eq!(circuit.len(), 9);
+        assert!(circuit.instructions().iter().all(|inst| inst.gate.gate_type == GateType::CCX));
+    }
+
+    #[test]
+    #[should_panic(expected = "4 controls need 2 clean ancillas, got 1")]
+    fn test_mcx_needs_ancillas() {
+        Circuit::new(6).mcx(&[0, 1, 2, 3], 4, &[5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "qubit 2 is listed twice")]
+    fn test_mcx_rejects_overlap() {
+        Circuit::new(5).mcx(&[0, 1, 2], 3, &[2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "qubit index 10 out of range for 4-qubit circuit")]
+    fn test_gate_rejects_out_of_range_qubit() {
+        Circuit::new(4).h(10);
+    }
+
+    #[test]
+    #[should_panic(expected = "cx acts on qubit 0 more than once")]
+    fn test_gate_rejects_control_equal_to_target() {
+        Circuit::new(4).cx(0, 0);
+    }
+
+    #[test]
+    fn test_try_apply_reports_bad_qubits() {
+        assert_eq!(
+            Circuit::new(4).try_apply(Gate::h(), &[5]).unwrap_err(),
+            HomayaError::QubitOutOfRange { qubit: 5, max: 4 }
+        );
+        assert!(matches!(
+            Circuit::new(4).try_apply(Gate::cx(), &[0]),
+            Err(HomayaError::InvalidGateParams { .. })
+        ));
+        assert_eq!(Circuit::new(4).try_apply(Gate::rx(0.5), &[3]).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_gates_follow_program_order() {
+        let circuit = Circuit::new(2).h(0).cx(0, 1).measure(0, 0).measure(1, 1);
+        let kinds: std::vec::Vec<_> = circuit.gates().map(|gate| gate.gate_type).collect();
+        assert_eq!(kinds, [GateType::H, GateType::CX, GateType::Measure, GateType::Measure]);
+    }
+
+    #[test]
+    fn test_controlled_promotes_gates() {
+        let circuit = Circuit::new(2).x(0).s(1).cx(0, 1).swap(0, 1).i(0);
+        let controlled = circuit.controlled(2).unwrap();
+
+        assert_eq!(controlled.num_qubits(), 3);
+        let gates: std::vec::Vec<_> = controlled.gates().map(|gate| gate.gate_type).collect();
+        assert_eq!(gates, [GateType::CX, GateType::CP, GateType::CCX, GateType::CSwap]);
+        assert_eq!(controlled.instructions()[2].qubits, std::vec![2, 0, 1]);
+        assert_eq!(controlled.instructions()[1].gate.params, GateParams::Angle(PI / 2.0));
+    }
+
+    #[test]
+    fn test_controlled_rejects_what_it_cannot_control() {
+        assert_eq!(
+            Circuit::new(2).h(1).controlled(1).unwrap_err(),
+            HomayaError::DuplicateQubit { qubit: 1 }
+        );
+        assert!(matches!(
+            Circuit::new(1).measure(0, 0).controlled(1),
+            Err(HomayaError::NotSupported { .. })
+        ));
+        assert!(matches!(
+            Circuit::new(3).cswap(0, 1, 2).controlled(3),
+            Err(HomayaError::NotSupported { .. })
+        ));
+
+        // An idle qubit of the circuit can serve as the control
+        assert_eq!(Circuit::new(3).h(0).controlled(1).unwrap().num_qubits(), 3);
+    }
+
+    #[test]
+    fn test_power_repeats_unitaries_only() {
+        let u = Circuit::new(2).h(0).cx(0, 1);
+        assert_eq!(u.power(3).unwrap().len(), 6);
+        assert_eq!(u.power(1).unwrap().instructions(), u.instructions());
+
+        let identity = u.power(0).unwrap();
+        assert!(identity.is_empty());
+        assert_eq!(identity.num_qubits(), 2);
+
+        assert!(matches!(
+            Circuit::new(1).x(0).measure(0, 0).power(2),
+            Err(HomayaError::NotSupported { .. })
+        ));
+    }
+
+    #[test]
+    fn test_optimize_cancels_neighbours_only() {
+        assert!(Circuit::new(1).h(0).h(0).optimize().is_empty());
+
+        // Cascade: once H·H goes, the X gates meet
+        assert!(Circuit::new(1).x(0).h(0).h(0).x(0).optimize().is_empty());
+
+        // CX·CX cancels only with the same control and target
+        assert!(Circuit::new(2).cx(0, 1).cx(0, 1).optimize().is_empty());
+        assert_eq!(Circuit::new(2).cx(0, 1).cx(1, 0).optimize().len(), 2);
+
+        // A gate on one of the qubits in between blocks it
+        assert_eq!(Circuit::new(2).cx(0, 1).h(1).cx(0, 1).optimize().len(), 3);
+
+        // Inverse pairs, not equal pairs
+        assert!(Circuit::new(1).s(0).sdg(0).rz(0.3, 0).rz(-0.3, 0).optimize().is_empty());
+        assert_eq!(Circuit::new(1).s(0).s(0).optimize().len(), 2);
+    }
+
+    #[test]
+    fn test_optimize_stops_at_measurements() {
+        let circuit = Circuit::new(1).x(0).measure(0, 0).x(0);
+        assert_eq!(circuit.optimize().instructions(), circuit.instructions());
+
+        let barrier = Circuit::new(2).h(0).barrier_all().h(0);
+        assert_eq!(barrier.optimize().len(), 3);
+
+        // An empty barrier is a wall too, not an out-of-bounds lookup
+        let empty = Circuit::new(1).h(0).barrier(&[]).h(0);
+        assert_eq!(empty.optimize().len(), 3);
+        assert!(empty.lint().is_empty());
+
+        let conditioned = Circuit::with_clbits(1, 1).x(0).c_if(0, ConditionalGate::x(0));
+        assert_eq!(conditioned.optimize().len(), 2);
+    }
+
+    #[test]
+    fn test_optimize_shrinks_blocks() {
+        let oracle = Circuit::new(2).x(0).cz(0, 1).x(0);
+        let circuit = Circuit::new(2)
+            .x(0)
+            .compose_block("oracle", &oracle)
+            .unwrap()
+            .h(1);
+        let optimized = circuit.optimize();
+
+        // The leading X cancels the oracle's first X
+        assert_eq!(optimized.len(), 3);
+        assert_eq!(optimized.count_blocks("oracle"), 1);
+        assert_eq!(optimized.optimize().instructions(), optimized.instructions());
+    }
+
+    #[test]
+    fn test_decompose_ccx_gate_counts() {
+        let circuit = Circuit::new(4)
+            .h(3)
+            .compose_block("toffoli", &Circuit::new(4).ccx(3, 0, 1))
+            .unwrap()
+            .cswap(0, 1, 2)
+            .decompose_ccx();
+
+        let counts = circuit.count_gates();
+        assert_eq!(counts.get(&GateType::CCX), None);
+        assert_eq!(counts[&GateType::CX], 6);
+        assert_eq!(counts[&GateType::T] + counts[&GateType::Tdg], 7);
+        assert_eq!(counts[&GateType::H], 3);
+        assert_eq!(counts[&GateType::CSwap], 1);
+
+        let (_, start, block) = circuit.block_spans().next().unwrap();
+        assert_eq!((start, block.len()), (1, 15));
+        assert!(block.iter().all(|inst| !inst.qubits.contains(&2)));
+    }
+
     #[test]
     fn test_bell_state_circuit() {
         let circuit = Circuit::new(2)
@@ -445,10 +1801,8 @@ mod tests {
             .h(2)
             .h(3);
 
-        // Each H is on a different qubit, but our simple depth calc
-        // counts sequential instructions. For proper parallel depth,
-        // we'd need a more sophisticated algorithm.
         assert_eq!(circuit.len(), 4);
+        assert_eq!(circuit.depth(), 1);
     }
 
     #[test]
@@ -459,6 +1813,133 @@ mod tests {
         assert_eq!(circuit.len(), 4); // 1 H + 3 measures
     }
 
+    #[test]
+    fn test_measure_range_skips_ancilla() {
+        let circuit = Circuit::new(4).h(3).measure_range(0..3);
+
+        assert_eq!(circuit.num_clbits(), 3);
+        let measured: std::vec::Vec<_> = circuit.instructions()[1..]
+            .iter()
+            .map(|inst| (inst.qubits[0], inst.clbits[0]))
+            .collect();
+        assert_eq!(measured, std::vec![(0, 0), (1, 1), (2, 2)]);
+        assert!(circuit.to_qasm().contains("creg c[3];"));
+    }
+
+    #[test]
+    fn test_measure_grows_clbits_to_highest_bit() {
+        let circuit = Circuit::new(2).measure(0, 0).measure(1, 3);
+
+        assert_eq!(circuit.num_clbits(), 4);
+        assert!(circuit.to_qasm().contains("creg c[4];\nmeasure q[0] -> c[0];\nmeasure q[1] -> c[3];\n"));
+        assert_eq!(Circuit::new(1).measure(0, 3).measure(0, 1).num_clbits(), 4);
+    }
+
+    #[test]
+    fn test_measure_qubits_packs_clbits() {
+        let circuit = Circuit::with_clbits(4, 1).measure_qubits(&[2, 0]);
+
+        assert_eq!(circuit.num_clbits(), 2);
+        assert_eq!(circuit.instructions()[0].qubits, std::vec![2]);
+        assert_eq!(circuit.instructions()[1].clbits, std::vec![1]);
+        assert!(circuit.to_qasm().contains("creg c[2];"));
+    }
+
+    #[test]
+    fn test_measure_parity_adds_ancilla() {
+        let circuit = Circuit::new(3).measure_parity(&[0, 2], 0);
+
+        assert_eq!(circuit.num_qubits(), 4);
+        assert_eq!(circuit.num_clbits(), 1);
+        assert_eq!(circuit.len(), 3); // 2 CX + 1 measure
+        assert_eq!(circuit.instructions()[2].qubits, std::vec![3]);
+    }
+
+    #[test]
+    fn test_tensor_shifts_indices() {
+        let left = Circuit::new(2).h(0).measure(1, 0);
+        let right = Circuit::new(2).cx(0, 1).measure(1, 0);
+        let both = left.tensor(&right);
+
+        assert_eq!(both.num_qubits(), 4);
+        assert_eq!(both.num_clbits(), 2);
+        assert_eq!(both.instructions()[2].qubits, vec![2, 3]);
+        assert_eq!(both.instructions()[3].qubits, vec![3]);
+        assert_eq!(both.instructions()[3].clbits, vec![1]);
+    }
+
+    #[test]
+    fn test_blocks_survive_composition() {
+        let oracle = Circuit::new(2).cz(0, 1);
+        let step = Circuit::new(2).compose_block("oracle", &oracle).unwrap().h(0);
+
+        assert_eq!(step.count_blocks("oracle"), 1);
+        assert_eq!(step.count_blocks("diffusion"), 0);
+        assert_eq!(step.clone().repeat(3).count_blocks("oracle"), 3);
+        assert_eq!(Circuit::new(2).compose(&step).unwrap().count_blocks("oracle"), 1);
+
+        let inverted = step.inverse();
+        assert_eq!(inverted.count_blocks("oracle"), 1);
+        assert_eq!(inverted.blocks[0].start, 1);
+    }
+
+    #[test]
+    fn test_inverse_inverts_gates() {
+        let circuit = Circuit::new(2).rx(0.3, 0).s(1).cx(0, 1).inverse();
+        let gates: Vec<&Gate> = circuit.instructions().iter().map(|inst| &inst.gate).collect();
+
+        assert_eq!(gates, [&Gate::cx(), &Gate::sdg(), &Gate::rx(-0.3)]);
+    }
+
+    #[test]
+    fn test_inverse_pairs_cancel() {
+        let circuit = Circuit::new(3).h(0).rx(0.7, 1).cx(0, 1).t(2).cp(0.9, 1, 2).u(0.3, 0.5, 0.8, 0);
+        let round_trip = circuit.clone().append(&circuit.inverse());
+
+        // Instruction i cancels against its mirror image
+        let n = round_trip.len();
+        for i in 0..n / 2 {
+            let (a, b) = (&round_trip.instructions()[i], &round_trip.instructions()[n - 1 - i]);
+            assert_eq!(a.qubits, b.qubits);
+            assert_eq!(a.gate.inverse().as_ref(), Some(&b.gate));
+        }
+        assert_eq!(round_trip.instructions()[n - 4].gate, Gate::tdg());
+    }
+
+    #[test]
+    fn test_inverse_keeps_iswap_and_sqrt_swap() {
+        let source = "OPENQASM 2.0;\ninclude \"qelib1.inc\";\nqreg q[2];\niswap q[0],q[1];\nsqrt_swap q[1],q[0];\n";
+        let inverted = Circuit::from_qasm(source).unwrap().inverse();
+
+        let gates: Vec<GateType> = inverted.instructions().iter().map(|inst| inst.gate.gate_type).collect();
+        use GateType::{ISwap, SqrtSwap, Swap};
+        assert_eq!(gates, [Swap, SqrtSwap, ISwap, ISwap, ISwap]);
+        assert!(inverted.instructions()[..2].iter().all(|inst| inst.qubits == [1, 0]));
+    }
+
+    #[test]
+    fn test_inverse_drops_measurements() {
+        let circuit = Circuit::new(2).h(0).barrier_all().measure(0, 0).reset(1);
+        let inverted = circuit.inverse();
+
+        let gates: Vec<GateType> = inverted.instructions().iter().map(|inst| inst.gate.gate_type).collect();
+        assert_eq!(gates, [GateType::Barrier, GateType::H]);
+        assert_eq!(inverted.num_clbits(), 1);
+    }
+
+    #[test]
+    fn test_estimated_gate_count() {
+        let basis = BasisSet::clifford_t();
+        let circuit = Circuit::new(2).h(0).cz(0, 1).swap(0, 1).measure_all();
+
+        // 1 H + 3 (CZ) + 3 (SWAP) + 2 measurements
+        assert_eq!(circuit.estimated_gate_count(&basis), Some(9));
+
+        let rotated = Circuit::new(1).rx(0.3, 0);
+        assert_eq!(rotated.estimated_gate_count(&basis), None);
+        assert!(!rotated.fits_budget(&basis, 100));
+    }
+
     #[test]
     fn test_gate_count() {
         let circuit = Circuit::new(2)
@@ -471,4 +1952,110 @@ mod tests {
         assert_eq!(counts.get(&GateType::H), Some(&3));
         assert_eq!(counts.get(&GateType::CX), Some(&1));
     }
+
+    #[test]
+    fn test_structural_hash_ignores_commuting_order() {
+        let a = Circuit::new(3).h(0).rx(0.5, 1).cx(0, 2).z(1).measure(1, 0);
+        let b = Circuit::new(3).rx(0.5, 1).z(1).h(0).measure(1, 0).cx(0, 2);
+        assert_eq!(a.structural_hash(), b.structural_hash());
+
+        // Rounding absorbs floating-point noise in parameters
+        let noisy = Circuit::new(3).h(0).rx(0.5 + 1e-13, 1).cx(0, 2).z(1).measure(1, 0);
+        assert_eq!(a.structural_hash(), noisy.structural_hash());
+    }
+
+    #[test]
+    fn test_structural_hash_distinguishes_circuits() {
+        let base = Circuit::new(2).h(0).x(0);
+        assert_ne!(base.structural_hash(), Circuit::new(2).x(0).h(0).structural_hash());
+        assert_ne!(base.structural_hash(), Circuit::new(3).h(0).x(0).structural_hash());
+        assert_ne!(base.structural_hash(), Circuit::new(2).h(0).x(1).structural_hash());
+        assert_ne!(
+            Circuit::new(1).rz(0.5, 0).structural_hash(),
+            Circuit::new(1).rz(0.6, 0).structural_hash()
+        );
+    }
+
+    #[test]
+    fn test_append_doubles_gates() {
+        let h = Circuit::new(2).h(0).h(1);
+        let doubled = h.clone().append(&h);
+
+        let gates: Vec<GateType> = doubled.instructions().iter().map(|inst| inst.gate.gate_type).collect();
+        assert_eq!(gates, vec![GateType::H; 4]);
+        assert_eq!(doubled.instructions()[2].qubits, vec![0]);
+        assert_eq!(doubled.instructions()[3].qubits, vec![1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot append a 3-qubit circuit to a 2-qubit circuit")]
+    fn test_append_width_mismatch() {
+        let _ = Circuit::new(2).append(&Circuit::new(3));
+    }
+
+    #[test]
+    fn test_append_on_relocates_block() {
+        let block = Circuit::new(2).h(0).cx(0, 1).measure(1, 0);
+        let circuit = Circuit::new(4).x(0).append_on(&block, &[1, 3]);
+
+        let qubits: Vec<&[usize]> = circuit.instructions().iter().map(|inst| inst.qubits.as_slice()).collect();
+        assert_eq!(qubits, vec![&[0][..], &[1], &[1, 3], &[3]]);
+        assert_eq!(circuit.instructions()[3].clbits, vec![0]);
+        assert_eq!(circuit.num_clbits(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "qubit 2 is listed twice")]
+    fn test_append_on_duplicate_qubit() {
+        let _ = Circuit::new(4).append_on(&Circuit::new(2).cx(0, 1), &[2, 2]);
+    }
+
+
+    #[test]
+    fn test_depth_serializes_shared_qubits() {
+        assert_eq!(Circuit::new(3).h(0).h(1).h(2).depth(), 1);
+        assert_eq!(Circuit::new(2).h(0).cx(0, 1).h(1).depth(), 3);
+        assert_eq!(Circuit::new(3).h(0).barrier_all().h(1).depth(), 1);
+        assert_eq!(Circuit::new(0).depth(), 0);
+    }
+
+    #[test]
+    fn test_gate_counts() {
+        let circuit = Circuit::new(3).h(0).cx(0, 1).swap(1, 2).ccx(0, 1, 2).barrier_all().measure_all();
+        assert_eq!(circuit.len(), 8);
+        assert_eq!(circuit.gate_count(), 4);
+        assert_eq!(circuit.two_qubit_gate_count(), 2);
+    }
+
+    #[test]
+    fn test_registers_map_to_global_indices() {
+        let mut circuit = Circuit::new(1);
+        let query = circuit.add_register("query", 3);
+        let ancilla = circuit.add_register("ancilla", 2);
+
+        assert_eq!(circuit.num_qubits(), 6);
+        assert_eq!(query.qubits(), &[1, 2, 3]);
+        // The boundary: last query qubit, then the first ancilla
+        assert_eq!((query[2], ancilla[0], ancilla[1]), (3, 4, 5));
+        assert_eq!(circuit.register("ancilla"), Some(&ancilla));
+        assert_eq!(circuit.registers().len(), 2);
+
+        let circuit = circuit.cx(query[2], ancilla[0]).measure_qubits(query.qubits());
+        assert_eq!(circuit.instructions()[0].qubits, vec![3, 4]);
+        assert_eq!(circuit.instructions()[1].qubits, vec![1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "register `query` already exists")]
+    fn test_register_names_are_unique() {
+        let mut circuit = Circuit::new(0);
+        circuit.add_register("query", 1);
+        circuit.add_register("query", 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "register name `Work` is not an OpenQASM identifier")]
+    fn test_register_name_must_be_identifier() {
+        Circuit::new(0).add_register("Work", 1);
+    }
 }
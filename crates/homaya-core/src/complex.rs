@@ -216,6 +216,91 @@ impl Neg for Complex {
     }
 }
 
+/// A complex number with 32-bit floating point components.
+///
+/// Half the size of [`Complex`], for state vectors where memory matters more
+/// than precision. Arithmetic carries about 7 significant digits instead of
+/// 16, so rounding error accumulates roughly 10⁸ times faster per gate.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Complex32 {
+    /// Real component
+    pub re: f32,
+    /// Imaginary component
+    pub im: f32,
+}
+
+impl Complex32 {
+    /// Zero: 0 + 0i
+    pub const ZERO: Self = Self { re: 0.0, im: 0.0 };
+
+    /// One: 1 + 0i
+    pub const ONE: Self = Self { re: 1.0, im: 0.0 };
+
+    /// Create a new complex number.
+    #[inline(always)]
+    pub const fn new(re: f32, im: f32) -> Self {
+        Self { re, im }
+    }
+
+    /// Compute |z|² = re² + im².
+    #[inline(always)]
+    pub fn norm_sqr(self) -> f32 {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl From<Complex> for Complex32 {
+    /// Round to single precision.
+    #[inline(always)]
+    fn from(c: Complex) -> Self {
+        Self::new(c.re as f32, c.im as f32)
+    }
+}
+
+impl From<Complex32> for Complex {
+    #[inline(always)]
+    fn from(c: Complex32) -> Self {
+        Self::new(c.re as f64, c.im as f64)
+    }
+}
+
+impl Add for Complex32 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            re: self.re + rhs.re,
+            im: self.im + rhs.im,
+        }
+    }
+}
+
+impl Mul for Complex32 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            re: self.re * rhs.re - self.im * rhs.im,
+            im: self.re * rhs.im + self.im * rhs.re,
+        }
+    }
+}
+
+impl Mul<f32> for Complex32 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: f32) -> Self {
+        Self {
+            re: self.re * rhs,
+            im: self.im * rhs,
+        }
+    }
+}
+
 #[cfg(feature = "serde")]
 impl serde::Serialize for Complex {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
@@ -235,6 +320,15 @@ impl<'de> serde::Deserialize<'de> for Complex {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_complex32_round_trip() {
+        let c = Complex::from_polar(1.0, 0.3);
+        let single = Complex32::from(c);
+        assert!(Complex::from(single).approx_eq(c, 1e-7));
+        assert!(Complex::from(single * single).approx_eq(c * c, 1e-6));
+        assert!((single.norm_sqr() - 1.0).abs() < 1e-6);
+    }
+
     #[test]
     fn test_basic_operations() {
         let a = Complex::new(1.0, 2.0);
@@ -255,8 +349,8 @@ mod tests {
     fn test_polar() {
         use crate::PI;
         let c = Complex::from_polar(1.0, PI / 4.0);
-        assert!((c.re - 0.7071067811865476).abs() < 1e-10);
-        assert!((c.im - 0.7071067811865476).abs() < 1e-10);
+        assert!((c.re - crate::INV_SQRT_2).abs() < 1e-10);
+        assert!((c.im - crate::INV_SQRT_2).abs() < 1e-10);
     }
 
     #[test]
@@ -0,0 +1,128 @@
+//! Classical control flow within a circuit.
+//!
+//! A classically conditioned instruction only applies when an earlier
+//! measurement wrote 1 to its classical bit, so a circuit containing one
+//! branches. [`ControlFlowGraph`] records those branches: which
+//! instructions are conditional, on which classical bit, and which
+//! measurement produced that bit.
+
+use std::collections::BTreeSet;
+
+use crate::{Circuit, GateType};
+
+/// A classically conditioned instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConditionalOp {
+    /// Index of the conditioned instruction in the circuit
+    pub instruction: usize,
+    /// Classical bit that must read 1 for it to apply
+    pub clbit: usize,
+    /// Index of the last measurement writing that bit beforehand, if any
+    pub source: Option<usize>,
+}
+
+/// The classical branching structure of a circuit.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_core::Circuit;
+///
+/// let flow = Circuit::new(2).h(0).cx(0, 1).measure_all().control_flow();
+/// assert!(!flow.is_branching());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ControlFlowGraph {
+    /// Conditional instructions in circuit order
+    conditionals: Vec<ConditionalOp>,
+}
+
+impl ControlFlowGraph {
+    /// Build the control-flow graph of a circuit.
+    pub fn new(circuit: &Circuit) -> Self {
+        let mut last_write: Vec<Option<usize>> = vec![None; circuit.num_clbits()];
+        let mut conditionals = Vec::new();
+
+        for (index, inst) in circuit.instructions().iter().enumerate() {
+            if let Some(clbit) = inst.condition {
+                conditionals.push(ConditionalOp {
+                    instruction: index,
+                    clbit,
+                    source: last_write.get(clbit).copied().flatten(),
+                });
+            }
+
+            if inst.gate.gate_type == GateType::Measure {
+                for &clbit in &inst.clbits {
+                    if clbit >= last_write.len() {
+                        last_write.resize(clbit + 1, None);
+                    }
+                    last_write[clbit] = Some(index);
+                }
+            }
+        }
+
+        Self { conditionals }
+    }
+
+    /// Conditional instructions, in circuit order.
+    #[inline]
+    pub fn conditionals(&self) -> &[ConditionalOp] {
+        &self.conditionals
+    }
+
+    /// Check whether the circuit branches on any classical bit.
+    #[inline]
+    pub fn is_branching(&self) -> bool {
+        !self.conditionals.is_empty()
+    }
+
+    /// Classical bits that some instruction is conditioned on.
+    pub fn controlling_clbits(&self) -> BTreeSet<usize> {
+        self.conditionals.iter().map(|op| op.clbit).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Gate, Instruction};
+
+    /// Teleport qubit 0 onto qubit 2 with classically controlled corrections.
+    fn teleportation() -> Circuit {
+        let mut circuit = Circuit::new(3)
+            .ry(1.0, 0)
+            .h(1)
+            .cx(1, 2)
+            .cx(0, 1)
+            .h(0)
+            .measure(0, 0)
+            .measure(1, 1);
+        circuit.push(Instruction::new(Gate::x(), vec![2]).conditioned_on(1));
+        circuit.push(Instruction::new(Gate::z(), vec![2]).conditioned_on(0));
+        circuit
+    }
+
+    #[test]
+    fn test_teleportation_corrections() {
+        let flow = teleportation().control_flow();
+        assert!(flow.is_branching());
+
+        // X correction on c[1] (measured at 6), Z correction on c[0] (measured at 5)
+        assert_eq!(
+            flow.conditionals(),
+            &[
+                ConditionalOp { instruction: 7, clbit: 1, source: Some(6) },
+                ConditionalOp { instruction: 8, clbit: 0, source: Some(5) },
+            ]
+        );
+        assert_eq!(flow.controlling_clbits(), BTreeSet::from([0, 1]));
+    }
+
+    #[test]
+    fn test_condition_without_measurement() {
+        let mut circuit = Circuit::with_clbits(1, 1);
+        circuit.push(Instruction::new(Gate::x(), vec![0]).conditioned_on(0));
+        assert_eq!(circuit.control_flow().conditionals()[0].source, None);
+    }
+}
@@ -0,0 +1,389 @@
+//! ASCII and SVG circuit diagrams.
+//!
+//! One wire per qubit, time flowing left to right. Each instruction sits in
+//! the earliest column where every wire it spans is free, so independent
+//! gates share a column; both renderers use the same columns. Multi-qubit
+//! gates draw a vertical connector through the rows in between, crossing
+//! uninvolved wires (`┼` in text).
+
+use std::fmt::Write;
+
+use crate::{Circuit, GateParams, GateType, Instruction};
+
+/// Render `circuit` as a text diagram.
+pub(crate) fn draw(circuit: &Circuit) -> String {
+    let n = circuit.num_qubits();
+    if n == 0 {
+        return String::new();
+    }
+
+    let columns = columns(circuit);
+    let prefix_width = format!("q{}: ", n - 1).chars().count();
+    let mut wires: Vec<String> = (0..n).map(|q| format!("{:<width$}", format!("q{}: ", q), width = prefix_width)).collect();
+    let mut gaps: Vec<String> = vec![" ".repeat(prefix_width); n - 1];
+
+    for column in &columns {
+        // Label per wire and which gaps a vertical connector crosses
+        let mut labels: Vec<Option<String>> = vec![None; n];
+        let mut connected = vec![false; n - 1];
+        for inst in column {
+            let (lo, hi) = span(inst).expect("only spanning instructions are placed");
+            for (q, slot) in labels.iter_mut().enumerate().take(hi + 1).skip(lo) {
+                *slot = Some(label(inst, q));
+            }
+            connected[lo..hi].iter_mut().for_each(|gap| *gap = true);
+        }
+
+        let width = labels.iter().flatten().map(|l| l.chars().count()).max().unwrap_or(1);
+        for (wire, label) in wires.iter_mut().zip(&labels) {
+            wire.push('─');
+            wire.push_str(&center(label.as_deref().unwrap_or(""), width, '─'));
+            wire.push('─');
+        }
+        for (gap, &crossed) in gaps.iter_mut().zip(&connected) {
+            gap.push(' ');
+            gap.push_str(&center(if crossed { "│" } else { "" }, width, ' '));
+            gap.push(' ');
+        }
+    }
+
+    let mut out = String::new();
+    for (q, wire) in wires.iter().enumerate() {
+        out.push_str(wire);
+        out.push('─');
+        out.push('\n');
+        if let Some(gap) = gaps.get(q) {
+            out.push_str(gap.trim_end());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Assign every instruction to the earliest column free on its whole span.
+fn columns(circuit: &Circuit) -> Vec<Vec<&Instruction>> {
+    let mut free = vec![0usize; circuit.num_qubits()];
+    let mut columns: Vec<Vec<&Instruction>> = Vec::new();
+    for inst in circuit.instructions() {
+        let Some((lo, hi)) = span(inst) else { continue };
+        let column = free[lo..=hi].iter().copied().max().unwrap_or(0);
+        free[lo..=hi].iter_mut().for_each(|slot| *slot = column + 1);
+        if column == columns.len() {
+            columns.push(Vec::new());
+        }
+        columns[column].push(inst);
+    }
+    columns
+}
+
+/// Left edge of the first column, leaving room for the wire labels.
+const SVG_LEFT: f64 = 50.0;
+/// Y coordinate of wire 0.
+const SVG_TOP: f64 = 30.0;
+/// Vertical distance between wires.
+const SVG_ROW: f64 = 50.0;
+/// Height of a gate box, and its minimum width.
+const SVG_BOX: f64 = 30.0;
+/// Horizontal space between neighbouring boxes.
+const SVG_GAP: f64 = 20.0;
+
+/// Render `circuit` as a self-contained SVG image.
+pub(crate) fn svg(circuit: &Circuit) -> String {
+    let n = circuit.num_qubits();
+    let columns = columns(circuit);
+    let y = |q: usize| SVG_TOP + q as f64 * SVG_ROW;
+
+    // Each column is as wide as its widest box
+    let widths: Vec<f64> = columns
+        .iter()
+        .map(|column| {
+            let widest = column
+                .iter()
+                .flat_map(|inst| inst.qubits.iter().map(move |&q| symbol(inst, q)))
+                .map(|text| box_width(&text))
+                .fold(SVG_BOX, f64::max);
+            widest + SVG_GAP
+        })
+        .collect();
+    let width = SVG_LEFT + widths.iter().sum::<f64>() + SVG_GAP;
+    let height = 2.0 * SVG_TOP + n.saturating_sub(1) as f64 * SVG_ROW;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="14">"#,
+        w = width,
+        h = height
+    );
+    let _ = writeln!(out, r#"<rect width="100%" height="100%" fill="white"/>"#);
+    for q in 0..n {
+        let _ = writeln!(out, r#"<text x="10" y="{}" dominant-baseline="middle">q{}</text>"#, y(q), q);
+        let _ = writeln!(
+            out,
+            r#"<line class="wire" x1="{}" y1="{y}" x2="{}" y2="{y}" stroke="black"/>"#,
+            SVG_LEFT - 10.0,
+            width - 10.0,
+            y = y(q)
+        );
+    }
+
+    let mut left = SVG_LEFT;
+    for (column, column_width) in columns.iter().zip(&widths) {
+        let x = left + column_width / 2.0;
+        for inst in column {
+            draw_svg_instruction(&mut out, inst, x, y);
+        }
+        left += column_width;
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Append the shapes for one instruction centered on column `x`.
+fn draw_svg_instruction(out: &mut String, inst: &Instruction, x: f64, y: impl Fn(usize) -> f64) {
+    use GateType::*;
+
+    let (lo, hi) = span(inst).expect("only spanning instructions are placed");
+    let half = SVG_BOX / 2.0;
+
+    if inst.gate.gate_type == Barrier {
+        let _ = writeln!(
+            out,
+            r#"<line class="barrier" x1="{x}" y1="{}" x2="{x}" y2="{}" stroke="gray" stroke-dasharray="4 3"/>"#,
+            y(lo) - half,
+            y(hi) + half
+        );
+        return;
+    }
+    if lo < hi {
+        let _ = writeln!(out, r#"<line class="connector" x1="{x}" y1="{}" x2="{x}" y2="{}" stroke="black"/>"#, y(lo), y(hi));
+    }
+
+    for &q in &inst.qubits {
+        let cy = y(q);
+        match symbol(inst, q).as_str() {
+            "●" => {
+                let _ = writeln!(out, r#"<circle class="control" cx="{x}" cy="{cy}" r="5" fill="black"/>"#);
+            }
+            "⊕" => {
+                let _ = writeln!(out, r#"<circle class="target" cx="{x}" cy="{cy}" r="10" fill="white" stroke="black"/>"#);
+                let _ = writeln!(
+                    out,
+                    r#"<path d="M {} {cy} H {} M {x} {} V {}" stroke="black"/>"#,
+                    x - 10.0,
+                    x + 10.0,
+                    cy - 10.0,
+                    cy + 10.0
+                );
+            }
+            "×" => {
+                let _ = writeln!(
+                    out,
+                    r#"<path class="swap" d="M {l} {t} L {r} {b} M {l} {b} L {r} {t}" stroke="black" stroke-width="2"/>"#,
+                    l = x - 6.0,
+                    r = x + 6.0,
+                    t = cy - 6.0,
+                    b = cy + 6.0
+                );
+            }
+            "M" => {
+                gate_box(out, x, cy, SVG_BOX);
+                // Meter: an arc with a needle
+                let _ = writeln!(
+                    out,
+                    r#"<path class="measure" d="M {} {b} A 9 9 0 0 1 {} {b} M {x} {b} L {} {}" fill="none" stroke="black"/>"#,
+                    x - 9.0,
+                    x + 9.0,
+                    x + 7.0,
+                    cy - 8.0,
+                    b = cy + 5.0
+                );
+            }
+            text => {
+                let width = box_width(text);
+                gate_box(out, x, cy, width);
+                let _ = writeln!(
+                    out,
+                    r#"<text x="{x}" y="{cy}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                    text
+                );
+            }
+        }
+    }
+
+    if let Some(clbit) = inst.condition {
+        let _ = writeln!(
+            out,
+            r#"<text class="condition" x="{x}" y="{}" text-anchor="middle" font-size="10">if c{}</text>"#,
+            y(hi) + half + 10.0,
+            clbit
+        );
+    }
+}
+
+/// A white gate box of `width` centered on (x, y).
+fn gate_box(out: &mut String, x: f64, y: f64, width: f64) {
+    let _ = writeln!(
+        out,
+        r#"<rect class="gate" x="{}" y="{}" width="{}" height="{}" fill="white" stroke="black"/>"#,
+        x - width / 2.0,
+        y - SVG_BOX / 2.0,
+        width,
+        SVG_BOX
+    );
+}
+
+/// Width of a box holding `text`, at roughly 8 px per character.
+fn box_width(text: &str) -> f64 {
+    (text.chars().count() as f64 * 8.0 + 12.0).max(SVG_BOX)
+}
+
+/// The lowest and highest qubit an instruction's column entry covers.
+fn span(inst: &Instruction) -> Option<(usize, usize)> {
+    Some((*inst.qubits.iter().min()?, *inst.qubits.iter().max()?))
+}
+
+/// What `inst` shows on wire `q`, which lies within its span.
+fn label(inst: &Instruction, q: usize) -> String {
+    if !inst.qubits.contains(&q) {
+        return "┼".to_string();
+    }
+    let text = symbol(inst, q);
+
+    match inst.condition {
+        Some(clbit) => format!("{} if c{}", text, clbit),
+        None => text,
+    }
+}
+
+/// The symbol for `inst` on one of its own qubits `q`, without condition.
+fn symbol(inst: &Instruction, q: usize) -> String {
+    use GateType::*;
+
+    let position = inst.qubits.iter().position(|&x| x == q).expect("q is one of the instruction's qubits");
+    let is_target = position == inst.qubits.len() - 1;
+
+    match inst.gate.gate_type {
+        CX | CY | CZ | CH | CP | CU | CCX if !is_target => "●".to_string(),
+        CSwap if position == 0 => "●".to_string(),
+        CX | CCX => "⊕".to_string(),
+        CZ => "●".to_string(),
+        Swap | CSwap => "×".to_string(),
+        ISwap => "iSWAP".to_string(),
+        SqrtSwap => "√SWAP".to_string(),
+        Measure => "M".to_string(),
+        Reset => "|0⟩".to_string(),
+        Barrier => "░".to_string(),
+        gate_type => format!("{}{}", name(gate_type), params(&inst.gate.params)),
+    }
+}
+
+/// Box label for a gate acting on a single wire.
+fn name(gate_type: GateType) -> &'static str {
+    use GateType::*;
+
+    match gate_type {
+        I => "I",
+        X => "X",
+        Y | CY => "Y",
+        Z => "Z",
+        H | CH => "H",
+        S => "S",
+        Sdg => "S†",
+        T => "T",
+        Tdg => "T†",
+        Rx => "Rx",
+        Ry => "Ry",
+        Rz => "Rz",
+        P | CP => "P",
+        U | CU => "U",
+        _ => "?",
+    }
+}
+
+/// Parameters rounded to two decimals, in parentheses.
+fn params(params: &GateParams) -> String {
+    match *params {
+        GateParams::None => String::new(),
+        GateParams::Angle(theta) => format!("({:.2})", theta),
+        GateParams::Angles3(theta, phi, lambda) => format!("({:.2},{:.2},{:.2})", theta, phi, lambda),
+    }
+}
+
+/// Center `text` in `width` characters, padding with `fill`.
+fn center(text: &str, width: usize, fill: char) -> String {
+    let len = text.chars().count();
+    let left = (width - len) / 2;
+    let right = width - len - left;
+    let fill = fill.to_string();
+    format!("{}{}{}", fill.repeat(left), text, fill.repeat(right))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Circuit, ConditionalGate};
+
+    #[test]
+    fn test_bell_state() {
+        let drawing = Circuit::new(2).h(0).cx(0, 1).measure_all().draw();
+        assert_eq!(
+            drawing,
+            "q0: ─H──●──M──\n\
+             \x20       │\n\
+             q1: ────⊕──M──\n"
+        );
+    }
+
+    #[test]
+    fn test_independent_gates_share_a_column() {
+        let drawing = Circuit::new(3).h(0).x(2).cx(0, 1).z(2).draw();
+        let lines: Vec<&str> = drawing.lines().collect();
+        assert_eq!(lines[0], "q0: ─H──●──");
+        assert_eq!(lines[4], "q2: ─X──Z──");
+    }
+
+    #[test]
+    fn test_span_crosses_uninvolved_wire() {
+        let drawing = Circuit::new(3).cx(2, 0).rx(0.5, 1).draw();
+        let lines: Vec<&str> = drawing.lines().collect();
+        assert_eq!(lines[0], "q0: ─⊕────────────");
+        assert_eq!(lines[2], "q1: ─┼──Rx(0.50)──");
+        assert_eq!(lines[4], "q2: ─●────────────");
+    }
+
+    #[test]
+    fn test_bell_svg() {
+        let svg = Circuit::new(2).h(0).cx(0, 1).measure_all().to_svg();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        // H and two measurement boxes; the CX target is a circle, not a box
+        assert_eq!(svg.matches("<rect class=\"gate\"").count(), 3);
+        assert_eq!(svg.matches("class=\"connector\"").count(), 1);
+        assert_eq!(svg.matches("class=\"control\"").count(), 1);
+        assert_eq!(svg.matches("class=\"target\"").count(), 1);
+        assert_eq!(svg.matches("class=\"measure\"").count(), 2);
+        assert_eq!(svg.matches("class=\"wire\"").count(), 2);
+    }
+
+    #[test]
+    fn test_svg_shares_columns_with_text() {
+        // H and X share the first column in both renderers
+        let circuit = Circuit::new(2).h(0).x(1).cx(0, 1);
+        let svg = circuit.to_svg();
+        let x_of = |label: &str| {
+            let end = svg.find(&format!(">{}</text>", label)).unwrap();
+            let start = svg[..end].rfind("x=\"").unwrap() + 3;
+            svg[start..].split('"').next().unwrap().to_string()
+        };
+        assert_eq!(x_of("H"), x_of("X"));
+    }
+
+    #[test]
+    fn test_svg_labels_parameters_and_conditions() {
+        let svg = Circuit::new(1).rx(0.5, 0).measure(0, 0).c_if(0, ConditionalGate::x(0)).to_svg();
+        assert!(svg.contains(">Rx(0.50)</text>"));
+        assert!(svg.contains(">if c0</text>"));
+    }
+}
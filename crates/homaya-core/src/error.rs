@@ -83,6 +83,14 @@ pub enum HomayaError {
         message: std::string::String,
     },
 
+    /// Malformed or unsupported input while parsing a circuit file.
+    ParseError {
+        /// 1-based line number
+        line: usize,
+        /// Error message
+        message: std::string::String,
+    },
+
     /// Backend error.
     BackendError {
         /// Backend name
@@ -128,6 +136,9 @@ impl fmt::Display for HomayaError {
             Self::SimulationError { message } => {
                 write!(f, "simulation error: {}", message)
             }
+            Self::ParseError { line, message } => {
+                write!(f, "line {}: {}", line, message)
+            }
             Self::BackendError { backend, message } => {
                 write!(f, "{} backend error: {}", backend, message)
             }
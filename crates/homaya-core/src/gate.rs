@@ -447,6 +447,49 @@ impl Gate {
         }
     }
 
+    /// Get the inverse (adjoint) of this gate.
+    ///
+    /// Self-inverse gates return themselves, S/T swap with their daggers and
+    /// rotation angles are negated. Returns `None` for non-unitary operations
+    /// (measure, reset, barrier), and for iSWAP and √SWAP, whose adjoints are
+    /// not single gates here; [`inverse_sequence`](Self::inverse_sequence)
+    /// covers those.
+    pub fn inverse(&self) -> Option<Self> {
+        use GateType::*;
+
+        let gate_type = match self.gate_type {
+            I | X | Y | Z | H | CX | CY | CZ | CH | Swap | CCX | CSwap => self.gate_type,
+            S => Sdg,
+            Sdg => S,
+            T => Tdg,
+            Tdg => T,
+            Rx | Ry | Rz | P | CP | U | CU => self.gate_type,
+            ISwap | SqrtSwap | Measure | Reset | Barrier => return None,
+        };
+
+        let params = match self.params {
+            GateParams::None => GateParams::None,
+            GateParams::Angle(theta) => GateParams::Angle(-theta),
+            // U(θ, φ, λ)† = U(-θ, -λ, -φ)
+            GateParams::Angles3(theta, phi, lambda) => GateParams::Angles3(-theta, -lambda, -phi),
+        };
+
+        Some(Self { gate_type, params })
+    }
+
+    /// Get the inverse as a sequence of gates on the same qubits.
+    ///
+    /// Gates with a single-gate [`inverse`](Self::inverse) return just that.
+    /// iSWAP and √SWAP both have order 4, so iSWAP† = iSWAP³ and
+    /// √SWAP† = √SWAP · SWAP. Returns `None` for non-unitary operations.
+    pub fn inverse_sequence(&self) -> Option<Vec<Self>> {
+        match self.gate_type {
+            GateType::ISwap => Some(vec![self.clone(); 3]),
+            GateType::SqrtSwap => Some(vec![Self::swap(), self.clone()]),
+            _ => self.inverse().map(|gate| vec![gate]),
+        }
+    }
+
     /// Returns the number of qubits this gate operates on.
     #[inline]
     pub const fn num_qubits(&self) -> usize {
@@ -503,6 +546,62 @@ mod tests {
         assert!(h_squared[1][1].approx_eq(Complex::ONE, 1e-10));
     }
 
+    #[test]
+    fn test_inverse_undoes_gate() {
+        for gate in [Gate::s(), Gate::t(), Gate::rx(0.3), Gate::ry(-1.2), Gate::p(0.7), Gate::u(0.4, 1.1, -0.5)] {
+            let m = gate.matrix_2x2().unwrap();
+            let inv = gate.inverse().unwrap().matrix_2x2().unwrap();
+
+            // inv · m = I
+            let product = [
+                [
+                    inv[0][0] * m[0][0] + inv[0][1] * m[1][0],
+                    inv[0][0] * m[0][1] + inv[0][1] * m[1][1],
+                ],
+                [
+                    inv[1][0] * m[0][0] + inv[1][1] * m[1][0],
+                    inv[1][0] * m[0][1] + inv[1][1] * m[1][1],
+                ],
+            ];
+
+            assert!(product[0][0].approx_eq(Complex::ONE, 1e-10), "{:?}", gate.gate_type);
+            assert!(product[0][1].approx_eq(Complex::ZERO, 1e-10), "{:?}", gate.gate_type);
+            assert!(product[1][0].approx_eq(Complex::ZERO, 1e-10), "{:?}", gate.gate_type);
+            assert!(product[1][1].approx_eq(Complex::ONE, 1e-10), "{:?}", gate.gate_type);
+        }
+
+        assert_eq!(Gate::measure().inverse(), None);
+    }
+
+    #[test]
+    fn test_every_unitary_gate_has_inverse_sequence() {
+        use GateType::*;
+
+        let gate = |gate_type| Gate {
+            gate_type,
+            params: GateParams::None,
+        };
+        for gate_type in [I, X, Y, Z, H, S, Sdg, T, Tdg, CX, CY, CZ, CH, Swap, ISwap, SqrtSwap, CCX, CSwap] {
+            assert!(gate(gate_type).inverse_sequence().is_some(), "{:?}", gate_type);
+        }
+        assert_eq!(gate(ISwap).inverse_sequence().unwrap(), vec![gate(ISwap); 3]);
+        assert_eq!(gate(SqrtSwap).inverse_sequence().unwrap(), vec![Gate::swap(), gate(SqrtSwap)]);
+        assert_eq!(Gate::rz(0.4).inverse_sequence().unwrap(), vec![Gate::rz(-0.4)]);
+        assert_eq!(Gate::reset().inverse_sequence(), None);
+    }
+
+    #[test]
+    fn test_phase_gate_special_angles() {
+        let close = |a: [[Complex; 2]; 2], b: [[Complex; 2]; 2]| {
+            a.iter().flatten().zip(b.iter().flatten()).all(|(x, y)| x.approx_eq(*y, 1e-12))
+        };
+
+        let p = |theta| Gate::p(theta).matrix_2x2().unwrap();
+        assert!(close(p(PI), Gate::z().matrix_2x2().unwrap()));
+        assert!(close(p(PI / 2.0), Gate::s().matrix_2x2().unwrap()));
+        assert!(close(p(PI / 4.0), Gate::t().matrix_2x2().unwrap()));
+    }
+
     #[test]
     fn test_pauli_anticommutation() {
         // XY = iZ, YX = -iZ → XY + YX = 0
@@ -0,0 +1,232 @@
+//! JSON circuit files.
+//!
+//! The schema is independent of the in-memory representation so that files
+//! stay readable across versions:
+//!
+//! ```json
+//! {"qubits": 2, "clbits": 2, "gates": [
+//!   {"op": "h", "targets": [0]},
+//!   {"op": "cx", "controls": [0], "targets": [1]},
+//!   {"op": "rz", "targets": [1], "params": [0.5]},
+//!   {"op": "measure", "targets": [0], "clbits": [0]},
+//!   {"op": "x", "targets": [1], "condition": 0}
+//! ]}
+//! ```
+//!
+//! Op names follow OpenQASM's `qelib1.inc`, as in [`Circuit::to_qasm`].
+//! `controls`, `params`, `clbits` and `condition` are omitted when empty.
+//! An optional top-level `name` holds the circuit name. Labeled blocks and
+//! named registers are not stored.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::qasm::qasm_name;
+use crate::{Circuit, Gate, GateParams, GateType, HomayaError, Instruction, Result};
+
+/// Every gate type, for looking names back up.
+const GATE_TYPES: [GateType; 28] = {
+    use GateType::*;
+    [
+        I, X, Y, Z, H, S, Sdg, T, Tdg, Rx, Ry, Rz, P, U, CX, CY, CZ, CH, CP, CU, Swap, ISwap, SqrtSwap, CCX, CSwap,
+        Measure, Reset, Barrier,
+    ]
+};
+
+/// Top-level JSON document.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CircuitRecord {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    qubits: usize,
+    clbits: usize,
+    gates: Vec<GateRecord>,
+}
+
+/// One instruction.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GateRecord {
+    #[serde(serialize_with = "serialize_op", deserialize_with = "deserialize_op")]
+    op: GateType,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    controls: Vec<usize>,
+    targets: Vec<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    params: Vec<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    clbits: Vec<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    condition: Option<usize>,
+}
+
+fn serialize_op<S: Serializer>(op: &GateType, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(qasm_name(*op))
+}
+
+fn deserialize_op<'de, D: Deserializer<'de>>(deserializer: D) -> core::result::Result<GateType, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    GATE_TYPES
+        .into_iter()
+        .find(|&gate_type| qasm_name(gate_type) == name)
+        .ok_or_else(|| serde::de::Error::custom(format!("unknown op `{}`", name)))
+}
+
+/// How many leading qubits of a gate are controls.
+fn control_count(gate_type: GateType) -> usize {
+    use GateType::*;
+
+    match gate_type {
+        CX | CY | CZ | CH | CP | CU | CSwap => 1,
+        CCX => 2,
+        _ => 0,
+    }
+}
+
+/// Serialize `circuit` to a single-line JSON document.
+pub(crate) fn to_json(circuit: &Circuit) -> String {
+    let gates = circuit
+        .instructions()
+        .iter()
+        .map(|inst| {
+            let controls = control_count(inst.gate.gate_type);
+            GateRecord {
+                op: inst.gate.gate_type,
+                controls: inst.qubits[..controls].to_vec(),
+                targets: inst.qubits[controls..].to_vec(),
+                params: match inst.gate.params {
+                    GateParams::None => Vec::new(),
+                    GateParams::Angle(theta) => vec![theta],
+                    GateParams::Angles3(theta, phi, lambda) => vec![theta, phi, lambda],
+                },
+                clbits: inst.clbits.clone(),
+                condition: inst.condition,
+            }
+        })
+        .collect();
+
+    let record = CircuitRecord {
+        name: circuit.name().map(String::from),
+        qubits: circuit.num_qubits(),
+        clbits: circuit.num_clbits(),
+        gates,
+    };
+    serde_json::to_string(&record).expect("circuit records always serialize")
+}
+
+/// Parse and validate a JSON document.
+pub(crate) fn from_json(source: &str) -> Result<Circuit> {
+    let record: CircuitRecord = serde_json::from_str(source).map_err(|e| HomayaError::ParseError {
+        line: e.line(),
+        message: e.to_string(),
+    })?;
+
+    let mut circuit = Circuit::with_clbits(record.qubits, record.clbits);
+    if let Some(name) = record.name {
+        circuit = circuit.named(name);
+    }
+
+    for gate in record.gates {
+        let gate_type = gate.op;
+        let name = qasm_name(gate_type);
+        let invalid = |message| HomayaError::InvalidGateParams { gate: name, message };
+
+        if gate.controls.len() != control_count(gate_type) {
+            return Err(invalid("wrong number of controls"));
+        }
+        let qubits: Vec<usize> = gate.controls.into_iter().chain(gate.targets).collect();
+        let arity = Gate { gate_type, params: GateParams::None }.num_qubits();
+        if gate_type != GateType::Barrier && qubits.len() != arity {
+            return Err(invalid("wrong number of qubits"));
+        }
+        for (i, &q) in qubits.iter().enumerate() {
+            if q >= record.qubits {
+                return Err(HomayaError::QubitOutOfRange { qubit: q, max: record.qubits });
+            }
+            if qubits[..i].contains(&q) {
+                return Err(HomayaError::DuplicateQubit { qubit: q });
+            }
+        }
+        if let Some(&c) = gate.clbits.iter().chain(&gate.condition).find(|&&c| c >= record.clbits) {
+            return Err(HomayaError::ClbitOutOfRange { clbit: c, max: record.clbits });
+        }
+
+        let params = match (gate_type, gate.params.as_slice()) {
+            (GateType::Rx | GateType::Ry | GateType::Rz | GateType::P | GateType::CP, &[theta]) => {
+                GateParams::Angle(theta)
+            }
+            (GateType::U | GateType::CU, &[theta, phi, lambda]) => GateParams::Angles3(theta, phi, lambda),
+            (GateType::Rx | GateType::Ry | GateType::Rz | GateType::P | GateType::CP | GateType::U | GateType::CU, _) => {
+                return Err(invalid("wrong number of parameters"))
+            }
+            (_, []) => GateParams::None,
+            (_, _) => return Err(invalid("takes no parameters")),
+        };
+
+        let mut inst = Instruction::with_clbits(Gate { gate_type, params }, qubits, gate.clbits);
+        inst.condition = gate.condition;
+        circuit.push(inst);
+    }
+
+    Ok(circuit)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Circuit, ConditionalGate, HomayaError};
+
+    #[test]
+    fn test_schema() {
+        let circuit = Circuit::new(2).h(0).cx(0, 1).rz(0.5, 1).measure(0, 0).c_if(0, ConditionalGate::x(1));
+        assert_eq!(
+            circuit.to_json(),
+            concat!(
+                r#"{"qubits":2,"clbits":1,"gates":["#,
+                r#"{"op":"h","targets":[0]},"#,
+                r#"{"op":"cx","controls":[0],"targets":[1]},"#,
+                r#"{"op":"rz","targets":[1],"params":[0.5]},"#,
+                r#"{"op":"measure","targets":[0],"clbits":[0]},"#,
+                r#"{"op":"x","targets":[1],"condition":0}]}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_round_trip_every_gate_shape() {
+        let circuit = Circuit::with_clbits(3, 2)
+            .named("shapes")
+            .u(0.1, 0.2, 0.3, 0)
+            .ccx(0, 1, 2)
+            .cswap(2, 0, 1)
+            .cp(-1.25, 1, 0)
+            .barrier_all()
+            .reset(2)
+            .measure(1, 1);
+
+        let parsed = Circuit::from_json(&circuit.to_json()).unwrap();
+        assert_eq!(parsed.instructions(), circuit.instructions());
+        assert_eq!(parsed.num_clbits(), 2);
+        assert_eq!(parsed.name(), Some("shapes"));
+    }
+
+    #[test]
+    fn test_invalid_documents() {
+        let err = Circuit::from_json("{\"qubits\": 1, \"clbits\": 0,\n\"gates\": [{\"op\": \"sx\", \"targets\": [0]}]}");
+        assert!(matches!(err, Err(HomayaError::ParseError { line: 2, ref message }) if message.contains("unknown op `sx`")));
+
+        let out_of_range = r#"{"qubits": 2, "clbits": 0, "gates": [{"op": "cx", "controls": [0], "targets": [2]}]}"#;
+        assert_eq!(
+            Circuit::from_json(out_of_range).unwrap_err(),
+            HomayaError::QubitOutOfRange { qubit: 2, max: 2 }
+        );
+
+        let no_control = r#"{"qubits": 2, "clbits": 0, "gates": [{"op": "cx", "targets": [0, 1]}]}"#;
+        assert!(matches!(
+            Circuit::from_json(no_control),
+            Err(HomayaError::InvalidGateParams { gate: "cx", .. })
+        ));
+
+        let no_angle = r#"{"qubits": 1, "clbits": 0, "gates": [{"op": "rx", "targets": [0]}]}"#;
+        assert!(Circuit::from_json(no_angle).is_err());
+    }
+}
@@ -3,9 +3,13 @@
 //! Ultra-fast quantum computing primitives.
 //!
 //! This crate provides the fundamental building blocks for quantum computation:
-//! - [`Complex`] - High-performance complex number operations
+//! - [`Complex`] - High-performance complex number operations ([`Complex32`] for half the memory)
 //! - [`Gate`] - Quantum gate definitions
 //! - [`Circuit`] - Quantum circuit builder
+//! - [`BasisSet`] - Native gate sets for cost estimation
+//! - [`ControlFlowGraph`] - Classical branching inside a circuit
+//! - [`format_outcome`] - The canonical bitstring format (bit 0 rightmost), see [`BitOrder`] for the alternative
+//! - [`states`] - Bell and GHZ state preparations
 //!
 //! ## Philosophy
 //!
@@ -29,11 +33,28 @@ mod complex;
 mod gate;
 mod circuit;
 mod error;
+mod basis;
+mod bitstring;
+mod control_flow;
+mod draw;
+#[cfg(feature = "serde")]
+mod json;
+mod lint;
+mod optimize;
+mod qasm;
+mod register;
+pub mod states;
 
-pub use complex::Complex;
+pub use complex::{Complex, Complex32};
 pub use gate::{Gate, GateType, GateParams};
-pub use circuit::{Circuit, Instruction};
+pub use circuit::{Circuit, ConditionalGate, Instruction};
 pub use error::HomayaError;
+pub use basis::BasisSet;
+pub use bitstring::{format_outcome, most_likely_outcome, parse_outcome, BitOrder};
+pub use control_flow::{ConditionalOp, ControlFlowGraph};
+pub use lint::LintWarning;
+pub use register::QubitRegister;
+pub use states::BellState;
 
 /// Result type for HOMAYA operations
 pub type Result<T> = core::result::Result<T, HomayaError>;
@@ -48,7 +69,7 @@ pub const TAU: f64 = core::f64::consts::TAU;
 pub const SQRT_2: f64 = core::f64::consts::SQRT_2;
 
 /// 1/√2, used frequently in quantum gates
-pub const INV_SQRT_2: f64 = 0.7071067811865476;
+pub const INV_SQRT_2: f64 = core::f64::consts::FRAC_1_SQRT_2;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,211 @@
+//! Lint pass flagging suspicious circuit patterns.
+//!
+//! None of these make a circuit invalid, but each is more often a mistake
+//! than intended, especially while learning.
+
+use core::fmt;
+
+use crate::{Circuit, GateType, Instruction};
+
+/// A likely mistake found by [`Circuit::lint`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum LintWarning {
+    /// A gate acts on a qubit after it was measured, with no reset between.
+    GateAfterMeasure {
+        /// The measured qubit
+        qubit: usize,
+        /// Index of the offending instruction
+        index: usize,
+    },
+    /// A measurement overwrites a classical bit nothing has read yet.
+    OverwrittenClbit {
+        /// The classical bit written twice
+        clbit: usize,
+        /// Index of the second measurement
+        index: usize,
+    },
+    /// No instruction touches this qubit.
+    UnusedQubit {
+        /// The idle qubit
+        qubit: usize,
+    },
+    /// A labeled block (e.g. an oracle) reduces to the identity.
+    IdentityBlock {
+        /// The block label
+        label: String,
+        /// Index of the block's first instruction
+        start: usize,
+    },
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GateAfterMeasure { qubit, index } => write!(
+                f,
+                "instruction {} acts on qubit {} after it was measured without a reset",
+                index, qubit
+            ),
+            Self::OverwrittenClbit { clbit, index } => write!(
+                f,
+                "instruction {} overwrites classical bit {} before anything reads it",
+                index, clbit
+            ),
+            Self::UnusedQubit { qubit } => write!(f, "qubit {} is never used", qubit),
+            Self::IdentityBlock { label, start } => write!(
+                f,
+                "block '{}' at instruction {} does nothing: its gates cancel to the identity",
+                label, start
+            ),
+        }
+    }
+}
+
+/// Run every check over `circuit`.
+pub(crate) fn lint(circuit: &Circuit) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    // Gates after measurement, and which qubits are touched at all
+    let mut measured = vec![false; circuit.num_qubits()];
+    let mut used = vec![false; circuit.num_qubits()];
+    for (index, inst) in circuit.instructions().iter().enumerate() {
+        let gate_type = inst.gate.gate_type;
+        if gate_type == GateType::Barrier {
+            continue;
+        }
+        for &q in &inst.qubits {
+            used[q] = true;
+            match gate_type {
+                GateType::Measure => measured[q] = true,
+                GateType::Reset => measured[q] = false,
+                _ if measured[q] => {
+                    warnings.push(LintWarning::GateAfterMeasure { qubit: q, index });
+                    measured[q] = false;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Classical bits written and not yet read by a condition
+    let mut unread = std::collections::BTreeSet::new();
+    for (index, inst) in circuit.instructions().iter().enumerate() {
+        if let Some(c) = inst.condition {
+            unread.remove(&c);
+        }
+        if inst.gate.gate_type == GateType::Measure {
+            for &c in &inst.clbits {
+                if !unread.insert(c) {
+                    warnings.push(LintWarning::OverwrittenClbit { clbit: c, index });
+                }
+            }
+        }
+    }
+
+    warnings.extend(
+        used.iter()
+            .enumerate()
+            .filter(|(_, &used)| !used)
+            .map(|(qubit, _)| LintWarning::UnusedQubit { qubit }),
+    );
+
+    warnings.extend(
+        circuit
+            .block_spans()
+            .filter(|(_, _, instructions)| cancels_to_identity(instructions))
+            .map(|(label, start, _)| LintWarning::IdentityBlock {
+                label: label.to_string(),
+                start,
+            }),
+    );
+
+    warnings
+}
+
+/// True if adjacent inverse pairs cancel all of `instructions`.
+///
+/// Only catches direct cancellation such as X·X or S·S†, not identities
+/// that need gate algebra to spot.
+fn cancels_to_identity(instructions: &[Instruction]) -> bool {
+    let mut stack: Vec<&Instruction> = Vec::new();
+    for inst in instructions {
+        match inst.gate.gate_type {
+            GateType::I | GateType::Barrier => continue,
+            GateType::Measure | GateType::Reset => return false,
+            _ => {}
+        }
+        let cancels = stack.last().is_some_and(|top| {
+            top.qubits == inst.qubits
+                && top.condition.is_none()
+                && inst.condition.is_none()
+                && top.gate.inverse().as_ref() == Some(&inst.gate)
+        });
+        if cancels {
+            stack.pop();
+        } else {
+            stack.push(inst);
+        }
+    }
+    stack.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_circuit() {
+        assert!(Circuit::new(2).h(0).cx(0, 1).measure_all().lint().is_empty());
+    }
+
+    #[test]
+    fn test_gate_after_measure() {
+        let warnings = Circuit::new(1).h(0).measure(0, 0).x(0).lint();
+        assert_eq!(warnings, vec![LintWarning::GateAfterMeasure { qubit: 0, index: 2 }]);
+
+        // A reset in between is the intended pattern
+        assert!(Circuit::new(1).h(0).measure_reset(0, 0).x(0).lint().is_empty());
+    }
+
+    #[test]
+    fn test_overwritten_clbit() {
+        let warnings = Circuit::new(2).measure(0, 0).measure(1, 0).lint();
+        assert_eq!(warnings, vec![LintWarning::OverwrittenClbit { clbit: 0, index: 1 }]);
+
+        // Reading the bit in between makes the second write deliberate
+        let reused = Circuit::new(2)
+            .measure(0, 0)
+            .c_if(0, crate::ConditionalGate::x(1))
+            .measure(1, 0);
+        assert!(reused.lint().is_empty());
+    }
+
+    #[test]
+    fn test_unused_qubit() {
+        let warnings = Circuit::new(3).h(0).barrier_all().x(2).lint();
+        assert_eq!(warnings, vec![LintWarning::UnusedQubit { qubit: 1 }]);
+    }
+
+    #[test]
+    fn test_identity_block() {
+        let identity = Circuit::new(2).x(1).h(0).h(0).x(1);
+        let real = Circuit::new(2).cz(0, 1);
+        let circuit = Circuit::new(2)
+            .compose_block("oracle", &identity)
+            .unwrap()
+            .compose_block("oracle", &real)
+            .unwrap()
+            .compose_block("noop", &Circuit::new(2).i(0))
+            .unwrap();
+
+        let warnings = circuit.lint();
+        assert_eq!(
+            warnings,
+            vec![
+                LintWarning::IdentityBlock { label: "oracle".to_string(), start: 0 },
+                LintWarning::IdentityBlock { label: "noop".to_string(), start: 5 },
+            ]
+        );
+        assert!(warnings[0].to_string().contains("'oracle'"));
+    }
+}
@@ -0,0 +1,59 @@
+//! Peephole optimization: cancel adjacent inverse gates.
+
+use crate::{Circuit, GateType, Instruction};
+
+/// Which instructions survive cancelling adjacent inverse pairs.
+///
+/// Each qubit keeps a stack of the surviving instructions on it. A gate
+/// cancels against an earlier one when that instruction is on top of the
+/// stack of every qubit the gate touches, uses the same qubits in the same
+/// order, and is its inverse. Popping exposes the gates before it, so
+/// cascades such as X·H·H·X disappear in a single pass. Measurements,
+/// resets, barriers and conditioned gates never cancel, so they block
+/// everything across them; a barrier on no qubits blocks every qubit.
+pub(crate) fn kept_instructions(circuit: &Circuit) -> Vec<bool> {
+    let instructions = circuit.instructions();
+    let mut kept = vec![true; instructions.len()];
+    let mut stacks: Vec<Vec<usize>> = vec![Vec::new(); circuit.num_qubits()];
+
+    for (index, inst) in instructions.iter().enumerate() {
+        if inst.qubits.is_empty() {
+            for stack in &mut stacks {
+                stack.push(index);
+            }
+            continue;
+        }
+
+        let top = stacks[inst.qubits[0]].last().copied();
+        let cancels = top.is_some_and(|j| {
+            inst.qubits.iter().all(|&q| stacks[q].last() == Some(&j))
+                && cancellable(inst)
+                && cancellable(&instructions[j])
+                && instructions[j].qubits == inst.qubits
+                && instructions[j].gate.inverse().as_ref() == Some(&inst.gate)
+        });
+
+        if cancels {
+            kept[top.expect("checked above")] = false;
+            kept[index] = false;
+            for &q in &inst.qubits {
+                stacks[q].pop();
+            }
+        } else {
+            for &q in &inst.qubits {
+                stacks[q].push(index);
+            }
+        }
+    }
+
+    kept
+}
+
+/// Whether `inst` may cancel against a neighbour at all.
+fn cancellable(inst: &Instruction) -> bool {
+    inst.condition.is_none()
+        && !matches!(
+            inst.gate.gate_type,
+            GateType::Measure | GateType::Reset | GateType::Barrier
+        )
+}
@@ -0,0 +1,656 @@
+//! OpenQASM 2.0 export and import.
+//!
+//! Emits programs against Qiskit's `qelib1.inc`, which provides `p`, `cp`
+//! and `u` on top of the original gate library. The parser reads back
+//! everything the exporter writes, plus the common `u1`/`u3`/`cu1` aliases.
+
+use std::fmt::Write;
+
+use crate::register::is_identifier;
+use crate::{Circuit, Gate, GateParams, GateType, HomayaError, Instruction, QubitRegister, Result};
+
+/// `iswap` is not in `qelib1.inc`; this is its standard decomposition.
+const ISWAP_DEF: &str = "gate iswap a,b { s a; s b; h a; cx a,b; cx b,a; h b; }";
+
+/// √SWAP = CX(a,b) · controlled-√X(b → a) · CX(a,b).
+const SQRT_SWAP_DEF: &str = "gate sqrt_swap a,b { cx a,b; csx b,a; cx a,b; }";
+
+/// Render `circuit` as an OpenQASM 2.0 program.
+pub(crate) fn to_qasm(circuit: &Circuit) -> String {
+    let instructions = circuit.instructions();
+    let uses = |gate_type| instructions.iter().any(|inst| inst.gate.gate_type == gate_type);
+
+    let mut out = String::from("OPENQASM 2.0;\ninclude \"qelib1.inc\";\n");
+    if uses(GateType::ISwap) {
+        out.push_str(ISWAP_DEF);
+        out.push('\n');
+    }
+    if uses(GateType::SqrtSwap) {
+        out.push_str(SQRT_SWAP_DEF);
+        out.push('\n');
+    }
+
+    let qubit = match named_qregs(circuit) {
+        Some(registers) => {
+            for reg in registers {
+                let _ = writeln!(out, "qreg {}[{}];", reg.name(), reg.len());
+            }
+            let labels: Vec<String> = registers
+                .iter()
+                .flat_map(|reg| (0..reg.len()).map(move |i| format!("{}[{}]", reg.name(), i)))
+                .collect();
+            labels
+        }
+        None => {
+            let _ = writeln!(out, "qreg q[{}];", circuit.num_qubits());
+            (0..circuit.num_qubits()).map(|q| format!("q[{}]", q)).collect()
+        }
+    };
+
+    // Size the classical register by the bits actually used
+    let clbits = instructions
+        .iter()
+        .flat_map(|inst| inst.clbits.iter().chain(&inst.condition))
+        .max()
+        .map_or(0, |&c| c + 1);
+
+    // OpenQASM 2.0 can only condition on a whole register, so conditioned
+    // circuits get one single-bit register per classical bit instead
+    let split = instructions.iter().any(Instruction::is_conditional);
+    let clbit = |c: usize| if split { format!("c{}[0]", c) } else { format!("c[{}]", c) };
+
+    if split {
+        for c in 0..clbits {
+            let _ = writeln!(out, "creg c{}[1];", c);
+        }
+    } else if clbits > 0 {
+        let _ = writeln!(out, "creg c[{}];", clbits);
+    }
+
+    for inst in instructions {
+        if let Some(c) = inst.condition {
+            let _ = write!(out, "if(c{}==1) ", c);
+        }
+
+        let qubits: Vec<&str> = inst.qubits.iter().map(|&q| qubit[q].as_str()).collect();
+        let qubits = qubits.join(",");
+
+        match inst.gate.gate_type {
+            GateType::Measure => {
+                let _ = writeln!(out, "measure {} -> {};", qubits, clbit(inst.clbits[0]));
+            }
+            gate_type => {
+                let params = match inst.gate.params {
+                    GateParams::None => String::new(),
+                    GateParams::Angle(theta) => format!("({})", theta),
+                    GateParams::Angles3(theta, phi, lambda) => format!("({},{},{})", theta, phi, lambda),
+                };
+                let _ = writeln!(out, "{}{} {};", qasm_name(gate_type), params, qubits);
+            }
+        }
+    }
+
+    out
+}
+
+/// The circuit's named registers, if they can stand in for `q`.
+///
+/// They must cover every qubit in order, and steer clear of the classical
+/// register names `c`, `c0`, `c1`, ...
+fn named_qregs(circuit: &Circuit) -> Option<&[QubitRegister]> {
+    let registers = circuit.registers();
+    let covered = registers.iter().try_fold(0, |next, reg| (reg.offset() == next).then(|| next + reg.len()));
+    let clashes = registers.iter().any(|reg| {
+        reg.name()
+            .strip_prefix('c')
+            .is_some_and(|rest| rest.chars().all(|c| c.is_ascii_digit()))
+    });
+    (!registers.is_empty() && covered == Some(circuit.num_qubits()) && !clashes).then_some(registers)
+}
+
+/// Parse an OpenQASM 2.0 program into a circuit.
+///
+/// Supports `qreg`/`creg` declarations (several of each), every gate
+/// [`to_qasm`] emits, `measure`, `reset`, `barrier`, register broadcast for
+/// single-qubit operations and `if(c==1)` on single-bit registers. Angles
+/// may be numbers or arithmetic on `pi`. Statements must end on the line
+/// they start.
+pub(crate) fn from_qasm(source: &str) -> Result<Circuit> {
+    let mut parser = QasmParser::default();
+
+    for (index, raw) in source.lines().enumerate() {
+        parser.line = index + 1;
+        let line = raw.split("//").next().unwrap_or("").trim();
+
+        // The only gate definitions accepted are the ones to_qasm writes
+        if line.starts_with("gate ") {
+            if line != ISWAP_DEF && line != SQRT_SWAP_DEF {
+                return Err(parser.error("custom gate definitions are not supported"));
+            }
+            continue;
+        }
+
+        let mut statements: Vec<&str> = line.split(';').collect();
+        let rest = statements.pop().unwrap_or("");
+        if !rest.trim().is_empty() {
+            return Err(parser.error(format!("expected ';' after `{}`", rest.trim())));
+        }
+        for statement in statements {
+            parser.statement(statement.trim())?;
+        }
+    }
+
+    // Keep register names unless there is nothing to keep beyond `q`
+    let lone_q = matches!(parser.qregs.as_slice(), [reg] if reg.name == "q");
+    let mut circuit = if lone_q || !parser.qregs.iter().all(|reg| is_identifier(&reg.name)) {
+        Circuit::with_clbits(parser.num_qubits, parser.num_clbits)
+    } else {
+        let mut circuit = Circuit::with_clbits(0, parser.num_clbits);
+        for reg in parser.qregs.iter().filter(|reg| reg.size > 0) {
+            circuit.add_register(reg.name.as_str(), reg.size);
+        }
+        circuit
+    };
+    for inst in parser.instructions {
+        circuit.push(inst);
+    }
+    Ok(circuit)
+}
+
+/// A declared quantum or classical register.
+struct Register {
+    name: String,
+    offset: usize,
+    size: usize,
+}
+
+/// Line-by-line OpenQASM 2.0 parser state.
+#[derive(Default)]
+struct QasmParser {
+    /// Current 1-based line, for error messages
+    line: usize,
+    qregs: Vec<Register>,
+    cregs: Vec<Register>,
+    num_qubits: usize,
+    num_clbits: usize,
+    instructions: Vec<Instruction>,
+}
+
+impl QasmParser {
+    fn error(&self, message: impl Into<String>) -> HomayaError {
+        HomayaError::ParseError {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    fn statement(&mut self, statement: &str) -> Result<()> {
+        if statement.is_empty() {
+            return Ok(());
+        }
+
+        let (keyword, rest) = split_keyword(statement);
+        match keyword {
+            "OPENQASM" if rest.starts_with('2') => Ok(()),
+            "OPENQASM" => Err(self.error(format!("unsupported OpenQASM version {}", rest))),
+            "include" => Ok(()),
+            "qreg" | "creg" => self.declare(keyword, rest),
+            _ if statement.starts_with("if(") || statement.starts_with("if (") => self.conditional(statement),
+            _ => {
+                let insts = self.operation(statement)?;
+                self.instructions.extend(insts);
+                Ok(())
+            }
+        }
+    }
+
+    /// `qreg name[size]` or `creg name[size]`.
+    fn declare(&mut self, keyword: &str, rest: &str) -> Result<()> {
+        let (name, size) = match parse_indexed(rest) {
+            Some((name, Some(size))) => (name, size),
+            _ => return Err(self.error(format!("malformed declaration `{} {}`", keyword, rest))),
+        };
+        if self.qregs.iter().chain(&self.cregs).any(|reg| reg.name == name) {
+            return Err(self.error(format!("register `{}` declared twice", name)));
+        }
+
+        let (registers, total) = if keyword == "qreg" {
+            (&mut self.qregs, &mut self.num_qubits)
+        } else {
+            (&mut self.cregs, &mut self.num_clbits)
+        };
+        registers.push(Register {
+            name: name.to_string(),
+            offset: *total,
+            size,
+        });
+        *total += size;
+        Ok(())
+    }
+
+    /// `if(creg==1) operation`, with `creg` a single-bit register.
+    fn conditional(&mut self, statement: &str) -> Result<()> {
+        let malformed = || self.error(format!("malformed condition `{}`", statement));
+        let body = statement["if".len()..].trim_start().strip_prefix('(').ok_or_else(malformed)?;
+        let close = body.find(')').ok_or_else(malformed)?;
+        let (test, operation) = (&body[..close], body[close + 1..].trim());
+        let (name, value) = test.split_once("==").ok_or_else(malformed)?;
+
+        let register = self.cregs.iter().find(|reg| reg.name == name.trim());
+        let clbit = match (register, value.trim()) {
+            (Some(reg), "1") if reg.size == 1 => reg.offset,
+            _ => {
+                return Err(self.error(format!(
+                    "only conditions of the form if(c==1) on a single-bit register are supported, got `{}`",
+                    test
+                )))
+            }
+        };
+
+        for inst in self.operation(operation)? {
+            self.instructions.push(inst.conditioned_on(clbit));
+        }
+        Ok(())
+    }
+
+    /// A gate application, `measure`, `reset` or `barrier`.
+    fn operation(&self, statement: &str) -> Result<Vec<Instruction>> {
+        let (head, operands) = split_operation(statement);
+
+        if head == "measure" {
+            let (qubits, clbits) = operands
+                .split_once("->")
+                .ok_or_else(|| self.error("expected `measure q[i] -> c[j]`"))?;
+            let qubits = self.operand(qubits, &self.qregs)?;
+            let clbits = self.operand(clbits, &self.cregs)?;
+            if qubits.len() != clbits.len() {
+                return Err(self.error("measure needs registers of the same size"));
+            }
+            return Ok(qubits
+                .into_iter()
+                .zip(clbits)
+                .map(|(q, c)| Instruction::with_clbits(Gate::measure(), vec![q], vec![c]))
+                .collect());
+        }
+
+        let (name, params) = match head.split_once('(') {
+            Some((name, params)) => {
+                let params = params
+                    .strip_suffix(')')
+                    .ok_or_else(|| self.error(format!("unclosed parameter list in `{}`", head)))?;
+                let values = params
+                    .split(',')
+                    .map(|expr| eval_angle(expr).ok_or_else(|| self.error(format!("cannot evaluate angle `{}`", expr.trim()))))
+                    .collect::<Result<Vec<f64>>>()?;
+                (name.trim(), values)
+            }
+            None => (head, Vec::new()),
+        };
+
+        let gate_type = gate_type_from_name(name).ok_or_else(|| self.error(format!("unsupported gate `{}`", name)))?;
+        let params = match (gate_type, params.as_slice()) {
+            (GateType::Rx | GateType::Ry | GateType::Rz | GateType::P | GateType::CP, &[theta]) => GateParams::Angle(theta),
+            (GateType::U | GateType::CU, &[theta, phi, lambda]) => GateParams::Angles3(theta, phi, lambda),
+            (GateType::Rx | GateType::Ry | GateType::Rz | GateType::P | GateType::CP | GateType::U | GateType::CU, _) => {
+                return Err(self.error(format!("wrong number of parameters for `{}`", name)))
+            }
+            (_, []) => GateParams::None,
+            (_, _) => return Err(self.error(format!("`{}` takes no parameters", name))),
+        };
+        let gate = Gate { gate_type, params };
+
+        let operands: Vec<Vec<usize>> = operands
+            .split(',')
+            .map(|operand| self.operand(operand, &self.qregs))
+            .collect::<Result<_>>()?;
+
+        if gate_type == GateType::Barrier {
+            // `barrier q, q[0];` names q[0] twice; it is still one barrier
+            let mut qubits = operands.concat();
+            let mut seen = std::collections::HashSet::new();
+            qubits.retain(|&q| seen.insert(q));
+            return Ok(vec![Instruction::new(gate, qubits)]);
+        }
+        if operands.len() != gate.num_qubits() {
+            return Err(self.error(format!(
+                "`{}` acts on {} qubit(s), got {}",
+                name,
+                gate.num_qubits(),
+                operands.len()
+            )));
+        }
+
+        // Whole-register operands broadcast, single-qubit operations only
+        match operands.as_slice() {
+            [qubits] => Ok(qubits.iter().map(|&q| Instruction::new(gate.clone(), vec![q])).collect()),
+            _ if operands.iter().all(|qubits| qubits.len() == 1) => {
+                let qubits = operands.concat();
+                if (1..qubits.len()).any(|i| qubits[..i].contains(&qubits[i])) {
+                    return Err(self.error(format!("`{}` acts on the same qubit more than once", name)));
+                }
+                Ok(vec![Instruction::new(gate, qubits)])
+            }
+            _ => Err(self.error(format!("register broadcast is only supported for single-qubit gates, not `{}`", name))),
+        }
+    }
+
+    /// Resolve `name[i]` to one index or `name` to the whole register.
+    fn operand(&self, operand: &str, registers: &[Register]) -> Result<Vec<usize>> {
+        let (name, index) = parse_indexed(operand).ok_or_else(|| self.error(format!("malformed operand `{}`", operand.trim())))?;
+        let register = registers
+            .iter()
+            .find(|reg| reg.name == name)
+            .ok_or_else(|| self.error(format!("unknown register `{}`", name)))?;
+
+        match index {
+            Some(i) if i < register.size => Ok(vec![register.offset + i]),
+            Some(i) => Err(self.error(format!("index {} out of range for `{}[{}]`", i, name, register.size))),
+            None => Ok((register.offset..register.offset + register.size).collect()),
+        }
+    }
+}
+
+/// Split off the first whitespace-delimited word.
+fn split_keyword(statement: &str) -> (&str, &str) {
+    match statement.split_once(char::is_whitespace) {
+        Some((keyword, rest)) => (keyword, rest.trim()),
+        None => (statement, ""),
+    }
+}
+
+/// Split `name(params) operands` after the closing parenthesis, if any.
+fn split_operation(statement: &str) -> (&str, &str) {
+    let split = match (statement.find('('), statement.find(char::is_whitespace)) {
+        (Some(open), Some(space)) if open < space => matching_paren(statement, open).map(|close| close + 1),
+        (_, space) => space,
+    };
+    match split {
+        Some(at) => (statement[..at].trim(), statement[at..].trim()),
+        None => (statement, ""),
+    }
+}
+
+/// Byte index of the parenthesis closing the one at `open`.
+fn matching_paren(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in text.char_indices().skip_while(|&(i, _)| i < open) {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 1 => return Some(i),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse `name[index]` or a bare `name`.
+fn parse_indexed(text: &str) -> Option<(&str, Option<usize>)> {
+    let text = text.trim();
+    match text.split_once('[') {
+        Some((name, index)) => {
+            let index = index.strip_suffix(']')?.trim().parse().ok()?;
+            Some((name.trim(), Some(index)))
+        }
+        None if !text.is_empty() && text.chars().all(|c| c.is_alphanumeric() || c == '_') => Some((text, None)),
+        None => None,
+    }
+}
+
+/// The gate type for a `qelib1.inc` name, including common aliases.
+fn gate_type_from_name(name: &str) -> Option<GateType> {
+    use GateType::*;
+
+    let aliases = [("u1", P), ("u3", U), ("cu1", CP)];
+    if let Some(&(_, gate_type)) = aliases.iter().find(|(alias, _)| *alias == name) {
+        return Some(gate_type);
+    }
+
+    [
+        I, X, Y, Z, H, S, Sdg, T, Tdg, Rx, Ry, Rz, P, U, CX, CY, CZ, CH, CP, CU, Swap, ISwap, SqrtSwap, CCX, CSwap,
+        Reset, Barrier,
+    ]
+    .into_iter()
+    .find(|&gate_type| qasm_name(gate_type) == name)
+}
+
+/// Evaluate an angle expression: numbers, `pi`, `+ - * /` and parentheses.
+fn eval_angle(expr: &str) -> Option<f64> {
+    let tokens: Vec<char> = expr.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut pos = 0;
+    let value = eval_sum(&tokens, &mut pos)?;
+    (pos == tokens.len()).then_some(value)
+}
+
+fn eval_sum(tokens: &[char], pos: &mut usize) -> Option<f64> {
+    let mut value = eval_product(tokens, pos)?;
+    while let Some(&op) = tokens.get(*pos).filter(|&&c| c == '+' || c == '-') {
+        *pos += 1;
+        let rhs = eval_product(tokens, pos)?;
+        value = if op == '+' { value + rhs } else { value - rhs };
+    }
+    Some(value)
+}
+
+fn eval_product(tokens: &[char], pos: &mut usize) -> Option<f64> {
+    let mut value = eval_factor(tokens, pos)?;
+    while let Some(&op) = tokens.get(*pos).filter(|&&c| c == '*' || c == '/') {
+        *pos += 1;
+        let rhs = eval_factor(tokens, pos)?;
+        value = if op == '*' { value * rhs } else { value / rhs };
+    }
+    Some(value)
+}
+
+fn eval_factor(tokens: &[char], pos: &mut usize) -> Option<f64> {
+    match tokens.get(*pos)? {
+        '-' => {
+            *pos += 1;
+            Some(-eval_factor(tokens, pos)?)
+        }
+        '(' => {
+            *pos += 1;
+            let value = eval_sum(tokens, pos)?;
+            (tokens.get(*pos) == Some(&')')).then(|| *pos += 1)?;
+            Some(value)
+        }
+        'p' => {
+            (tokens.get(*pos + 1) == Some(&'i')).then(|| *pos += 2)?;
+            Some(crate::PI)
+        }
+        _ => {
+            let start = *pos;
+            while tokens
+                .get(*pos)
+                .is_some_and(|&c| c.is_ascii_digit() || c == '.' || c == 'e' || ((c == '-' || c == '+') && *pos > start && tokens[*pos - 1] == 'e'))
+            {
+                *pos += 1;
+            }
+            tokens[start..*pos].iter().collect::<String>().parse().ok()
+        }
+    }
+}
+
+/// The `qelib1.inc` name of a gate.
+pub(crate) fn qasm_name(gate_type: GateType) -> &'static str {
+    use GateType::*;
+
+    match gate_type {
+        I => "id",
+        X => "x",
+        Y => "y",
+        Z => "z",
+        H => "h",
+        S => "s",
+        Sdg => "sdg",
+        T => "t",
+        Tdg => "tdg",
+        Rx => "rx",
+        Ry => "ry",
+        Rz => "rz",
+        P => "p",
+        U => "u",
+        CX => "cx",
+        CY => "cy",
+        CZ => "cz",
+        CH => "ch",
+        CP => "cp",
+        CU => "cu3",
+        Swap => "swap",
+        ISwap => "iswap",
+        SqrtSwap => "sqrt_swap",
+        CCX => "ccx",
+        CSwap => "cswap",
+        Measure => "measure",
+        Reset => "reset",
+        Barrier => "barrier",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Circuit;
+
+    #[test]
+    fn test_bell_state() {
+        let qasm = Circuit::new(2).h(0).cx(0, 1).measure_all().to_qasm();
+        assert_eq!(
+            qasm,
+            "OPENQASM 2.0;\n\
+             include \"qelib1.inc\";\n\
+             qreg q[2];\n\
+             creg c[2];\n\
+             h q[0];\n\
+             cx q[0],q[1];\n\
+             measure q[0] -> c[0];\n\
+             measure q[1] -> c[1];\n"
+        );
+    }
+
+    #[test]
+    fn test_creg_sized_by_used_bits() {
+        let qasm = Circuit::with_clbits(3, 5).ccx(0, 1, 2).measure(2, 1).to_qasm();
+        assert!(qasm.contains("creg c[2];"));
+        assert!(qasm.contains("ccx q[0],q[1],q[2];"));
+        assert!(qasm.contains("measure q[2] -> c[1];"));
+
+        assert!(!Circuit::new(1).x(0).to_qasm().contains("creg"));
+    }
+
+    #[test]
+    fn test_parameterized_gates() {
+        let qasm = Circuit::new(2).p(0.5, 0).cp(-0.25, 0, 1).u(1.0, 2.0, 3.0, 1).to_qasm();
+        assert!(qasm.contains("p(0.5) q[0];"));
+        assert!(qasm.contains("cp(-0.25) q[0],q[1];"));
+        assert!(qasm.contains("u(1,2,3) q[1];"));
+    }
+
+    #[test]
+    fn test_parse_round_trips_export() {
+        let mut circuit = Circuit::new(3)
+            .h(0)
+            .x(1)
+            .sdg(2)
+            .rx(0.25, 0)
+            .u(1.0, -2.0, 0.5, 1)
+            .cp(-1.5, 0, 2)
+            .ccx(0, 1, 2)
+            .cswap(2, 0, 1)
+            .barrier_all()
+            .reset(1)
+            .measure(0, 0)
+            .measure(2, 1);
+        circuit.push(crate::Instruction::new(crate::Gate::z(), vec![1]).conditioned_on(1));
+
+        let parsed = Circuit::from_qasm(&circuit.to_qasm()).unwrap();
+        assert_eq!(parsed.instructions(), circuit.instructions());
+        assert_eq!(parsed.num_qubits(), 3);
+        assert_eq!(parsed.num_clbits(), 2);
+    }
+
+    #[test]
+    fn test_parse_registers_and_angles() {
+        let source = "OPENQASM 2.0;\n\
+                      include \"qelib1.inc\";\n\
+                      qreg a[1]; qreg b[2]; // two registers\n\
+                      creg c[2];\n\
+                      h b;\n\
+                      u1(-pi/2) a[0];\n\
+                      rz(2*(pi+1)/4) b[1];\n\
+                      measure b -> c;\n";
+        let circuit = Circuit::from_qasm(source).unwrap();
+
+        assert_eq!(circuit.num_qubits(), 3);
+        let insts = circuit.instructions();
+        assert_eq!(insts[0].qubits, vec![1]);
+        assert_eq!(insts[1].qubits, vec![2]);
+        assert_eq!(insts[2].gate, crate::Gate::p(-crate::PI / 2.0));
+        assert_eq!(insts[3].gate, crate::Gate::rz((crate::PI + 1.0) / 2.0));
+        assert_eq!(insts[5].clbits, vec![1]);
+    }
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        let error = |source: &str| match Circuit::from_qasm(source) {
+            Err(crate::HomayaError::ParseError { line, message }) => (line, message),
+            other => panic!("expected a parse error, got {:?}", other),
+        };
+
+        let (line, message) = error("qreg q[2];\nh q[0];\nfoo q[1];\n");
+        assert_eq!(line, 3);
+        assert!(message.contains("unsupported gate `foo`"));
+
+        assert_eq!(error("qreg q[2];\nh q[0]\n").0, 2);
+        assert!(error("qreg q[2];\ncx q[0];").1.contains("acts on 2 qubit(s)"));
+        assert!(error("qreg q[2];\nh q[2];").1.contains("out of range"));
+        assert!(error("qreg q[2];\ncx q[1],q[1];").1.contains("same qubit more than once"));
+        assert!(error("h r[0];").1.contains("unknown register"));
+        assert!(error("qreg q[1];\nrx(+1) q[0];").1.contains("cannot evaluate angle"));
+    }
+
+    #[test]
+    fn test_conditioned_instruction_splits_register() {
+        let mut circuit = Circuit::new(2).h(0).measure(0, 0);
+        circuit.push(crate::Instruction::new(crate::Gate::x(), vec![1]).conditioned_on(0));
+        let qasm = circuit.to_qasm();
+
+        assert!(qasm.contains("creg c0[1];"));
+        assert!(qasm.contains("measure q[0] -> c0[0];"));
+        assert!(qasm.contains("if(c0==1) x q[1];"));
+    }
+
+    #[test]
+    fn test_named_registers_round_trip() {
+        let mut circuit = Circuit::with_clbits(0, 2);
+        let query = circuit.add_register("query", 2);
+        let ancilla = circuit.add_register("ancilla", 1);
+        let circuit = circuit.h(query[1]).cx(query[1], ancilla[0]).measure(ancilla[0], 1);
+        let qasm = circuit.to_qasm();
+
+        assert!(qasm.contains("qreg query[2];\nqreg ancilla[1];\n"), "{}", qasm);
+        assert!(qasm.contains("cx query[1],ancilla[0];"));
+        assert!(qasm.contains("measure ancilla[0] -> c[1];"));
+
+        let parsed = Circuit::from_qasm(&qasm).unwrap();
+        assert_eq!(parsed.instructions(), circuit.instructions());
+        assert_eq!(parsed.registers(), circuit.registers());
+        assert_eq!(parsed.to_qasm(), qasm);
+
+        // A lone `q` is the anonymous layout
+        assert!(Circuit::from_qasm("qreg q[2];").unwrap().registers().is_empty());
+    }
+
+    #[test]
+    fn test_partial_registers_fall_back_to_q() {
+        // Qubit 0 belongs to no register
+        let mut circuit = Circuit::new(1);
+        let work = circuit.add_register("work", 1);
+        let qasm = circuit.cx(0, work[0]).to_qasm();
+        assert!(qasm.contains("qreg q[2];\ncx q[0],q[1];"), "{}", qasm);
+
+        // `c1` would collide with the split classical registers
+        let mut circuit = Circuit::new(0);
+        circuit.add_register("c1", 1);
+        assert!(circuit.to_qasm().contains("qreg q[1];"));
+    }
+}
@@ -0,0 +1,131 @@
+//! Named qubit registers.
+//!
+//! A register is a handle on a contiguous run of a circuit's qubits, so
+//! builders can write `query[i]` and `ancilla[0]` instead of offsetting
+//! raw indices by hand. See [`Circuit::add_register`](crate::Circuit::add_register).
+
+use core::ops::Index;
+
+/// A named, contiguous block of qubits in a [`Circuit`](crate::Circuit).
+///
+/// Indexing maps a register-local index to the circuit's global one.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_core::Circuit;
+///
+/// let mut circuit = Circuit::new(0);
+/// let query = circuit.add_register("query", 3);
+/// let ancilla = circuit.add_register("ancilla", 1);
+///
+/// assert_eq!(ancilla[0], 3);
+/// let circuit = circuit.x(ancilla[0]).cx(query[2], ancilla[0]);
+/// assert_eq!(circuit.instructions()[1].qubits, vec![2, 3]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QubitRegister {
+    /// Register name, used for the `qreg` in OpenQASM output
+    name: String,
+    /// Global index of each qubit, in order
+    qubits: Vec<usize>,
+}
+
+impl QubitRegister {
+    /// A register of `size` qubits starting at global index `offset`.
+    pub(crate) fn new(name: String, offset: usize, size: usize) -> Self {
+        Self {
+            name,
+            qubits: (offset..offset + size).collect(),
+        }
+    }
+
+    /// Get the register name.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Global index of the register's first qubit.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.qubits[0]
+    }
+
+    /// Get the number of qubits.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.qubits.len()
+    }
+
+    /// Always false: [`add_register`](crate::Circuit::add_register) refuses
+    /// empty registers.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.qubits.is_empty()
+    }
+
+    /// Global indices of all the register's qubits, in order.
+    ///
+    /// Handy for the slice-taking builders, e.g.
+    /// [`measure_qubits`](crate::Circuit::measure_qubits).
+    #[inline]
+    pub fn qubits(&self) -> &[usize] {
+        &self.qubits
+    }
+
+    /// Check if global qubit `q` belongs to this register.
+    #[inline]
+    pub fn contains(&self, q: usize) -> bool {
+        self.qubits.contains(&q)
+    }
+}
+
+/// Whether `name` is an OpenQASM 2.0 identifier: a lowercase letter, then
+/// letters, digits or `_`.
+pub(crate) fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_lowercase()) && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl Index<usize> for QubitRegister {
+    type Output = usize;
+
+    /// Global index of the register's `i`-th qubit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than the register length.
+    fn index(&self, i: usize) -> &usize {
+        assert!(
+            i < self.qubits.len(),
+            "index {} out of range for register `{}` of {} qubit(s)",
+            i,
+            self.name,
+            self.qubits.len()
+        );
+        &self.qubits[i]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_maps_to_global_indices() {
+        let reg = QubitRegister::new("work".into(), 4, 3);
+        assert_eq!(reg.offset(), 4);
+        assert_eq!(reg.len(), 3);
+        assert_eq!((reg[0], reg[2]), (4, 6));
+        assert_eq!(reg.qubits(), &[4, 5, 6]);
+        assert!(reg.contains(5) && !reg.contains(7));
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 out of range for register `work` of 3 qubit(s)")]
+    fn test_register_index_out_of_range() {
+        let _ = QubitRegister::new("work".into(), 4, 3)[3];
+    }
+}
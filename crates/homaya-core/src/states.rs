@@ -0,0 +1,93 @@
+//! Circuits preparing standard entangled states.
+//!
+//! Bitstrings in the docs use the canonical order, qubit 0 rightmost, so
+//! |01⟩ has qubit 0 set.
+
+use crate::Circuit;
+
+/// The four maximally entangled two-qubit Bell states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BellState {
+    /// |Φ+⟩ = (|00⟩ + |11⟩)/√2
+    PhiPlus,
+    /// |Φ−⟩ = (|00⟩ − |11⟩)/√2
+    PhiMinus,
+    /// |Ψ+⟩ = (|01⟩ + |10⟩)/√2
+    PsiPlus,
+    /// |Ψ−⟩ = (|01⟩ − |10⟩)/√2, the singlet
+    PsiMinus,
+}
+
+/// Prepare a Bell state on qubits 0 and 1 from |00⟩.
+///
+/// H and CX make |Φ+⟩; a Z on qubit 0 flips the sign of |11⟩, an X on
+/// qubit 1 turns Φ into Ψ, and for Ψ− a final Z on qubit 1 flips the sign
+/// of |10⟩.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_core::states::{bell, BellState};
+///
+/// let circuit = bell(BellState::PhiPlus);
+/// assert_eq!(circuit.len(), 2);
+/// ```
+pub fn bell(which: BellState) -> Circuit {
+    let phi_plus = Circuit::new(2).h(0).cx(0, 1);
+    match which {
+        BellState::PhiPlus => phi_plus,
+        BellState::PhiMinus => phi_plus.z(0),
+        BellState::PsiPlus => phi_plus.x(1),
+        BellState::PsiMinus => phi_plus.x(1).z(1),
+    }
+}
+
+/// Prepare the n-qubit GHZ state (|0...0⟩ + |1...1⟩)/√2.
+///
+/// H on qubit 0, then a CX chain copying it down the register.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_core::states::ghz;
+///
+/// let circuit = ghz(4);
+/// assert_eq!(circuit.depth(), 4);
+/// ```
+pub fn ghz(n_qubits: usize) -> Circuit {
+    let mut circuit = Circuit::new(n_qubits);
+    if n_qubits == 0 {
+        return circuit;
+    }
+
+    circuit = circuit.h(0);
+    for q in 1..n_qubits {
+        circuit = circuit.cx(q - 1, q);
+    }
+    circuit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GateType;
+
+    #[test]
+    fn test_ghz_structure() {
+        let circuit = ghz(3);
+        let gates: Vec<(GateType, &[usize])> = circuit
+            .instructions()
+            .iter()
+            .map(|inst| (inst.gate.gate_type, inst.qubits.as_slice()))
+            .collect();
+        assert_eq!(gates, [(GateType::H, &[0][..]), (GateType::CX, &[0, 1]), (GateType::CX, &[1, 2])]);
+        assert!(ghz(0).is_empty());
+    }
+
+    #[test]
+    fn test_bell_states_are_distinct() {
+        let all = [BellState::PhiPlus, BellState::PhiMinus, BellState::PsiPlus, BellState::PsiMinus];
+        let hashes: std::collections::HashSet<u64> = all.iter().map(|&b| bell(b).structural_hash()).collect();
+        assert_eq!(hashes.len(), 4);
+    }
+}
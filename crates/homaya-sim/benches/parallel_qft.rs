@@ -0,0 +1,52 @@
+//! Serial vs multi-threaded dense simulation of a 20-qubit QFT.
+//!
+//! Run with: `cargo bench -p homaya-sim --features parallel --bench parallel_qft`
+//!
+//! The serial case runs inside a one-thread rayon pool, so both sides use
+//! the same code path and differ only in the threads available; the
+//! parallel case gets rayon's global pool, one thread per core.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use homaya_core::{Circuit, PI};
+use homaya_sim::Simulator;
+
+/// Textbook QFT: H and controlled phases per qubit, then reverse the order.
+fn qft(n: usize) -> Circuit {
+    let mut circuit = Circuit::new(n).named("qft");
+    for target in (0..n).rev() {
+        circuit = circuit.h(target);
+        for control in (0..target).rev() {
+            circuit = circuit.cp(PI / (1 << (target - control)) as f64, control, target);
+        }
+    }
+    for q in 0..n / 2 {
+        circuit = circuit.swap(q, n - 1 - q);
+    }
+    circuit
+}
+
+fn serial_vs_parallel(c: &mut Criterion) {
+    let single = rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+
+    let mut group = c.benchmark_group("qft");
+    group.sample_size(10);
+    for n in [16, 20] {
+        let circuit = qft(n);
+
+        // Splitting the work must not change a single bit of the result
+        let serial = single.install(|| Simulator::new().run(&circuit).unwrap());
+        let parallel = Simulator::new().run(&circuit).unwrap();
+        assert_eq!(serial.amplitudes(), parallel.amplitudes(), "parallel result differs from serial");
+
+        group.bench_with_input(BenchmarkId::new("serial", n), &circuit, |b, circuit| {
+            single.install(|| b.iter(|| black_box(Simulator::new().run(circuit).unwrap())))
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", n), &circuit, |b, circuit| {
+            b.iter(|| black_box(Simulator::new().run(circuit).unwrap()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, serial_vs_parallel);
+criterion_main!(benches);
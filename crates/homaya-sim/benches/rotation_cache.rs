@@ -0,0 +1,37 @@
+//! Repeated rotations with and without the simulator's gate-matrix cache.
+//!
+//! Run with: `cargo bench -p homaya-sim --bench rotation_cache`
+//!
+//! One qubit, so the cos/sin per gate is a visible share of the work. The
+//! uncached side rebuilds every matrix from its angle; the cached side
+//! reuses one simulator, whose cache is warm after the first run.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use homaya_core::{Circuit, PI};
+use homaya_sim::{Simulator, StateVector};
+
+const GATES: usize = 1000;
+
+/// Apply every gate with a freshly computed matrix.
+fn run_uncached(circuit: &Circuit) -> StateVector {
+    let mut state = StateVector::new(circuit.num_qubits());
+    for inst in circuit.instructions() {
+        state.apply_single(inst.qubits[0], inst.gate.matrix_2x2().unwrap());
+    }
+    state
+}
+
+fn cached_vs_uncached(c: &mut Criterion) {
+    let circuit = (0..GATES).fold(Circuit::new(1), |circuit, _| circuit.rz(PI / 7.0, 0));
+
+    let mut sim = Simulator::new();
+    assert_eq!(sim.run(&circuit).unwrap().amplitudes(), run_uncached(&circuit).amplitudes());
+
+    let mut group = c.benchmark_group("rz_1000");
+    group.bench_function("uncached", |b| b.iter(|| black_box(run_uncached(black_box(&circuit)))));
+    group.bench_function("cached", |b| b.iter(|| black_box(sim.run(black_box(&circuit)).unwrap())));
+    group.finish();
+}
+
+criterion_group!(benches, cached_vs_uncached);
+criterion_main!(benches);
@@ -0,0 +1,43 @@
+//! Sparse vs dense simulation of Bernstein-Vazirani.
+//!
+//! Run with: `cargo bench -p homaya-sim --bench sparse`
+//!
+//! Each query qubit gets its H, CNOT, H in turn, so the state never holds
+//! more than four amplitudes: the sparse simulator's memory stays flat
+//! while the dense state doubles with every qubit (512 MiB at 25 qubits).
+//! Dense runs stop at 21 qubits; the sparse ones go on to 61, far past
+//! what a dense state vector can hold.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use homaya_core::Circuit;
+use homaya_sim::{Simulator, SparseSimulator};
+
+/// Bernstein-Vazirani on `n` query qubits with every secret bit set, without readout.
+fn bernstein_vazirani(n: usize) -> Circuit {
+    let ancilla = n;
+    let start = Circuit::new(n + 1).x(ancilla).h(ancilla);
+    (0..n).fold(start, |circuit, q| circuit.h(q).cx(q, ancilla).h(q))
+}
+
+fn sparse_vs_dense(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bernstein_vazirani");
+    for n in [8, 12, 16, 20] {
+        let circuit = bernstein_vazirani(n);
+        group.bench_with_input(BenchmarkId::new("dense", n), &circuit, |b, circuit| {
+            b.iter(|| black_box(Simulator::new().run(circuit).unwrap()))
+        });
+        group.bench_with_input(BenchmarkId::new("sparse", n), &circuit, |b, circuit| {
+            b.iter(|| black_box(SparseSimulator::new().run(circuit).unwrap()))
+        });
+    }
+    for n in [30, 40, 60] {
+        let circuit = bernstein_vazirani(n);
+        group.bench_with_input(BenchmarkId::new("sparse", n), &circuit, |b, circuit| {
+            b.iter(|| black_box(SparseSimulator::new().run(circuit).unwrap()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, sparse_vs_dense);
+criterion_main!(benches);
@@ -6,6 +6,10 @@
 //! - **Full gate support** including all standard gates
 //! - **Measurement** with proper state collapse
 //! - **Sampling** for running multiple shots
+//! - **Single precision** via [`Simulator::run_f32`] at half the memory
+//! - **Sparse states** via [`SparseSimulator`] for circuits that stay on a few basis states
+//! - **Noise** via [`Simulator::run_noisy`] with depolarizing and readout errors
+//! - **Multi-threading** of dense gates with the `parallel` feature, bit-identical to serial
 //!
 //! # Example
 //!
@@ -48,7 +52,15 @@
 #![deny(missing_docs)]
 
 mod statevector;
+mod statevector32;
 mod simulator;
+mod sparse;
+mod qiskit;
+mod noise;
 
-pub use statevector::StateVector;
-pub use simulator::{Simulator, MeasurementResult};
+pub use statevector::{StateVector, PARALLEL_MIN_QUBITS, PHASE_PIVOT_EPSILON};
+pub use statevector32::StateVector32;
+pub use simulator::{CacheStats, MeasurementResult, SimConfig, Simulator};
+pub use sparse::{SparseSimulator, SparseState};
+pub use qiskit::qiskit_result_json;
+pub use noise::NoiseModel;
@@ -0,0 +1,86 @@
+//! Monte-Carlo noise models.
+//!
+//! Each shot of [`Simulator::run_noisy`](crate::Simulator::run_noisy) is a
+//! pure state trajectory: errors are sampled as discrete Pauli and bit
+//! flips, and averaging over shots reproduces the noisy channel.
+
+/// Per-gate depolarizing and readout errors for
+/// [`Simulator::run_noisy`](crate::Simulator::run_noisy).
+///
+/// After every gate, each qubit it acted on suffers a uniformly random
+/// Pauli X, Y or Z with probability `depolarizing`, so a two-qubit gate
+/// sees two independent single-qubit channels. Each measured bit is then
+/// flipped with probability `readout` before it is recorded; the qubit
+/// itself keeps the true outcome.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_sim::NoiseModel;
+///
+/// let noise = NoiseModel::new().with_depolarizing(0.01).with_readout_error(0.02);
+/// assert!(!noise.is_noiseless());
+/// assert!(NoiseModel::new().is_noiseless());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NoiseModel {
+    /// Probability of a random Pauli on each qubit after each gate
+    pub depolarizing: f64,
+    /// Probability that a measured bit is recorded flipped
+    pub readout: f64,
+}
+
+impl NoiseModel {
+    /// A model with no errors; add them with the `with_*` methods.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the per-gate depolarizing probability.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in [0, 1].
+    pub fn with_depolarizing(mut self, p: f64) -> Self {
+        check_probability("depolarizing", p);
+        self.depolarizing = p;
+        self
+    }
+
+    /// Set the readout bit-flip probability.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in [0, 1].
+    pub fn with_readout_error(mut self, p: f64) -> Self {
+        check_probability("readout", p);
+        self.readout = p;
+        self
+    }
+
+    /// Whether the model never introduces an error.
+    pub fn is_noiseless(&self) -> bool {
+        self.depolarizing == 0.0 && self.readout == 0.0
+    }
+}
+
+fn check_probability(name: &str, p: f64) {
+    assert!((0.0..=1.0).contains(&p), "{} probability {} is not in [0, 1]", name, p);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_sets_fields() {
+        let noise = NoiseModel::new().with_depolarizing(0.1).with_readout_error(0.2);
+        assert_eq!(noise, NoiseModel { depolarizing: 0.1, readout: 0.2 });
+    }
+
+    #[test]
+    #[should_panic(expected = "readout probability 1.5 is not in [0, 1]")]
+    fn test_rejects_invalid_probability() {
+        NoiseModel::new().with_readout_error(1.5);
+    }
+}
@@ -0,0 +1,98 @@
+//! Export to Qiskit's `Result` JSON schema.
+//!
+//! Qiskit keys counts by hexadecimal integers (`"0x3"`) rather than
+//! bitstrings and wraps them in a result envelope, so the output of
+//! [`qiskit_result_json`] loads with `qiskit.result.Result.from_dict`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use homaya_core::parse_outcome;
+
+/// Render a measurement histogram as a Qiskit `Result` JSON document.
+///
+/// Keys must be canonical bitstrings (classical bit 0 rightmost), as
+/// returned by [`Simulator::sample`](crate::Simulator::sample); keys that do
+/// not parse are skipped. The header records one classical register `c` as
+/// wide as the longest key, so Qiskit can turn the hex keys back into
+/// bitstrings.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use homaya_sim::qiskit_result_json;
+///
+/// let counts = HashMap::from([("11".to_string(), 3), ("00".to_string(), 1)]);
+/// let json = qiskit_result_json(&counts);
+/// assert!(json.contains(r#""counts": {"0x0": 1, "0x3": 3}"#));
+/// assert!(json.contains(r#""shots": 4"#));
+/// ```
+pub fn qiskit_result_json(counts: &HashMap<String, usize>) -> String {
+    let width = counts.keys().map(String::len).max().unwrap_or(0);
+    let shots: usize = counts.values().sum();
+
+    // Sorted by value for stable output
+    let mut by_value = BTreeMap::new();
+    for (bits, &count) in counts {
+        if let Some(value) = parse_outcome(bits) {
+            *by_value.entry(value).or_insert(0) += count;
+        }
+    }
+    let entries: Vec<String> = by_value
+        .iter()
+        .map(|(value, count)| format!("\"0x{:x}\": {}", value, count))
+        .collect();
+
+    let mut json = String::new();
+    let _ = write!(
+        json,
+        concat!(
+            "{{\"backend_name\": \"homaya_simulator\", \"backend_version\": \"{}\", ",
+            "\"qobj_id\": \"\", \"job_id\": \"\", \"success\": true, \"results\": [",
+            "{{\"shots\": {}, \"success\": true, \"data\": {{\"counts\": {{{}}}}}, ",
+            "\"header\": {{\"creg_sizes\": [[\"c\", {}]], \"memory_slots\": {}}}}}]}}"
+        ),
+        env!("CARGO_PKG_VERSION"),
+        shots,
+        entries.join(", "),
+        width,
+        width
+    );
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_histogram() {
+        let counts = HashMap::from([
+            ("011".to_string(), 512),
+            ("000".to_string(), 500),
+            ("110".to_string(), 12),
+        ]);
+
+        assert_eq!(
+            qiskit_result_json(&counts),
+            format!(
+                concat!(
+                    "{{\"backend_name\": \"homaya_simulator\", \"backend_version\": \"{}\", ",
+                    "\"qobj_id\": \"\", \"job_id\": \"\", \"success\": true, \"results\": [",
+                    "{{\"shots\": 1024, \"success\": true, ",
+                    "\"data\": {{\"counts\": {{\"0x0\": 500, \"0x3\": 512, \"0x6\": 12}}}}, ",
+                    "\"header\": {{\"creg_sizes\": [[\"c\", 3]], \"memory_slots\": 3}}}}]}}"
+                ),
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+    }
+
+    #[test]
+    fn test_hex_uses_bit_zero_rightmost() {
+        // Clbit 0 set and clbit 4 set: 0b10001 = 0x11
+        let counts = HashMap::from([("10001".to_string(), 1)]);
+        assert!(qiskit_result_json(&counts).contains("\"0x11\": 1"));
+    }
+}
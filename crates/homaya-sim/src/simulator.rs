@@ -2,8 +2,51 @@
 //!
 //! Executes quantum circuits on state vectors.
 
-use homaya_core::{Circuit, Complex, Gate, GateType, GateParams, HomayaError, Result, INV_SQRT_2, PI};
-use crate::StateVector;
+use homaya_core::{BitOrder, Circuit, Complex, Gate, GateType, Instruction, GateParams, HomayaError, Result, INV_SQRT_2, PI};
+use crate::{NoiseModel, StateVector, StateVector32};
+
+/// A state vector the simulator can apply gates to.
+///
+/// Lets one instruction loop drive the double- and single-precision state
+/// vectors and the sparse state.
+pub(crate) trait GateTarget {
+    fn apply_single(&mut self, qubit: usize, matrix: [[Complex; 2]; 2]);
+    fn apply_two(&mut self, q0: usize, q1: usize, matrix: [[Complex; 4]; 4]);
+    fn apply_controlled(&mut self, control: usize, target: usize, matrix: [[Complex; 2]; 2]);
+    fn measure(&mut self, qubit: usize, random: f64) -> u8;
+    fn reset(&mut self, qubit: usize, random: f64);
+    /// (basis state, probability) pairs in ascending basis order; states
+    /// left out have probability zero.
+    fn distribution(&self) -> Vec<(usize, f64)>;
+}
+
+macro_rules! impl_gate_target {
+    ($state:ty) => {
+        impl GateTarget for $state {
+            fn apply_single(&mut self, qubit: usize, matrix: [[Complex; 2]; 2]) {
+                <$state>::apply_single(self, qubit, matrix)
+            }
+            fn apply_two(&mut self, q0: usize, q1: usize, matrix: [[Complex; 4]; 4]) {
+                <$state>::apply_two(self, q0, q1, matrix)
+            }
+            fn apply_controlled(&mut self, control: usize, target: usize, matrix: [[Complex; 2]; 2]) {
+                <$state>::apply_controlled(self, control, target, matrix)
+            }
+            fn measure(&mut self, qubit: usize, random: f64) -> u8 {
+                <$state>::measure(self, qubit, random)
+            }
+            fn reset(&mut self, qubit: usize, random: f64) {
+                <$state>::reset(self, qubit, random)
+            }
+            fn distribution(&self) -> Vec<(usize, f64)> {
+                <$state>::probabilities(self).into_iter().enumerate().collect()
+            }
+        }
+    };
+}
+
+impl_gate_target!(StateVector);
+impl_gate_target!(StateVector32);
 
 /// Measurement results from circuit execution.
 #[derive(Clone, Debug, Default)]
@@ -20,9 +63,63 @@ impl MeasurementResult {
         }
     }
 
-    /// Get result as a bitstring.
+    /// Read a result back from a canonical bitstring.
+    ///
+    /// Returns `None` if the string is empty or contains anything but `0`
+    /// and `1`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_sim::MeasurementResult;
+    ///
+    /// let result = MeasurementResult::from_bitstring("110").unwrap();
+    /// assert_eq!(result.as_int(), 6);
+    /// assert!(!result.bit(0) && result.bit(2));
+    /// assert_eq!(result.bitstring(), "110");
+    /// ```
+    pub fn from_bitstring(bits: &str) -> Option<Self> {
+        if bits.is_empty() || !bits.bytes().all(|b| b == b'0' || b == b'1') {
+            return None;
+        }
+        Some(Self {
+            bits: bits.bytes().rev().map(|b| b - b'0').collect(),
+        })
+    }
+
+    /// Whether classical bit `i` is set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not a classical bit of the result.
+    pub fn bit(&self, i: usize) -> bool {
+        self.bits[i] == 1
+    }
+
+    /// Whether every classical bit is 0.
+    pub fn all_zero(&self) -> bool {
+        self.bits.iter().all(|&b| b == 0)
+    }
+
+    /// Get result as a canonical bitstring (classical bit 0 rightmost).
+    ///
+    /// See [`format_outcome`](homaya_core::format_outcome) for the format.
     pub fn bitstring(&self) -> String {
-        self.bits.iter().map(|b| if *b == 0 { '0' } else { '1' }).collect()
+        self.bitstring_with(BitOrder::LittleEndian)
+    }
+
+    /// Get result as a bitstring in the given bit order.
+    pub fn bitstring_with(&self, order: BitOrder) -> String {
+        order.format(self.as_int() as usize, self.bits.len())
+    }
+
+    /// Export this single shot as a Qiskit `Result` JSON document.
+    ///
+    /// See [`qiskit_result_json`](crate::qiskit_result_json), which also
+    /// takes a full histogram from [`Simulator::sample`].
+    pub fn to_qiskit_json(&self) -> String {
+        let counts = std::collections::HashMap::from([(self.bitstring(), 1)]);
+        crate::qiskit_result_json(&counts)
     }
 
     /// Get result as an integer (little-endian).
@@ -51,12 +148,102 @@ impl MeasurementResult {
 /// assert!(state.probability(0) > 0.49); // |00⟩
 /// assert!(state.probability(3) > 0.49); // |11⟩
 /// ```
+///
+/// # Randomness
+///
+/// Every stochastic step draws from one xorshift64 generator (Marsaglia's
+/// 13/7/17 shifts) owned by the simulator: one draw per executed measure
+/// or reset, in instruction order, and one per shot when terminal
+/// measurements are sampled from the final state. A seed becomes the
+/// generator state directly (0, which xorshift can't leave, is replaced by
+/// the default state). The generator and draw order are pinned, so a given
+/// seed, circuit and shot count give the same histogram across versions.
 #[derive(Clone, Debug)]
 pub struct Simulator {
     /// Random seed for measurements
     seed: Option<u64>,
     /// Current random state
     rng_state: u64,
+    /// Shots for [`sample_configured`](Self::sample_configured)
+    shots: usize,
+    /// Matrices of parameterized gates seen so far
+    matrix_cache: std::collections::HashMap<MatrixKey, [[Complex; 2]; 2]>,
+    /// Most recent cache entry, checked before hashing
+    last_matrix: Option<(MatrixKey, [[Complex; 2]; 2])>,
+    /// Lookups into `matrix_cache`
+    cache_stats: CacheStats,
+    /// How histogram keys are written
+    bit_order: BitOrder,
+    /// Whether returned states keep their global phase
+    track_global_phase: bool,
+}
+
+/// A parameterized gate by kind and exact angle bits.
+///
+/// Keying on the bit pattern rather than a rounded angle means a hit
+/// returns exactly the matrix the gate would have computed.
+type MatrixKey = (GateType, [u64; 3]);
+
+/// Most distinct parameterized gates the cache remembers.
+///
+/// Circuits with more distinct angles than this (e.g. a parameter sweep)
+/// compute the rest on every application instead of growing without bound.
+const MATRIX_CACHE_CAPACITY: usize = 1024;
+
+/// Hits and misses of the simulator's gate-matrix cache.
+///
+/// Rotation, phase and `U` gates (including `CP`) look up their 2×2 matrix
+/// by gate kind and angle before computing it; fixed gates skip the cache.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_core::{Circuit, PI};
+/// use homaya_sim::Simulator;
+///
+/// let mut circuit = Circuit::new(1);
+/// for _ in 0..10 {
+///     circuit = circuit.rz(PI / 7.0, 0);
+/// }
+/// let mut sim = Simulator::new();
+/// sim.run(&circuit).unwrap();
+/// assert_eq!(sim.cache_stats().misses, 1);
+/// assert_eq!(sim.cache_stats().hits, 9);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Gates whose matrix came from the cache
+    pub hits: u64,
+    /// Gates whose matrix had to be computed
+    pub misses: u64,
+}
+
+/// Settings for a [`Simulator`].
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_core::Circuit;
+/// use homaya_sim::{SimConfig, Simulator};
+///
+/// let config = SimConfig { seed: Some(42), shots: 500 };
+/// let circuit = Circuit::new(1).h(0).measure(0, 0);
+/// let a = Simulator::with_config(config).sample_configured(&circuit).unwrap();
+/// let b = Simulator::with_config(config).sample_configured(&circuit).unwrap();
+/// assert_eq!(a, b);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SimConfig {
+    /// Seed for every measurement; `None` uses the fixed default state
+    pub seed: Option<u64>,
+    /// Number of shots to sample
+    pub shots: usize,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self { seed: None, shots: 1024 }
+    }
 }
 
 impl Default for Simulator {
@@ -65,23 +252,132 @@ impl Default for Simulator {
     }
 }
 
+/// Initial xorshift state when no seed is given.
+const DEFAULT_RNG_STATE: u64 = 0x853c49e6748fea9b;
+
+/// The generator state for `seed`; xorshift would stay at 0 forever.
+fn rng_state_for(seed: u64) -> u64 {
+    if seed == 0 {
+        DEFAULT_RNG_STATE
+    } else {
+        seed
+    }
+}
+
 impl Simulator {
     /// Create a new simulator.
     pub fn new() -> Self {
-        Self {
-            seed: None,
-            rng_state: 0x853c49e6748fea9b, // Default seed
-        }
+        Self::with_config(SimConfig::default())
     }
 
     /// Create a simulator with a specific seed for reproducibility.
     pub fn with_seed(seed: u64) -> Self {
-        Self {
+        Self::with_config(SimConfig {
             seed: Some(seed),
-            rng_state: seed,
+            ..SimConfig::default()
+        })
+    }
+
+    /// Create a simulator from a [`SimConfig`].
+    ///
+    /// With a seed, every [`sample`](Self::sample) call restarts the
+    /// generator from it, so the same circuit and shot count always give
+    /// the same histogram, mid-circuit measurements included.
+    pub fn with_config(config: SimConfig) -> Self {
+        Self {
+            seed: config.seed,
+            rng_state: config.seed.map_or(DEFAULT_RNG_STATE, rng_state_for),
+            shots: config.shots,
+            matrix_cache: std::collections::HashMap::new(),
+            last_matrix: None,
+            cache_stats: CacheStats::default(),
+            bit_order: BitOrder::default(),
+            track_global_phase: true,
+        }
+    }
+
+    /// Write histogram keys in `order` instead of the canonical
+    /// little-endian form (classical bit 0 rightmost).
+    ///
+    /// Affects [`sample`](Self::sample) and every other method returning
+    /// bitstring keys. Classical bit 0 is printed leftmost under
+    /// [`BitOrder::BigEndian`]; which qubit feeds which bit is unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{BitOrder, Circuit};
+    /// use homaya_sim::Simulator;
+    ///
+    /// let circuit = Circuit::new(3).x(0).measure_all();
+    /// let counts = Simulator::new().with_bit_order(BitOrder::BigEndian).sample(&circuit, 10).unwrap();
+    /// assert_eq!(counts.get("100"), Some(&10));
+    /// ```
+    pub fn with_bit_order(mut self, order: BitOrder) -> Self {
+        self.bit_order = order;
+        self
+    }
+
+    /// The order histogram keys are written in.
+    pub fn bit_order(&self) -> BitOrder {
+        self.bit_order
+    }
+
+    /// Keep (the default) or normalize away the global phase of returned
+    /// states.
+    ///
+    /// With tracking off, [`run`](Self::run),
+    /// [`run_from_state`](Self::run_from_state),
+    /// [`run_with_measurements`](Self::run_with_measurements) and
+    /// [`run_batch`](Self::run_batch) rotate the final state with
+    /// [`StateVector::remove_global_phase`], so its first significant
+    /// amplitude is real and positive. Printouts then don't depend on how a
+    /// circuit happened to accumulate phase. Probabilities and samples are
+    /// unaffected.
+    ///
+    /// Only do this for standalone circuits. A global phase on a circuit
+    /// becomes a relative phase once the circuit is controlled (see
+    /// [`Circuit::controlled`](homaya_core::Circuit::controlled)), so a state
+    /// normalized this way says nothing about the sub-block's phase.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{Circuit, Complex};
+    /// use homaya_sim::Simulator;
+    ///
+    /// // Rz(π)|0⟩ = -i|0⟩
+    /// let circuit = Circuit::new(1).rz(homaya_core::PI, 0);
+    /// let state = Simulator::new().track_global_phase(false).run(&circuit).unwrap();
+    /// assert!(state.amplitude(0).approx_eq(Complex::from_real(1.0), 1e-12));
+    /// ```
+    pub fn track_global_phase(mut self, track: bool) -> Self {
+        self.track_global_phase = track;
+        self
+    }
+
+    /// Whether returned states keep their global phase.
+    pub fn tracks_global_phase(&self) -> bool {
+        self.track_global_phase
+    }
+
+    /// Rotate away `state`'s global phase unless tracking it.
+    fn finish_state(&self, state: &mut StateVector) {
+        if !self.track_global_phase {
+            state.remove_global_phase();
         }
     }
 
+    /// Gate-matrix cache hits and misses since this simulator was created.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache_stats
+    }
+
+    /// Sample with the configured number of shots.
+    pub fn sample_configured(&mut self, circuit: &Circuit) -> Result<std::collections::HashMap<String, usize>> {
+        self.sample(circuit, self.shots)
+    }
+
     /// Simple xorshift64 PRNG for fast random numbers.
     fn next_random(&mut self) -> f64 {
         let mut x = self.rng_state;
@@ -98,6 +394,26 @@ impl Simulator {
     }
 
     /// Run a circuit starting from a given state.
+    ///
+    /// Build the start with [`StateVector::from_basis`] or
+    /// [`StateVector::from_amplitudes`], e.g. to check how a circuit acts on
+    /// |1⟩ or to feed an eigenstate to phase estimation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HomayaError::QubitMismatch`] if the state and circuit
+    /// widths differ.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{Circuit, Complex, INV_SQRT_2};
+    /// use homaya_sim::{Simulator, StateVector};
+    ///
+    /// // H|1⟩ = |−⟩
+    /// let state = Simulator::new().run_from_state(&Circuit::new(1).h(0), StateVector::from_basis(1, 1)).unwrap();
+    /// assert!(state.amplitude(1).approx_eq(Complex::from_real(-INV_SQRT_2), 1e-12));
+    /// ```
     pub fn run_from_state(&mut self, circuit: &Circuit, state: StateVector) -> Result<StateVector> {
         if state.num_qubits() != circuit.num_qubits() {
             return Err(HomayaError::QubitMismatch {
@@ -110,56 +426,442 @@ impl Simulator {
         let mut measurements = MeasurementResult::new(circuit.num_clbits());
 
         for inst in circuit.instructions() {
-            self.apply_instruction(&mut state, &inst.gate, &inst.qubits, &inst.clbits, &mut measurements)?;
+            self.apply_instruction(&mut state, inst, &mut measurements)?;
         }
 
+        self.finish_state(&mut state);
         Ok(state)
     }
 
     /// Run a circuit with measurements and return both state and results.
     pub fn run_with_measurements(&mut self, circuit: &Circuit) -> Result<(StateVector, MeasurementResult)> {
+        let (mut state, measurements) = self.execute(circuit, StateVector::new(circuit.num_qubits()))?;
+        self.finish_state(&mut state);
+        Ok((state, measurements))
+    }
+
+    /// Get P(qubit i = 1) for every qubit from one simulation pass.
+    ///
+    /// Measurements are skipped, so the probabilities are those the qubits
+    /// hold just before readout rather than a single collapsed outcome.
+    /// With no bits written, classically conditioned gates never apply.
+    pub fn per_qubit_probabilities(&mut self, circuit: &Circuit) -> Result<Vec<f64>> {
         let mut state = StateVector::new(circuit.num_qubits());
         let mut measurements = MeasurementResult::new(circuit.num_clbits());
 
         for inst in circuit.instructions() {
-            self.apply_instruction(&mut state, &inst.gate, &inst.qubits, &inst.clbits, &mut measurements)?;
+            if inst.gate.gate_type == GateType::Measure {
+                continue;
+            }
+            self.apply_instruction(&mut state, inst, &mut measurements)?;
         }
 
-        Ok((state, measurements))
+        Ok(state.qubit_probabilities())
+    }
+
+    /// Get the exact probability of every measured bitstring.
+    ///
+    /// The circuit is simulated once and the final distribution is read out
+    /// through its measurements, so the result is free of shot noise. Keys
+    /// are canonical bitstrings as in [`sample`](Self::sample); outcomes of
+    /// probability zero are left out.
+    ///
+    /// # Errors
+    ///
+    /// [`HomayaError::NotSupported`] unless every measurement is terminal,
+    /// since mid-circuit measurements, resets and conditions make the
+    /// distribution depend on the collapse of each shot.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// let circuit = Circuit::new(2).h(0).cx(0, 1).measure_all();
+    /// let probabilities = Simulator::new().outcome_probabilities(&circuit).unwrap();
+    /// assert!((probabilities["11"] - 0.5).abs() < 1e-12);
+    /// assert_eq!(probabilities.get("01"), None);
+    /// ```
+    pub fn outcome_probabilities(&mut self, circuit: &Circuit) -> Result<std::collections::HashMap<String, f64>> {
+        let readout = terminal_measurements(circuit).ok_or(HomayaError::NotSupported {
+            operation: "exact outcome probabilities need every measurement at the end",
+        })?;
+
+        let mut state = StateVector::new(circuit.num_qubits());
+        let mut unused = MeasurementResult::new(circuit.num_clbits());
+        for inst in circuit.instructions() {
+            if inst.gate.gate_type != GateType::Measure {
+                self.apply_instruction(&mut state, inst, &mut unused)?;
+            }
+        }
+
+        let mut probabilities = std::collections::HashMap::new();
+        for (index, p) in state.probabilities().into_iter().enumerate().filter(|&(_, p)| p > 0.0) {
+            let mut result = MeasurementResult::new(circuit.num_clbits());
+            for &(qubit, clbit) in &readout {
+                result.bits[clbit] = ((index >> qubit) & 1) as u8;
+            }
+            *probabilities.entry(result.bitstring_with(self.bit_order)).or_insert(0.0) += p;
+        }
+
+        Ok(probabilities)
+    }
+
+    /// Get the simulated probability of measuring `target`.
+    ///
+    /// Looks `target` up in [`outcome_probabilities`](Self::outcome_probabilities),
+    /// so it must be a canonical bitstring as wide as the classical register;
+    /// anything else is never measured and gives 0.
+    ///
+    /// # Errors
+    ///
+    /// As for [`outcome_probabilities`](Self::outcome_probabilities).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// let circuit = Circuit::new(1).ry(1.0, 0).measure(0, 0);
+    /// let p = Simulator::new().success_probability(&circuit, "1").unwrap();
+    /// assert!((p - 0.5f64.sin().powi(2)).abs() < 1e-12);
+    /// ```
+    pub fn success_probability(&mut self, circuit: &Circuit, target: &str) -> Result<f64> {
+        Ok(self.outcome_probabilities(circuit)?.get(target).copied().unwrap_or(0.0))
+    }
+
+    /// Compare the simulated outcome distribution with an ideal one.
+    ///
+    /// Returns the classical fidelity (Σₓ √(p(x) q(x)))² between the exact
+    /// [`outcome_probabilities`](Self::outcome_probabilities) p and
+    /// `expected` q: 1 when they agree, 0 when they share no outcome.
+    /// `expected` should sum to 1; bitstrings missing from it have q = 0.
+    ///
+    /// # Errors
+    ///
+    /// As for [`outcome_probabilities`](Self::outcome_probabilities).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// let circuit = Circuit::new(2).h(0).cx(0, 1).measure_all();
+    /// let bell = HashMap::from([("00".to_string(), 0.5), ("11".to_string(), 0.5)]);
+    /// assert!((Simulator::new().algorithm_fidelity(&circuit, &bell).unwrap() - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn algorithm_fidelity(
+        &mut self,
+        circuit: &Circuit,
+        expected: &std::collections::HashMap<String, f64>,
+    ) -> Result<f64> {
+        let overlap: f64 = self
+            .outcome_probabilities(circuit)?
+            .iter()
+            .filter_map(|(bits, &p)| expected.get(bits).map(|&q| (p * q.max(0.0)).sqrt()))
+            .sum();
+        Ok(overlap * overlap)
     }
 
     /// Sample the circuit multiple times.
+    ///
+    /// Keys are canonical bitstrings with classical bit 0 rightmost, so
+    /// `measure(q, c)` lands in character `num_clbits - 1 - c`, unless
+    /// [`with_bit_order`](Self::with_bit_order) picked big-endian keys.
+    ///
+    /// When every measurement is terminal the state is simulated once and
+    /// the shots are drawn from its distribution; otherwise (mid-circuit
+    /// measurement, reset, [`c_if`](Circuit::c_if)) each shot reruns the
+    /// circuit, collapsing at every measurement so conditions see that
+    /// shot's bits.
     pub fn sample(&mut self, circuit: &Circuit, shots: usize) -> Result<std::collections::HashMap<String, usize>> {
+        self.sample_with(circuit, shots, StateVector::new)
+    }
+
+    /// Sample with a fresh simulator seeded by `seed`.
+    ///
+    /// Shorthand for `Simulator::with_seed(seed).sample(circuit, shots)`,
+    /// reproducible across runs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// let circuit = Circuit::new(2).h(0).cx(0, 1).measure_all();
+    /// let a = Simulator::sample_seeded(&circuit, 100, 7).unwrap();
+    /// let b = Simulator::sample_seeded(&circuit, 100, 7).unwrap();
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn sample_seeded(circuit: &Circuit, shots: usize, seed: u64) -> Result<std::collections::HashMap<String, usize>> {
+        Self::with_seed(seed).sample(circuit, shots)
+    }
+
+    /// Run each circuit and return the final states in input order.
+    ///
+    /// Every circuit runs on its own copy of this simulator, as if it were
+    /// the only one, so results don't depend on the batch order. With the
+    /// `parallel` feature the circuits run concurrently and give the same
+    /// states as the serial loop.
+    ///
+    /// # Errors
+    ///
+    /// The first error any circuit raises, in input order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// let circuits: Vec<Circuit> = (0..3).map(|q| Circuit::new(3).x(q)).collect();
+    /// let states = Simulator::new().run_batch(&circuits).unwrap();
+    /// assert_eq!(states[2].most_likely().0, 0b100);
+    /// ```
+    pub fn run_batch(&mut self, circuits: &[Circuit]) -> Result<Vec<StateVector>> {
+        self.map_batch(circuits, |sim, circuit| sim.run(circuit))
+    }
+
+    /// Sample each circuit `shots` times and return the histograms in input order.
+    ///
+    /// Each circuit is sampled on its own copy of this simulator, so with a
+    /// seed every histogram equals what [`sample`](Self::sample) gives for
+    /// that circuit alone, with or without the `parallel` feature.
+    ///
+    /// # Errors
+    ///
+    /// The first error any circuit raises, in input order.
+    pub fn sample_batch(
+        &mut self,
+        circuits: &[Circuit],
+        shots: usize,
+    ) -> Result<Vec<std::collections::HashMap<String, usize>>> {
+        self.map_batch(circuits, |sim, circuit| sim.sample(circuit, shots))
+    }
+
+    /// Apply `f` to every circuit, each with a fresh copy of this simulator.
+    fn map_batch<T, F>(&self, circuits: &[Circuit], f: F) -> Result<Vec<T>>
+    where
+        T: Send,
+        F: Fn(&mut Simulator, &Circuit) -> Result<T> + Sync + Send,
+    {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            circuits.par_iter().map(|circuit| f(&mut self.clone(), circuit)).collect()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        circuits.iter().map(|circuit| f(&mut self.clone(), circuit)).collect()
+    }
+
+    /// Run a circuit in single precision and return the final state.
+    ///
+    /// Halves the memory of [`run`](Self::run), see [`StateVector32`] for
+    /// the accuracy trade-off.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// let state = Simulator::new().run_f32(&Circuit::new(2).h(0).cx(0, 1)).unwrap();
+    /// assert!((state.probability(3) - 0.5).abs() < 1e-6);
+    /// ```
+    pub fn run_f32(&mut self, circuit: &Circuit) -> Result<StateVector32> {
+        let (state, _) = self.execute(circuit, StateVector32::new(circuit.num_qubits()))?;
+        Ok(state)
+    }
+
+    /// Sample the circuit multiple times in single precision.
+    ///
+    /// Same keys and seeding as [`sample`](Self::sample). Shot noise dwarfs
+    /// the single-precision rounding error, so this is the natural use of
+    /// the f32 path.
+    pub fn sample_f32(&mut self, circuit: &Circuit, shots: usize) -> Result<std::collections::HashMap<String, usize>> {
+        self.sample_with(circuit, shots, StateVector32::new)
+    }
+
+    /// Sample the circuit under `noise`, one Monte-Carlo trajectory per shot.
+    ///
+    /// Every shot reruns the circuit, collapsing at each measurement. After
+    /// each applied gate one draw per operand decides whether (and which)
+    /// Pauli error strikes it, and after each measurement one draw decides
+    /// whether the recorded bit flips. Keys and seeding are as in
+    /// [`sample`](Self::sample); a noiseless model delegates to it, so the
+    /// histograms agree exactly.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::{NoiseModel, Simulator};
+    ///
+    /// let circuit = Circuit::new(1).x(0).measure(0, 0);
+    /// let noise = NoiseModel::new().with_readout_error(0.1);
+    /// let counts = Simulator::with_seed(5).run_noisy(&circuit, &noise, 1000).unwrap();
+    /// assert!(counts["0"] > 50 && counts["0"] < 150);
+    /// ```
+    pub fn run_noisy(
+        &mut self,
+        circuit: &Circuit,
+        noise: &NoiseModel,
+        shots: usize,
+    ) -> Result<std::collections::HashMap<String, usize>> {
+        if noise.is_noiseless() {
+            return self.sample(circuit, shots);
+        }
+
+        if let Some(seed) = self.seed {
+            self.rng_state = rng_state_for(seed);
+        }
+
+        let mut counts = std::collections::HashMap::new();
+        for _ in 0..shots {
+            let mut state = StateVector::new(circuit.num_qubits());
+            let mut measurements = MeasurementResult::new(circuit.num_clbits());
+
+            for inst in circuit.instructions() {
+                let applies = inst.condition.is_none_or(|clbit| measurements.bits.get(clbit) == Some(&1));
+                self.apply_instruction(&mut state, inst, &mut measurements)?;
+                if !applies {
+                    continue;
+                }
+
+                match inst.gate.gate_type {
+                    GateType::Measure => {
+                        if let Some(&clbit) = inst.clbits.first() {
+                            if self.next_random() < noise.readout {
+                                measurements.bits[clbit] ^= 1;
+                            }
+                        }
+                    }
+                    GateType::Reset | GateType::Barrier => {}
+                    _ => {
+                        for &qubit in &inst.qubits {
+                            self.apply_depolarizing(&mut state, qubit, noise.depolarizing);
+                        }
+                    }
+                }
+            }
+
+            *counts.entry(measurements.bitstring_with(self.bit_order)).or_insert(0) += 1;
+        }
+
+        Ok(counts)
+    }
+
+    /// With probability `p`, apply X, Y or Z (equally likely) to `qubit`.
+    fn apply_depolarizing<S: GateTarget>(&mut self, state: &mut S, qubit: usize, p: f64) {
+        let random = self.next_random();
+        if random >= p {
+            return;
+        }
+
+        // The same draw, rescaled to [0, 3), picks the Pauli
+        let pauli = match (3.0 * random / p) as usize {
+            0 => [[Complex::ZERO, Complex::ONE], [Complex::ONE, Complex::ZERO]],
+            1 => [[Complex::ZERO, -Complex::I], [Complex::I, Complex::ZERO]],
+            _ => [[Complex::ONE, Complex::ZERO], [Complex::ZERO, -Complex::ONE]],
+        };
+        state.apply_single(qubit, pauli);
+    }
+
+    /// Shared sampling loop for every state representation.
+    pub(crate) fn sample_with<S: GateTarget>(
+        &mut self,
+        circuit: &Circuit,
+        shots: usize,
+        new_state: impl Fn(usize) -> S,
+    ) -> Result<std::collections::HashMap<String, usize>> {
         let mut counts = std::collections::HashMap::new();
 
         // Reset seed if specified
         if let Some(seed) = self.seed {
-            self.rng_state = seed;
+            self.rng_state = rng_state_for(seed);
+        }
+
+        let Some(readout) = terminal_measurements(circuit) else {
+            for _ in 0..shots {
+                let (_, result) = self.execute(circuit, new_state(circuit.num_qubits()))?;
+                *counts.entry(result.bitstring_with(self.bit_order)).or_insert(0) += 1;
+            }
+            return Ok(counts);
+        };
+
+        // Simulate once, then draw every shot from the final distribution
+        let mut state = new_state(circuit.num_qubits());
+        let mut unused = MeasurementResult::new(circuit.num_clbits());
+        for inst in circuit.instructions() {
+            if inst.gate.gate_type != GateType::Measure {
+                self.apply_instruction(&mut state, inst, &mut unused)?;
+            }
         }
 
+        let distribution = state.distribution();
+        let cumulative: Vec<f64> = distribution
+            .iter()
+            .scan(0.0, |total, &(_, p)| {
+                *total += p;
+                Some(*total)
+            })
+            .collect();
+
         for _ in 0..shots {
-            let (_, result) = self.run_with_measurements(circuit)?;
-            *counts.entry(result.bitstring()).or_insert(0) += 1;
+            let random = self.next_random() * cumulative.last().copied().unwrap_or(1.0);
+            let position = cumulative.partition_point(|&c| c <= random).min(cumulative.len() - 1);
+            let index = distribution[position].0;
+
+            let mut result = MeasurementResult::new(circuit.num_clbits());
+            for &(qubit, clbit) in &readout {
+                result.bits[clbit] = ((index >> qubit) & 1) as u8;
+            }
+            *counts.entry(result.bitstring_with(self.bit_order)).or_insert(0) += 1;
         }
 
         Ok(counts)
     }
 
+    /// Apply every instruction to `state`, recording measurements.
+    pub(crate) fn execute<S: GateTarget>(&mut self, circuit: &Circuit, mut state: S) -> Result<(S, MeasurementResult)> {
+        let mut measurements = MeasurementResult::new(circuit.num_clbits());
+
+        for inst in circuit.instructions() {
+            self.apply_instruction(&mut state, inst, &mut measurements)?;
+        }
+
+        Ok((state, measurements))
+    }
+
     /// Apply a single instruction to the state.
-    fn apply_instruction(
+    fn apply_instruction<S: GateTarget>(
         &mut self,
-        state: &mut StateVector,
-        gate: &Gate,
-        qubits: &[usize],
-        clbits: &[usize],
+        state: &mut S,
+        inst: &Instruction,
         measurements: &mut MeasurementResult,
     ) -> Result<()> {
         use GateType::*;
 
+        // Measurements have already collapsed the state, so the bit is definite
+        if let Some(clbit) = inst.condition {
+            if measurements.bits.get(clbit) != Some(&1) {
+                return Ok(());
+            }
+        }
+
+        let (gate, qubits, clbits) = (&inst.gate, &inst.qubits, &inst.clbits);
+
         match gate.gate_type {
             // Single-qubit gates
             I | X | Y | Z | H | S | Sdg | T | Tdg | Rx | Ry | Rz | P | U => {
-                let matrix = self.get_single_qubit_matrix(gate)?;
+                let matrix = self.single_qubit_matrix(gate)?;
                 state.apply_single(qubits[0], matrix);
             }
 
@@ -186,9 +888,8 @@ impl Simulator {
             }
 
             CP => {
-                if let GateParams::Angle(theta) = gate.params {
-                    let phase = Complex::from_polar(1.0, theta);
-                    let cp_matrix = [[Complex::ONE, Complex::ZERO], [Complex::ZERO, phase]];
+                if let GateParams::Angle(_) = gate.params {
+                    let cp_matrix = self.single_qubit_matrix(gate)?;
                     state.apply_controlled(qubits[0], qubits[1], cp_matrix);
                 }
             }
@@ -203,6 +904,28 @@ impl Simulator {
                 state.apply_two(qubits[0], qubits[1], swap_matrix);
             }
 
+            ISwap => {
+                let i = Complex::I;
+                let iswap_matrix = [
+                    [Complex::ONE, Complex::ZERO, Complex::ZERO, Complex::ZERO],
+                    [Complex::ZERO, Complex::ZERO, i, Complex::ZERO],
+                    [Complex::ZERO, i, Complex::ZERO, Complex::ZERO],
+                    [Complex::ZERO, Complex::ZERO, Complex::ZERO, Complex::ONE],
+                ];
+                state.apply_two(qubits[0], qubits[1], iswap_matrix);
+            }
+
+            SqrtSwap => {
+                let (a, b) = (Complex::new(0.5, 0.5), Complex::new(0.5, -0.5));
+                let sqrt_swap_matrix = [
+                    [Complex::ONE, Complex::ZERO, Complex::ZERO, Complex::ZERO],
+                    [Complex::ZERO, a, b, Complex::ZERO],
+                    [Complex::ZERO, b, a, Complex::ZERO],
+                    [Complex::ZERO, Complex::ZERO, Complex::ZERO, Complex::ONE],
+                ];
+                state.apply_two(qubits[0], qubits[1], sqrt_swap_matrix);
+            }
+
             // Three-qubit gates (decomposed)
             CCX => {
                 // Toffoli decomposition using 6 CNOTs and single-qubit gates
@@ -242,15 +965,54 @@ impl Simulator {
         Ok(())
     }
 
-    /// Get the 2x2 matrix for a single-qubit gate.
-    fn get_single_qubit_matrix(&self, gate: &Gate) -> Result<[[Complex; 2]; 2]> {
+    /// Get the 2x2 matrix for a single-qubit gate (the target block for CP).
+    ///
+    /// Parameterized gates go through the matrix cache.
+    fn single_qubit_matrix(&mut self, gate: &Gate) -> Result<[[Complex; 2]; 2]> {
+        let key = match gate.params {
+            GateParams::None => return Self::compute_matrix(gate),
+            GateParams::Angle(theta) => (gate.gate_type, [theta.to_bits(), 0, 0]),
+            GateParams::Angles3(theta, phi, lambda) => (gate.gate_type, [theta.to_bits(), phi.to_bits(), lambda.to_bits()]),
+        };
+
+        // Runs of the same gate are the common case; skip the hash for them
+        if let Some((last, matrix)) = self.last_matrix {
+            if last == key {
+                self.cache_stats.hits += 1;
+                return Ok(matrix);
+            }
+        }
+
+        let matrix = match self.matrix_cache.get(&key) {
+            Some(matrix) => {
+                self.cache_stats.hits += 1;
+                *matrix
+            }
+            None => {
+                self.cache_stats.misses += 1;
+                let matrix = Self::compute_matrix(gate)?;
+                if self.matrix_cache.len() < MATRIX_CACHE_CAPACITY {
+                    self.matrix_cache.insert(key, matrix);
+                }
+                matrix
+            }
+        };
+        self.last_matrix = Some((key, matrix));
+        Ok(matrix)
+    }
+
+    fn compute_matrix(gate: &Gate) -> Result<[[Complex; 2]; 2]> {
+        if let (GateType::CP, GateParams::Angle(theta)) = (gate.gate_type, &gate.params) {
+            let phase = Complex::from_polar(1.0, *theta);
+            return Ok([[Complex::ONE, Complex::ZERO], [Complex::ZERO, phase]]);
+        }
         gate.matrix_2x2().ok_or(HomayaError::NotSupported {
             operation: "gate has no 2x2 matrix",
         })
     }
 
     /// Apply Toffoli (CCX) gate using decomposition.
-    fn apply_ccx(&mut self, state: &mut StateVector, c1: usize, c2: usize, target: usize) {
+    fn apply_ccx<S: GateTarget>(&mut self, state: &mut S, c1: usize, c2: usize, target: usize) {
         // Standard Toffoli decomposition
         let h = Complex::from_real(INV_SQRT_2);
         let h_matrix = [[h, h], [h, -h]];
@@ -275,11 +1037,10 @@ impl Simulator {
         state.apply_single(c2, tdg_matrix);
         state.apply_controlled(c1, c2, x_matrix);
         state.apply_single(c1, t_matrix);
-        state.apply_single(c2, [[Complex::ONE, Complex::ZERO], [Complex::ZERO, Complex::I]]);
     }
 
     /// Apply Fredkin (CSWAP) gate.
-    fn apply_cswap(&mut self, state: &mut StateVector, control: usize, t1: usize, t2: usize) {
+    fn apply_cswap<S: GateTarget>(&mut self, state: &mut S, control: usize, t1: usize, t2: usize) {
         let x_matrix = [[Complex::ZERO, Complex::ONE], [Complex::ONE, Complex::ZERO]];
 
         // CSWAP = CNOT(t2, t1) + Toffoli(control, t1, t2) + CNOT(t2, t1)
@@ -289,10 +1050,59 @@ impl Simulator {
     }
 }
 
+/// The (qubit, clbit) readout of a circuit whose measurements all come last.
+///
+/// Returns `None` if any qubit is acted on after being measured, or the
+/// circuit resets or branches, since then one simulation cannot stand in
+/// for every shot.
+fn terminal_measurements(circuit: &Circuit) -> Option<Vec<(usize, usize)>> {
+    let mut measured = vec![false; circuit.num_qubits()];
+    let mut readout = Vec::new();
+
+    for inst in circuit.instructions() {
+        match inst.gate.gate_type {
+            _ if inst.is_conditional() => return None,
+            GateType::Measure if !measured[inst.qubits[0]] => {
+                measured[inst.qubits[0]] = true;
+                if let Some(&clbit) = inst.clbits.first() {
+                    readout.push((inst.qubits[0], clbit));
+                }
+            }
+            GateType::Barrier => {}
+            GateType::Reset => return None,
+            _ if inst.qubits.iter().any(|&q| measured[q]) => return None,
+            _ => {}
+        }
+    }
+
+    Some(readout)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use homaya_core::Circuit;
+    use homaya_core::{Circuit, ConditionalGate};
+
+    #[test]
+    fn test_run_from_basis_state() {
+        let minus = StateVector::from_amplitudes(vec![
+            Complex::from_real(homaya_core::INV_SQRT_2),
+            Complex::from_real(-homaya_core::INV_SQRT_2),
+        ])
+        .unwrap();
+        let state = Simulator::new().run_from_state(&Circuit::new(1).h(0), StateVector::from_basis(1, 1)).unwrap();
+        assert!(state.approx_eq(&minus, 1e-12));
+
+        // And back: the inverse maps |−⟩ to |1⟩
+        let state = Simulator::new().run_from_state(&Circuit::new(1).h(0).inverse(), minus).unwrap();
+        assert!(state.approx_eq(&StateVector::from_basis(1, 1), 1e-12));
+    }
+
+    #[test]
+    fn test_run_from_state_width_mismatch() {
+        let result = Simulator::new().run_from_state(&Circuit::new(2).h(0), StateVector::from_basis(1, 1));
+        assert!(matches!(result, Err(HomayaError::QubitMismatch { expected: 2, got: 1 })));
+    }
 
     #[test]
     fn test_simple_circuit() {
@@ -328,6 +1138,34 @@ mod tests {
         assert!(state.probability(7) > 0.49); // |111⟩
     }
 
+    #[test]
+    fn test_ghz_helper() {
+        let state = Simulator::new().run(&homaya_core::states::ghz(3)).unwrap();
+        for i in 0..8 {
+            let expected = if i == 0 || i == 7 { 0.5 } else { 0.0 };
+            assert!((state.probability(i) - expected).abs() < 1e-10, "|{:03b}⟩", i);
+        }
+    }
+
+    #[test]
+    fn test_bell_helper_amplitudes() {
+        use homaya_core::states::{bell, BellState};
+
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        let cases = [
+            (BellState::PhiPlus, [h, 0.0, 0.0, h]),
+            (BellState::PhiMinus, [h, 0.0, 0.0, -h]),
+            (BellState::PsiPlus, [0.0, h, h, 0.0]),
+            (BellState::PsiMinus, [0.0, h, -h, 0.0]),
+        ];
+        for (which, amplitudes) in cases {
+            let state = Simulator::new().run(&bell(which)).unwrap();
+            for (i, &a) in amplitudes.iter().enumerate() {
+                assert!(state.amplitude(i).approx_eq(Complex::from_real(a), 1e-10), "{:?} |{:02b}⟩", which, i);
+            }
+        }
+    }
+
     #[test]
     fn test_measurement_sampling() {
         let circuit = Circuit::new(2)
@@ -348,6 +1186,84 @@ mod tests {
         assert!(count_11 < 600);
     }
 
+    #[test]
+    fn test_bitstring_format() {
+        // Only qubit 0 flipped: bit 0 is the rightmost character
+        let circuit = Circuit::new(3).x(0).measure_all();
+        let mut sim = Simulator::with_seed(1);
+        let counts = sim.sample(&circuit, 10).unwrap();
+        assert_eq!(counts.get("001"), Some(&10));
+
+        let (_, result) = sim.run_with_measurements(&Circuit::new(4).x(1).x(3).measure_all()).unwrap();
+        assert_eq!(result.bitstring(), "1010");
+        assert_eq!(result.as_int(), 0b1010);
+    }
+
+    #[test]
+    fn test_measure_reset_reuses_qubit() {
+        // Round 1 reads 1, the reset returns the qubit to |0⟩ so round 2 reads 0
+        let circuit = Circuit::new(1).x(0).measure_reset(0, 0).measure(0, 1);
+        let counts = Simulator::with_seed(3).sample(&circuit, 50).unwrap();
+        assert_eq!(counts.get("01"), Some(&50));
+    }
+
+    #[test]
+    fn test_reset_returns_one_to_zero() {
+        let circuit = Circuit::new(1).x(0).reset(0);
+        let state = Simulator::with_seed(9).run(&circuit).unwrap();
+        assert!((state.probability(0) - 1.0).abs() < 1e-12);
+
+        let counts = Simulator::with_seed(9).sample(&circuit.measure(0, 0), 50).unwrap();
+        assert_eq!(counts.get("0"), Some(&50));
+    }
+
+    #[test]
+    fn test_mid_circuit_measurement_collapses() {
+        // Measuring qubit 0 of a Bell pair mid-circuit fixes qubit 1 too
+        let circuit = Circuit::new(2).h(0).cx(0, 1).measure(0, 0).x(0);
+        let mut sim = Simulator::with_seed(21);
+        for _ in 0..10 {
+            let (state, result) = sim.run_with_measurements(&circuit).unwrap();
+            let bit = result.bits[0] as usize;
+            // Qubit 0 was flipped after the measurement, qubit 1 kept the outcome
+            let expected = (1 - bit) | bit << 1;
+            assert!((state.probability(expected) - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_c_if_applies_only_when_bit_set() {
+        let feed_forward = |prep: Circuit| prep.measure(0, 0).c_if(0, ConditionalGate::x(1)).measure(1, 1);
+
+        let one = Simulator::with_seed(3).sample(&feed_forward(Circuit::new(2).x(0)), 50).unwrap();
+        assert_eq!(one.get("11"), Some(&50));
+
+        let zero = Simulator::with_seed(3).sample(&feed_forward(Circuit::new(2)), 50).unwrap();
+        assert_eq!(zero.get("00"), Some(&50));
+
+        // A random bit: qubit 1 always follows it
+        let random = Simulator::with_seed(3).sample(&feed_forward(Circuit::new(2).h(0)), 200).unwrap();
+        assert_eq!(random.len(), 2);
+        assert_eq!(random["00"] + random["11"], 200);
+    }
+
+    #[test]
+    fn test_measure_parity() {
+        // q0 = |1⟩, (q1, q2) in a Bell pair: q1 ⊕ q2 = 0 and q0 ⊕ q1 ⊕ q2 = 1
+        let circuit = Circuit::new(3)
+            .x(0)
+            .h(1)
+            .cx(1, 2)
+            .measure_parity(&[1, 2], 0)
+            .measure_parity(&[0, 1, 2], 1);
+
+        let mut sim = Simulator::with_seed(7);
+        for _ in 0..100 {
+            let (_, result) = sim.run_with_measurements(&circuit).unwrap();
+            assert_eq!(result.bits, vec![0, 1]);
+        }
+    }
+
     #[test]
     fn test_rotation_gates() {
         use std::f64::consts::PI;
@@ -362,15 +1278,137 @@ mod tests {
     }
 
     #[test]
-    fn test_toffoli_truth_table() {
+    fn test_ry_half_pi_gives_equal_superposition() {
+        use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+        let state = Simulator::new().run(&Circuit::new(1).ry(PI / 2.0, 0)).unwrap();
+        assert!(state.amplitude(0).approx_eq(Complex::from_real(FRAC_1_SQRT_2), 1e-10));
+        assert!(state.amplitude(1).approx_eq(Complex::from_real(FRAC_1_SQRT_2), 1e-10));
+    }
+
+    #[test]
+    fn test_rz_relative_phase() {
+        let theta = 0.7;
+        let state = Simulator::new().run(&Circuit::new(1).h(0).rz(theta, 0)).unwrap();
+
+        // Rz(θ) = diag(e^{-iθ/2}, e^{iθ/2}): populations unchanged, |1⟩ leads |0⟩ by θ
+        assert!((state.probability(0) - 0.5).abs() < 1e-10);
+        assert!((state.probability(1) - 0.5).abs() < 1e-10);
+        let relative = state.amplitude(1) / state.amplitude(0);
+        assert!(relative.approx_eq(Complex::from_polar(1.0, theta), 1e-10));
+    }
+
+    #[test]
+    fn test_circuit_then_inverse_is_identity() {
+        let circuit = Circuit::new(3)
+            .h(0)
+            .rx(0.7, 1)
+            .ry(-1.3, 2)
+            .cx(0, 1)
+            .rz(2.1, 1)
+            .s(2)
+            .t(0)
+            .cp(0.9, 1, 2)
+            .ccx(0, 1, 2)
+            .u(0.3, 0.5, 0.8, 0);
+
+        let state = Simulator::new().run(&circuit.clone().append(&circuit.inverse())).unwrap();
+        assert!((state.probability(0) - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_iswap_then_inverse_is_identity() {
+        let prep = Circuit::new(2).ry(0.7, 0).h(1).t(1);
+        for gate in ["iswap", "sqrt_swap"] {
+            let source = format!("OPENQASM 2.0;\ninclude \"qelib1.inc\";\nqreg q[2];\n{} q[0],q[1];\n", gate);
+            let circuit = Circuit::from_qasm(&source).unwrap();
+            let mut sim = Simulator::new();
+
+            let start = sim.run(&prep).unwrap();
+            let moved = sim.run(&prep.clone().append(&circuit)).unwrap();
+            let back = sim.run(&prep.clone().append(&circuit).append(&circuit.inverse())).unwrap();
+            assert!(moved.fidelity(&start) < 0.99, "{} should change this state", gate);
+            assert!(back.approx_eq(&start, 1e-10), "{}", gate);
+        }
+    }
+
+    #[test]
+    fn test_sqrt_swap_squares_to_swap() {
+        let source = "OPENQASM 2.0;\ninclude \"qelib1.inc\";\nqreg q[2];\nsqrt_swap q[0],q[1];\nsqrt_swap q[0],q[1];\n";
+        let state = Simulator::new().run(&Circuit::new(2).x(0).append(&Circuit::from_qasm(source).unwrap())).unwrap();
+        assert!((state.probability(0b10) - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_phase_gate_matches_z_and_s() {
+        use std::f64::consts::PI;
+
         let mut sim = Simulator::new();
+        let prep = Circuit::new(1).h(0).t(0);
 
-        // CCX flips target only when both controls are |1⟩
-        // Input: |110⟩ → Output: |111⟩
-        let circuit = Circuit::new(3).x(0).x(1).ccx(0, 1, 2);
-        let state = sim.run(&circuit).unwrap();
+        let p_pi = sim.run(&prep.clone().p(PI, 0)).unwrap();
+        let z = sim.run(&prep.clone().z(0)).unwrap();
+        assert!(p_pi.inner_product(&z).approx_eq(Complex::ONE, 1e-10));
+
+        let p_half_pi = sim.run(&prep.clone().p(PI / 2.0, 0)).unwrap();
+        let s = sim.run(&prep.s(0)).unwrap();
+        assert!(p_half_pi.inner_product(&s).approx_eq(Complex::ONE, 1e-10));
+    }
+
+    #[test]
+    fn test_tensor_gives_product_state() {
+        let bell = Circuit::new(2).h(0).cx(0, 1);
+        let plus = Circuit::new(1).h(0);
+
+        let mut sim = Simulator::new();
+        let bell_state = sim.run(&bell).unwrap();
+        let plus_state = sim.run(&plus).unwrap();
+        let combined = sim.run(&bell.tensor(&plus)).unwrap();
+
+        // |+⟩ sits on the new top qubit: amplitude(i) = plus(i >> 2) · bell(i & 0b11)
+        for i in 0..8 {
+            let expected = plus_state.get(i >> 2) * bell_state.get(i & 0b11);
+            assert!(combined.get(i).approx_eq(expected, 1e-10), "amplitude {}", i);
+        }
+    }
+
+    #[test]
+    fn test_toffoli_truth_table() {
+        // CCX flips target only when both controls are |1⟩, and leaves the
+        // amplitude at exactly 1: a stray phase on any input (the old
+        // decomposition put i on every |x1x⟩) fails here
+        for (c1, c2, target) in [(0, 1, 2), (2, 0, 1), (1, 2, 0)] {
+            for input in 0..8usize {
+                let prep = (0..3).filter(|&q| (input >> q) & 1 == 1).fold(Circuit::new(3), |c, q| c.x(q));
+                let state = Simulator::new().run(&prep.ccx(c1, c2, target)).unwrap();
+
+                let flip = (input >> c1) & (input >> c2) & 1;
+                let output = input ^ (flip << target);
+                assert!(
+                    state.get(output).approx_eq(Complex::ONE, 1e-10),
+                    "ccx({}, {}, {}) on |{:03b}⟩: amplitude of |{:03b}⟩ is {:?}",
+                    c1,
+                    c2,
+                    target,
+                    input,
+                    output,
+                    state.get(output)
+                );
+            }
+        }
+    }
 
-        assert!(state.probability(0b111) > 0.99);
+    #[test]
+    fn test_toffoli_has_no_relative_phase() {
+        // CCX permutes basis states without touching their phases:
+        // H⊗3 then CCX must leave every amplitude real and equal
+        let circuit = Circuit::new(3).h(0).h(1).h(2).ccx(0, 1, 2);
+        let state = Simulator::new().run(&circuit).unwrap();
+
+        let expected = Complex::from_real(0.125f64.sqrt());
+        for i in 0..8 {
+            assert!(state.get(i).approx_eq(expected, 1e-10), "amplitude {}: {:?}", i, state.get(i));
+        }
     }
 
     #[test]
@@ -382,4 +1420,470 @@ mod tests {
         // |01⟩ → |10⟩
         assert!(state.probability(0b10) > 0.99);
     }
+
+    #[test]
+    fn test_swap_decomposed_matches_swap() {
+        let mut sim = Simulator::new();
+        let prep = Circuit::new(2).ry(0.4, 0).rx(1.2, 1).cx(0, 1);
+        let swapped = sim.run(&prep.clone().swap(0, 1)).unwrap();
+        let decomposed = sim.run(&prep.swap_decomposed(0, 1)).unwrap();
+        assert!(swapped.inner_product(&decomposed).approx_eq(Complex::ONE, 1e-10));
+    }
+
+    #[test]
+    fn test_mcx_four_controls_restores_ancillas() {
+        let mut sim = Simulator::new();
+        for input in 0..16usize {
+            // Controls 0-3, target 4, ancillas 5 and 6
+            let mut circuit = Circuit::new(7);
+            for q in (0..4).filter(|&q| (input >> q) & 1 == 1) {
+                circuit = circuit.x(q);
+            }
+            let state = sim.run(&circuit.mcx(&[0, 1, 2, 3], 4, &[5, 6])).unwrap();
+
+            let expected = if input == 0b1111 { input | 1 << 4 } else { input };
+            assert!((state.probability(expected) - 1.0).abs() < 1e-10, "input {:04b}", input);
+        }
+    }
+
+    #[test]
+    fn test_mcz_flips_only_all_ones() {
+        let mut sim = Simulator::new();
+        // Uniform superposition on 4 qubits, one ancilla on qubit 4
+        let prep = (0..4).fold(Circuit::new(5), |c, q| c.h(q));
+        let state = sim.run(&prep.mcz(&[0, 1, 2, 3], &[4])).unwrap();
+
+        for i in 0..16 {
+            let sign = if i == 0b1111 { -1.0 } else { 1.0 };
+            assert!(state.amplitude(i).approx_eq(Complex::new(sign * 0.25, 0.0), 1e-10), "basis {:04b}", i);
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_histogram_mid_circuit() {
+        // Mid-circuit measurement, reset and a condition force per-shot reruns
+        let circuit = Circuit::new(2)
+            .h(0)
+            .measure(0, 0)
+            .reset(0)
+            .h(0)
+            .c_if(0, ConditionalGate::x(1))
+            .measure(0, 1)
+            .measure(1, 2);
+        let config = SimConfig { seed: Some(42), shots: 300 };
+
+        let a = Simulator::with_config(config).sample_configured(&circuit).unwrap();
+        let b = Simulator::with_config(config).sample_configured(&circuit).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.values().sum::<usize>(), 300);
+
+        let other = Simulator::with_config(SimConfig { seed: Some(43), ..config })
+            .sample_configured(&circuit)
+            .unwrap();
+        assert_ne!(a, other);
+    }
+
+    #[test]
+    fn test_zero_seed_still_random() {
+        let circuit = Circuit::new(1).h(0).measure(0, 0);
+        let counts = Simulator::with_seed(0).sample(&circuit, 200).unwrap();
+        assert_eq!(counts.len(), 2, "{:?}", counts);
+    }
+
+    #[test]
+    fn test_t_squared_is_s() {
+        let mut sim = Simulator::new();
+        let tt = sim.run(&Circuit::new(1).x(0).t(0).t(0)).unwrap();
+        let s = sim.run(&Circuit::new(1).x(0).s(0)).unwrap();
+
+        // S|1⟩ = i|1⟩
+        assert!(tt.amplitude(1).approx_eq(Complex::I, 1e-10));
+        assert!(tt.inner_product(&s).approx_eq(Complex::ONE, 1e-10));
+
+        let undone = sim.run(&Circuit::new(1).x(0).t(0).s(0).tdg(0).sdg(0)).unwrap();
+        assert!(undone.amplitude(1).approx_eq(Complex::ONE, 1e-10));
+    }
+
+    #[test]
+    fn test_cz_flips_sign_of_11() {
+        let mut sim = Simulator::new();
+        for (input, sign) in [(0b00, 1.0), (0b01, 1.0), (0b10, 1.0), (0b11, -1.0)] {
+            let mut prep = Circuit::new(2);
+            for q in (0..2).filter(|&q| (input >> q) & 1 == 1) {
+                prep = prep.x(q);
+            }
+            let state = sim.run(&prep.cz(0, 1)).unwrap();
+            assert!(state.amplitude(input).approx_eq(Complex::from_real(sign), 1e-10), "input {:02b}", input);
+        }
+    }
+
+    #[test]
+    fn test_cp_pi_is_cz() {
+        use std::f64::consts::PI;
+
+        let mut sim = Simulator::new();
+        for input in 0..4 {
+            let mut prep = Circuit::new(2);
+            for q in (0..2).filter(|&q| (input >> q) & 1 == 1) {
+                prep = prep.x(q);
+            }
+            let cp = sim.run(&prep.clone().cp(PI, 0, 1)).unwrap();
+            let cz = sim.run(&prep.cz(0, 1)).unwrap();
+            assert!(cp.inner_product(&cz).approx_eq(Complex::ONE, 1e-10), "input {:02b}", input);
+        }
+    }
+
+    #[test]
+    fn test_f32_matches_f64_within_tolerance() {
+        // 10 qubits, ~100 gates of every simulated kind
+        let mut circuit = Circuit::new(10);
+        for layer in 0..4 {
+            for q in 0..10 {
+                circuit = circuit.h(q).rz(0.37 * (q + layer) as f64, q).ry(0.11 * q as f64, q);
+            }
+            for q in 0..9 {
+                circuit = circuit.cx(q, q + 1).cp(0.5, q + 1, q);
+            }
+            circuit = circuit.ccx(0, 4, 9).swap(2, 7);
+        }
+
+        let exact = Simulator::new().run(&circuit).unwrap();
+        let single = Simulator::new().run_f32(&circuit).unwrap();
+
+        for (p64, p32) in exact.probabilities().iter().zip(single.probabilities()) {
+            assert!((p64 - p32).abs() < 1e-5, "{} vs {}", p64, p32);
+        }
+        assert!(exact.fidelity(&single.to_f64()) > 1.0 - 1e-4);
+    }
+
+    #[test]
+    fn test_sample_f32_matches_sample() {
+        let circuit = Circuit::new(3).h(0).cx(0, 1).cx(1, 2).measure_all();
+        let counts = Simulator::with_seed(5).sample_f32(&circuit, 200).unwrap();
+
+        assert_eq!(counts.values().sum::<usize>(), 200);
+        assert!(counts.keys().all(|bits| bits == "000" || bits == "111"));
+    }
+
+    #[test]
+    fn test_sample_follows_measure_register_order() {
+        // Qubit 0 (in |1⟩) is read into clbit 2, qubit 2 into clbit 0
+        let circuit = Circuit::new(3).x(0).measure(0, 2).measure(1, 1).measure(2, 0);
+        let counts = Simulator::sample_seeded(&circuit, 20, 1).unwrap();
+        assert_eq!(counts.get("100"), Some(&20));
+    }
+
+    #[test]
+    fn test_terminal_measurements() {
+        let terminal = Circuit::new(2).h(0).measure(0, 0).barrier_all().h(1).measure(1, 1);
+        assert_eq!(terminal_measurements(&terminal), Some(vec![(0, 0), (1, 1)]));
+
+        let mid_circuit = Circuit::new(1).h(0).measure(0, 0).h(0);
+        assert_eq!(terminal_measurements(&mid_circuit), None);
+    }
+
+    #[test]
+    fn test_measurement_result_to_qiskit_json() {
+        let result = MeasurementResult { bits: vec![1, 1, 0] };
+        let json = result.to_qiskit_json();
+        assert!(json.contains("\"counts\": {\"0x3\": 1}"));
+        assert!(json.contains("\"memory_slots\": 3"));
+    }
+
+    #[test]
+    fn test_cached_matrices_match_uncached() {
+        let mut circuit = Circuit::new(2).h(0).h(1);
+        for i in 0..1000 {
+            circuit = circuit.rz(PI / 7.0, i % 2).cp(PI / 3.0, 0, 1);
+        }
+        circuit = circuit.u(0.3, 0.2, 0.1, 0).rx(0.4, 1);
+
+        let mut sim = Simulator::new();
+        let cached = sim.run(&circuit).unwrap();
+
+        // Apply every gate with a freshly computed matrix
+        let mut uncached = StateVector::new(2);
+        for inst in circuit.instructions() {
+            let matrix = Simulator::compute_matrix(&inst.gate).unwrap();
+            match inst.qubits[..] {
+                [q] => uncached.apply_single(q, matrix),
+                [c, t] => uncached.apply_controlled(c, t, matrix),
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(cached.amplitudes(), uncached.amplitudes());
+
+        // rz, cp, u and rx each miss once; H is never cached
+        assert_eq!(sim.cache_stats(), CacheStats { hits: 1998, misses: 4 });
+        sim.run(&circuit).unwrap();
+        assert_eq!(sim.cache_stats().misses, 4);
+    }
+
+    #[test]
+    fn test_cache_stops_growing_at_capacity() {
+        let mut circuit = Circuit::new(1);
+        for i in 0..MATRIX_CACHE_CAPACITY + 10 {
+            circuit = circuit.rz(i as f64 * 1e-3, 0);
+        }
+        let mut sim = Simulator::new();
+        sim.run(&circuit).unwrap();
+        sim.run(&circuit).unwrap();
+
+        assert_eq!(sim.matrix_cache.len(), MATRIX_CACHE_CAPACITY);
+        assert_eq!(sim.cache_stats().hits, MATRIX_CACHE_CAPACITY as u64);
+    }
+
+    #[test]
+    fn test_run_noisy_without_noise_matches_sample() {
+        let circuit = Circuit::new(3).h(0).cx(0, 1).rx(0.7, 2).measure_all();
+        let noisy = Simulator::with_seed(9).run_noisy(&circuit, &NoiseModel::new(), 500).unwrap();
+        let clean = Simulator::with_seed(9).sample(&circuit, 500).unwrap();
+        assert_eq!(noisy, clean);
+    }
+
+    #[test]
+    fn test_run_noisy_errors() {
+        // Certain readout error inverts every bit without touching the state
+        let circuit = Circuit::new(2).x(0).measure_all();
+        let flipped = NoiseModel::new().with_readout_error(1.0);
+        let counts = Simulator::with_seed(2).run_noisy(&circuit, &flipped, 50).unwrap();
+        assert_eq!(counts.get("10"), Some(&50));
+
+        // Full depolarizing after X leaves qubit 0 at 1 only under Z (1 in 3)
+        let depolarized = NoiseModel::new().with_depolarizing(1.0);
+        let counts = Simulator::with_seed(2).run_noisy(&Circuit::new(1).x(0).measure(0, 0), &depolarized, 3000).unwrap();
+        let ones = counts.get("1").copied().unwrap_or(0);
+        assert!((900..1100).contains(&ones), "{:?}", counts);
+
+        // Repeatable under a seed
+        let again = Simulator::with_seed(2).run_noisy(&Circuit::new(1).x(0).measure(0, 0), &depolarized, 3000).unwrap();
+        assert_eq!(counts, again);
+    }
+
+    #[test]
+    fn test_outcome_probabilities_follow_clbits() {
+        // Qubit 1 lands in clbit 0, qubit 0 in clbit 1; qubit 2 is not read
+        let circuit = Circuit::with_clbits(3, 2).x(1).h(2).measure(1, 0).measure(0, 1);
+        let probabilities = Simulator::new().outcome_probabilities(&circuit).unwrap();
+        assert_eq!(probabilities.len(), 1);
+        assert!((probabilities["01"] - 1.0).abs() < 1e-12);
+
+        let mid_circuit = Circuit::new(1).measure(0, 0).x(0).measure(0, 0);
+        assert!(matches!(
+            Simulator::new().outcome_probabilities(&mid_circuit),
+            Err(HomayaError::NotSupported { .. })
+        ));
+    }
+
+    #[test]
+    fn test_success_probability_and_fidelity() {
+        let circuit = Circuit::new(2).h(0).cx(0, 1).measure_all();
+        let mut sim = Simulator::new();
+        assert!((sim.success_probability(&circuit, "00").unwrap() - 0.5).abs() < 1e-12);
+        assert_eq!(sim.success_probability(&circuit, "10").unwrap(), 0.0);
+        assert_eq!(sim.success_probability(&circuit, "000").unwrap(), 0.0);
+
+        let uniform: std::collections::HashMap<String, f64> =
+            ["00", "01", "10", "11"].iter().map(|bits| (bits.to_string(), 0.25)).collect();
+        assert!((sim.algorithm_fidelity(&circuit, &uniform).unwrap() - 0.5).abs() < 1e-12);
+
+        let wrong = std::collections::HashMap::from([("01".to_string(), 1.0)]);
+        assert_eq!(sim.algorithm_fidelity(&circuit, &wrong).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_batch_matches_individual_runs() {
+        let circuits = [
+            Circuit::new(2).h(0).cx(0, 1).measure_all(),
+            Circuit::new(2).x(1).measure_all(),
+            Circuit::new(2).h(0).h(1).measure_all(),
+        ];
+
+        let batch = Simulator::with_seed(6).sample_batch(&circuits, 200).unwrap();
+        assert_eq!(batch.len(), 3);
+        for (circuit, counts) in circuits.iter().zip(&batch) {
+            assert_eq!(counts, &Simulator::with_seed(6).sample(circuit, 200).unwrap());
+        }
+
+        let states = Simulator::new().run_batch(&circuits[1..2]).unwrap();
+        assert_eq!(states[0].most_likely(), (0b10, 1.0));
+        assert!(Simulator::new().run_batch(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_measurement_result_accessors() {
+        let result = MeasurementResult::from_bitstring("000").unwrap();
+        assert!(result.all_zero());
+        assert_eq!(result.bits.len(), 3);
+
+        let result = MeasurementResult::from_bitstring("0101").unwrap();
+        assert!(!result.all_zero());
+        assert_eq!((0..4).map(|i| result.bit(i)).collect::<Vec<_>>(), [true, false, true, false]);
+        assert_eq!(result.bitstring_with(BitOrder::BigEndian), "1010");
+
+        assert!(MeasurementResult::from_bitstring("").is_none());
+        assert!(MeasurementResult::from_bitstring("012").is_none());
+    }
+
+    #[test]
+    fn test_big_endian_keys_reverse() {
+        let circuit = Circuit::new(3).h(0).x(1).measure_all();
+        let little = Simulator::with_seed(4).sample(&circuit, 100).unwrap();
+        let big = Simulator::with_seed(4).with_bit_order(BitOrder::BigEndian).sample(&circuit, 100).unwrap();
+
+        assert_eq!(little.len(), 2);
+        for (bits, count) in &little {
+            let reversed: String = bits.chars().rev().collect();
+            assert_eq!(big.get(&reversed), Some(count), "{} vs {:?}", bits, big);
+        }
+        assert!(big.contains_key("110") && little.contains_key("011"));
+
+        let mut sim = Simulator::new().with_bit_order(BitOrder::BigEndian);
+        assert!((sim.success_probability(&circuit, "010").unwrap() - 0.5).abs() < 1e-12);
+    }
+
+    /// Apply `matrix` to `target` where both `c1` and `c2` are |1⟩.
+    fn apply_doubly_controlled(state: &mut StateVector, c1: usize, c2: usize, target: usize, matrix: [[Complex; 2]; 2]) {
+        let controls = (1 << c1) | (1 << c2);
+        let amplitudes = state.amplitudes_mut();
+        for i0 in 0..amplitudes.len() {
+            if i0 & controls == controls && i0 & (1 << target) == 0 {
+                let i1 = i0 | (1 << target);
+                let (a0, a1) = (amplitudes[i0], amplitudes[i1]);
+                amplitudes[i0] = matrix[0][0] * a0 + matrix[0][1] * a1;
+                amplitudes[i1] = matrix[1][0] * a0 + matrix[1][1] * a1;
+            }
+        }
+    }
+
+    #[test]
+    fn test_controlled_x_is_cx_on_both_control_values() {
+        let controlled = Circuit::new(1).x(0).controlled(1).unwrap();
+        for control in [false, true] {
+            let mut prep = Circuit::new(2);
+            if control {
+                prep = prep.x(1);
+            }
+            let state = Simulator::new().run(&prep.clone().append(&controlled)).unwrap();
+            let expected = Simulator::new().run(&prep.cx(1, 0)).unwrap();
+            assert!(state.approx_eq(&expected, 1e-12), "control = {}", control);
+            assert_eq!(state.most_likely().0, if control { 0b11 } else { 0b00 });
+        }
+    }
+
+    #[test]
+    fn test_controlled_matches_definition() {
+        // A generic state on 3 qubits; qubit 2 is the control
+        let prep = Circuit::new(3).ry(0.3, 0).ry(1.1, 1).ry(2.0, 2).cx(0, 1).rz(0.7, 2).cx(2, 0);
+        let start = Simulator::new().run(&prep).unwrap();
+
+        let single = [
+            Gate::x(), Gate::y(), Gate::z(), Gate::h(), Gate::s(), Gate::sdg(), Gate::t(), Gate::tdg(),
+            Gate::rx(0.9), Gate::ry(-1.3), Gate::rz(2.2), Gate::p(0.4), Gate::u(0.8, 1.9, -0.6),
+        ];
+        for gate in single {
+            let circuit = Circuit::new(2).try_apply(gate.clone(), &[1]).unwrap();
+            let state = Simulator::new().run_from_state(&circuit.controlled(2).unwrap(), start.clone()).unwrap();
+            let mut expected = start.clone();
+            expected.apply_controlled(2, 1, gate.matrix_2x2().unwrap());
+            assert!(state.approx_eq(&expected, 1e-10), "{:?}", gate);
+        }
+
+        let (o, z, i) = (Complex::ONE, Complex::ZERO, Complex::I);
+        let h = Complex::from_real(INV_SQRT_2);
+        let two = [
+            (Gate::cx(), [[z, o], [o, z]]),
+            (Gate::cy(), [[z, -i], [i, z]]),
+            (Gate::cz(), [[o, z], [z, -o]]),
+            (Gate::ch(), [[h, h], [h, -h]]),
+            (Gate::cp(1.7), [[o, z], [z, Complex::from_polar(1.0, 1.7)]]),
+        ];
+        for (gate, matrix) in two {
+            let circuit = Circuit::new(2).try_apply(gate.clone(), &[0, 1]).unwrap();
+            let state = Simulator::new().run_from_state(&circuit.controlled(2).unwrap(), start.clone()).unwrap();
+            let mut expected = start.clone();
+            apply_doubly_controlled(&mut expected, 2, 0, 1, matrix);
+            assert!(state.approx_eq(&expected, 1e-10), "{:?}", gate);
+        }
+    }
+
+    #[test]
+    fn test_controlled_ccx_matches_definition() {
+        // Qubit 3 controls a Toffoli on 0, 1 → 2; a generic start state
+        let prep = Circuit::new(4).ry(0.3, 0).ry(1.1, 1).ry(2.0, 2).ry(0.6, 3).cx(0, 1).rz(0.7, 2).cx(3, 0);
+        let start = Simulator::new().run(&prep).unwrap();
+        let controlled = Circuit::new(3).ccx(0, 1, 2).controlled(3).unwrap();
+        let state = Simulator::new().run_from_state(&controlled, start.clone()).unwrap();
+
+        // Only |1011⟩ and |1111⟩ trade places
+        let mut expected = start;
+        expected.amplitudes_mut().swap(0b1011, 0b1111);
+        assert!(state.approx_eq(&expected, 1e-10));
+    }
+
+    #[test]
+    fn test_power_of_x() {
+        let x = Circuit::new(1).x(0);
+        let squared = Simulator::new().run(&x.power(2).unwrap()).unwrap();
+        assert!(squared.approx_eq(&StateVector::new(1), 1e-12));
+
+        let cubed = Simulator::new().run(&x.power(3).unwrap()).unwrap();
+        assert!((cubed.probability(1) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_decomposed_ccx_matches_toffoli() {
+        let decomposed = Circuit::new(3).ccx(0, 1, 2).decompose_ccx();
+        assert!(decomposed.gates().all(|gate| gate.gate_type != GateType::CCX));
+
+        // Truth table: every basis input maps to the same basis output
+        for input in 0..8 {
+            let mut prep = Circuit::new(3);
+            for q in (0..3).filter(|&q| (input >> q) & 1 == 1) {
+                prep = prep.x(q);
+            }
+            let start = Simulator::new().run(&prep).unwrap();
+            let expected = Simulator::new().run_from_state(&Circuit::new(3).ccx(0, 1, 2), start.clone()).unwrap();
+            let state = Simulator::new().run_from_state(&decomposed, start).unwrap();
+            assert!(state.approx_eq(&expected, 1e-12), "input {:03b}", input);
+        }
+
+        // Relative phases survive too
+        let prep = Circuit::new(3).h(0).ry(0.8, 1).h(2).t(2);
+        let start = Simulator::new().run(&prep).unwrap();
+        let expected = Simulator::new().run_from_state(&Circuit::new(3).ccx(0, 1, 2), start.clone()).unwrap();
+        let state = Simulator::new().run_from_state(&decomposed, start).unwrap();
+        assert!(state.approx_eq(&expected, 1e-12));
+    }
+
+    #[test]
+    fn test_untracked_global_phase_is_normalized() {
+        // Y, S and Rz all leave complex phases on the surviving amplitudes
+        let circuit = Circuit::new(2).h(0).y(1).rz(0.7, 0).s(1).cx(0, 1);
+        let tracked = Simulator::new().run(&circuit).unwrap();
+        let mut sim = Simulator::new().track_global_phase(false);
+        assert!(Simulator::new().tracks_global_phase() && !sim.tracks_global_phase());
+        let normalized = sim.run(&circuit).unwrap();
+
+        let (leading, amp) = (0..4).map(|i| (i, normalized.amplitude(i))).find(|(_, a)| a.abs() > 1e-10).unwrap();
+        assert!(amp.re > 0.0 && amp.im.abs() < 1e-12, "{:?}", amp);
+        assert!(tracked.amplitude(leading).im.abs() > 0.1, "circuit should carry a phase");
+
+        assert!(normalized.approx_eq_up_to_global_phase(&tracked, 1e-12));
+        for i in 0..4 {
+            assert!((normalized.probability(i) - tracked.probability(i)).abs() < 1e-12);
+        }
+
+        let (state, _) = sim.run_with_measurements(&circuit).unwrap();
+        assert!(state.approx_eq(&normalized, 1e-12));
+    }
+
+    #[test]
+    fn test_measurement_into_same_clbit_last_write_wins() {
+        // Qubit 0 reads 1 into bit 0, then qubit 1 overwrites it with 0
+        let circuit = Circuit::new(2).x(0).measure(0, 0).measure(1, 0);
+        let (_, result) = Simulator::new().run_with_measurements(&circuit).unwrap();
+        assert!(!result.bit(0));
+        assert_eq!(Simulator::with_seed(1).sample(&circuit, 10).unwrap().get("0"), Some(&10));
+    }
 }
@@ -0,0 +1,464 @@
+//! Sparse state simulation.
+//!
+//! Stores only the non-zero amplitudes, so circuits that keep the state on a
+//! handful of basis states (GHZ preparation, arithmetic on basis inputs,
+//! classical oracles) run in memory proportional to that handful rather
+//! than 2^n.
+//!
+//! A Hadamard on every qubit populates all 2^n entries, which is worse than
+//! dense: a hash map entry costs several times a dense amplitude. The
+//! simulator therefore switches a run to a dense [`StateVector`] once
+//! occupancy passes a configurable fraction of 2^n. Bernstein-Vazirani and
+//! Deutsch-Jozsa hit that switch right after their first Hadamard layer, so
+//! they run correctly here but need as much memory as with [`Simulator`].
+
+use std::collections::HashMap;
+
+use homaya_core::{Circuit, Complex, HomayaError, Result};
+
+use crate::simulator::GateTarget;
+use crate::{SimConfig, Simulator, StateVector};
+
+/// Amplitudes with |a|² below this are dropped as rounding noise.
+const PRUNE_NORM_SQR: f64 = 1e-24;
+
+/// A quantum state stored as its non-zero amplitudes.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_sim::SparseState;
+///
+/// let state = SparseState::new(50);
+/// assert_eq!(state.num_nonzero(), 1);
+/// assert_eq!(state.probability(0), 1.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SparseState {
+    /// Number of qubits
+    num_qubits: usize,
+    /// Non-zero amplitudes by basis state
+    amplitudes: HashMap<usize, Complex>,
+}
+
+impl SparseState {
+    /// Largest register a `usize` basis index can address.
+    pub const MAX_QUBITS: usize = usize::BITS as usize - 1;
+
+    /// Create |0...0⟩ on `num_qubits` qubits.
+    pub fn new(num_qubits: usize) -> Self {
+        Self {
+            num_qubits,
+            amplitudes: HashMap::from([(0, Complex::ONE)]),
+        }
+    }
+
+    /// Copy the non-zero amplitudes of a dense state.
+    pub fn from_dense(state: &StateVector) -> Self {
+        let amplitudes = state
+            .amplitudes()
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| a.norm_sqr() >= PRUNE_NORM_SQR)
+            .map(|(i, &a)| (i, a))
+            .collect();
+        Self {
+            num_qubits: state.num_qubits(),
+            amplitudes,
+        }
+    }
+
+    /// Expand into a dense state vector.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HomayaError::CircuitTooLarge`] beyond
+    /// [`StateVector::MAX_QUBITS`].
+    pub fn to_dense(&self) -> Result<StateVector> {
+        if self.num_qubits > StateVector::MAX_QUBITS {
+            return Err(HomayaError::CircuitTooLarge {
+                qubits: self.num_qubits,
+                max: StateVector::MAX_QUBITS,
+            });
+        }
+
+        let mut state = StateVector::new(self.num_qubits);
+        state.set(0, Complex::ZERO);
+        for (&i, &a) in &self.amplitudes {
+            state.set(i, a);
+        }
+        Ok(state)
+    }
+
+    /// Get the number of qubits.
+    pub fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    /// Number of stored (non-zero) amplitudes.
+    pub fn num_nonzero(&self) -> usize {
+        self.amplitudes.len()
+    }
+
+    /// Get the amplitude of a computational basis state.
+    pub fn amplitude(&self, basis_state: usize) -> Complex {
+        self.amplitudes.get(&basis_state).copied().unwrap_or(Complex::ZERO)
+    }
+
+    /// Get the probability of measuring a specific basis state.
+    pub fn probability(&self, basis_state: usize) -> f64 {
+        self.amplitude(basis_state).norm_sqr()
+    }
+
+    /// Non-zero amplitudes in ascending basis order.
+    pub fn nonzero(&self) -> Vec<(usize, Complex)> {
+        let mut entries: Vec<_> = self.amplitudes.iter().map(|(&i, &a)| (i, a)).collect();
+        entries.sort_unstable_by_key(|&(i, _)| i);
+        entries
+    }
+
+    /// Replace the amplitudes with `out`, dropping rounding noise.
+    fn store(&mut self, mut out: HashMap<usize, Complex>) {
+        out.retain(|_, a| a.norm_sqr() >= PRUNE_NORM_SQR);
+        self.amplitudes = out;
+    }
+
+    /// Apply a 2^k × 2^k matrix on `qubits` to every entry accepted by `acts`.
+    ///
+    /// Row and column indices pack the listed qubits' bits, first qubit
+    /// lowest, matching [`StateVector::apply_two`].
+    fn apply_matrix<const N: usize>(&mut self, qubits: &[usize], matrix: &[[Complex; N]; N], acts: impl Fn(usize) -> bool) {
+        let mask: usize = qubits.iter().map(|&q| 1 << q).sum();
+        let spread = |local: usize| -> usize {
+            qubits
+                .iter()
+                .enumerate()
+                .filter(|&(bit, _)| (local >> bit) & 1 == 1)
+                .map(|(_, &q)| 1 << q)
+                .sum()
+        };
+
+        let mut out = HashMap::with_capacity(self.amplitudes.len());
+        for (&i, &a) in &self.amplitudes {
+            if !acts(i) {
+                *out.entry(i).or_insert(Complex::ZERO) += a;
+                continue;
+            }
+            let column = qubits.iter().enumerate().map(|(bit, &q)| ((i >> q) & 1) << bit).sum::<usize>();
+            let base = i & !mask;
+            for (row, matrix_row) in matrix.iter().enumerate() {
+                let factor = matrix_row[column];
+                if factor != Complex::ZERO {
+                    *out.entry(base | spread(row)).or_insert(Complex::ZERO) += factor * a;
+                }
+            }
+        }
+        self.store(out);
+    }
+}
+
+impl GateTarget for SparseState {
+    fn apply_single(&mut self, qubit: usize, matrix: [[Complex; 2]; 2]) {
+        self.apply_matrix(&[qubit], &matrix, |_| true);
+    }
+
+    fn apply_two(&mut self, q0: usize, q1: usize, matrix: [[Complex; 4]; 4]) {
+        self.apply_matrix(&[q0, q1], &matrix, |_| true);
+    }
+
+    fn apply_controlled(&mut self, control: usize, target: usize, matrix: [[Complex; 2]; 2]) {
+        self.apply_matrix(&[target], &matrix, |i| (i >> control) & 1 == 1);
+    }
+
+    fn measure(&mut self, qubit: usize, random: f64) -> u8 {
+        let prob_0: f64 = self
+            .amplitudes
+            .iter()
+            .filter(|(&i, _)| (i >> qubit) & 1 == 0)
+            .map(|(_, a)| a.norm_sqr())
+            .sum();
+
+        // Same convention as the dense state, so seeded runs agree
+        let result = if random < prob_0 { 0 } else { 1 };
+        let norm = if result == 0 { prob_0 } else { 1.0 - prob_0 };
+        let inv_sqrt_norm = 1.0 / norm.sqrt();
+
+        self.amplitudes.retain(|&i, _| (i >> qubit) & 1 == result);
+        for a in self.amplitudes.values_mut() {
+            *a = *a * inv_sqrt_norm;
+        }
+        result as u8
+    }
+
+    fn reset(&mut self, qubit: usize, random: f64) {
+        if self.measure(qubit, random) == 1 {
+            let x_matrix = [[Complex::ZERO, Complex::ONE], [Complex::ONE, Complex::ZERO]];
+            self.apply_single(qubit, x_matrix);
+        }
+    }
+
+    fn distribution(&self) -> Vec<(usize, f64)> {
+        self.nonzero().into_iter().map(|(i, a)| (i, a.norm_sqr())).collect()
+    }
+}
+
+/// A sparse state that turns dense once it fills up.
+#[derive(Clone, Debug)]
+enum HybridState {
+    /// Still sparse; densify above this many entries
+    Sparse(SparseState, usize),
+    /// Switched to a full state vector
+    Dense(StateVector),
+}
+
+impl HybridState {
+    fn new(num_qubits: usize, dense_fraction: f64) -> Self {
+        let limit = if num_qubits <= StateVector::MAX_QUBITS {
+            ((1usize << num_qubits) as f64 * dense_fraction) as usize
+        } else {
+            usize::MAX
+        };
+        HybridState::Sparse(SparseState::new(num_qubits), limit)
+    }
+
+    /// Switch to dense if the sparse state has outgrown its limit.
+    fn check_occupancy(&mut self) {
+        if let HybridState::Sparse(state, limit) = self {
+            if state.num_nonzero() > *limit {
+                let dense = state.to_dense().expect("limit is only finite for dense-sized registers");
+                *self = HybridState::Dense(dense);
+            }
+        }
+    }
+
+    fn into_sparse(self) -> SparseState {
+        match self {
+            HybridState::Sparse(state, _) => state,
+            HybridState::Dense(state) => SparseState::from_dense(&state),
+        }
+    }
+}
+
+macro_rules! delegate {
+    ($self:ident, $state:ident => $call:expr) => {{
+        let result = match $self {
+            HybridState::Sparse($state, _) => $call,
+            HybridState::Dense($state) => $call,
+        };
+        $self.check_occupancy();
+        result
+    }};
+}
+
+impl GateTarget for HybridState {
+    fn apply_single(&mut self, qubit: usize, matrix: [[Complex; 2]; 2]) {
+        delegate!(self, state => GateTarget::apply_single(state, qubit, matrix))
+    }
+
+    fn apply_two(&mut self, q0: usize, q1: usize, matrix: [[Complex; 4]; 4]) {
+        delegate!(self, state => GateTarget::apply_two(state, q0, q1, matrix))
+    }
+
+    fn apply_controlled(&mut self, control: usize, target: usize, matrix: [[Complex; 2]; 2]) {
+        delegate!(self, state => GateTarget::apply_controlled(state, control, target, matrix))
+    }
+
+    fn measure(&mut self, qubit: usize, random: f64) -> u8 {
+        delegate!(self, state => GateTarget::measure(state, qubit, random))
+    }
+
+    fn reset(&mut self, qubit: usize, random: f64) {
+        delegate!(self, state => GateTarget::reset(state, qubit, random))
+    }
+
+    fn distribution(&self) -> Vec<(usize, f64)> {
+        match self {
+            HybridState::Sparse(state, _) => state.distribution(),
+            HybridState::Dense(state) => state.distribution(),
+        }
+    }
+}
+
+/// Simulator over [`SparseState`], falling back to dense when it pays.
+///
+/// Gates, measurement and seeding behave exactly as in [`Simulator`]; only
+/// the state representation differs.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_core::states::ghz;
+/// use homaya_sim::SparseSimulator;
+///
+/// // 2^40 dense amplitudes would need 16 TiB
+/// let state = SparseSimulator::new().run(&ghz(40)).unwrap();
+/// assert_eq!(state.num_nonzero(), 2);
+/// assert!((state.probability((1 << 40) - 1) - 0.5).abs() < 1e-10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SparseSimulator {
+    /// Shared gate dispatch and random number generator
+    sim: Simulator,
+    /// Occupancy, as a fraction of 2^n, above which a run turns dense
+    dense_fraction: f64,
+}
+
+impl Default for SparseSimulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SparseSimulator {
+    /// Default occupancy fraction at which a run switches to dense.
+    ///
+    /// A hash map entry costs several times a 16-byte dense amplitude, so
+    /// past about a quarter full the dense vector is smaller and faster.
+    pub const DEFAULT_DENSE_FRACTION: f64 = 0.25;
+
+    /// Create a new sparse simulator.
+    pub fn new() -> Self {
+        Self::with_config(SimConfig::default())
+    }
+
+    /// Create a sparse simulator with a specific seed for reproducibility.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_config(SimConfig {
+            seed: Some(seed),
+            ..SimConfig::default()
+        })
+    }
+
+    /// Create a sparse simulator from a [`SimConfig`].
+    pub fn with_config(config: SimConfig) -> Self {
+        Self {
+            sim: Simulator::with_config(config),
+            dense_fraction: Self::DEFAULT_DENSE_FRACTION,
+        }
+    }
+
+    /// Set the occupancy fraction of 2^n above which a run turns dense.
+    ///
+    /// Use a value above 1 to stay sparse no matter what. Registers wider
+    /// than [`StateVector::MAX_QUBITS`] always stay sparse.
+    pub fn with_dense_fraction(mut self, fraction: f64) -> Self {
+        self.dense_fraction = fraction;
+        self
+    }
+
+    /// Run a circuit and return the final state.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HomayaError::CircuitTooLarge`] beyond
+    /// [`SparseState::MAX_QUBITS`], or any error the dense simulator would.
+    pub fn run(&mut self, circuit: &Circuit) -> Result<SparseState> {
+        check_width(circuit)?;
+        let (state, _) = self.sim.execute(circuit, self.new_state(circuit.num_qubits()))?;
+        Ok(state.into_sparse())
+    }
+
+    /// Sample the circuit multiple times.
+    ///
+    /// Same keys and seeding as [`Simulator::sample`].
+    pub fn sample(&mut self, circuit: &Circuit, shots: usize) -> Result<HashMap<String, usize>> {
+        check_width(circuit)?;
+        let fraction = self.dense_fraction;
+        self.sim.sample_with(circuit, shots, |n| HybridState::new(n, fraction))
+    }
+
+    fn new_state(&self, num_qubits: usize) -> HybridState {
+        HybridState::new(num_qubits, self.dense_fraction)
+    }
+}
+
+fn check_width(circuit: &Circuit) -> Result<()> {
+    if circuit.num_qubits() > SparseState::MAX_QUBITS {
+        return Err(HomayaError::CircuitTooLarge {
+            qubits: circuit.num_qubits(),
+            max: SparseState::MAX_QUBITS,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_core::states::ghz;
+
+    /// Sparse and dense states agree on every amplitude.
+    fn assert_matches_dense(circuit: &Circuit, sparse: &mut SparseSimulator) {
+        let dense = Simulator::new().run(circuit).unwrap();
+        let state = sparse.run(circuit).unwrap();
+        for i in 0..dense.dimension() {
+            assert!(state.amplitude(i).approx_eq(dense.amplitude(i), 1e-10), "basis {}", i);
+        }
+    }
+
+    /// Bernstein-Vazirani on 4 query qubits, secret 1011, without readout.
+    fn bernstein_vazirani() -> Circuit {
+        let mut circuit = (0..5).fold(Circuit::new(5).x(4), |c, q| c.h(q));
+        for q in [0, 1, 3] {
+            circuit = circuit.cx(q, 4);
+        }
+        (0..4).fold(circuit, |c, q| c.h(q))
+    }
+
+    /// Grover on 3 qubits marking |101⟩, two iterations, without readout.
+    fn grover() -> Circuit {
+        let mark = |c: Circuit| c.x(1).h(2).ccx(0, 1, 2).h(2).x(1);
+        let diffuse = |c: Circuit| {
+            let c = (0..3).fold(c, |c, q| c.h(q).x(q));
+            let c = c.h(2).ccx(0, 1, 2).h(2);
+            (0..3).fold(c, |c, q| c.x(q).h(q))
+        };
+        let start = (0..3).fold(Circuit::new(3), |c, q| c.h(q));
+        (0..2).fold(start, |c, _| diffuse(mark(c)))
+    }
+
+    #[test]
+    fn test_matches_dense_bernstein_vazirani_and_grover() {
+        for circuit in [bernstein_vazirani(), grover()] {
+            // Pure sparse, and with the default switch to dense
+            assert_matches_dense(&circuit, &mut SparseSimulator::new().with_dense_fraction(2.0));
+            assert_matches_dense(&circuit, &mut SparseSimulator::new());
+        }
+    }
+
+    #[test]
+    fn test_sample_matches_dense() {
+        let circuit = grover().measure_all();
+        let dense = Simulator::with_seed(9).sample(&circuit, 200).unwrap();
+        let sparse = SparseSimulator::with_seed(9).with_dense_fraction(2.0).sample(&circuit, 200).unwrap();
+        assert_eq!(sparse, dense);
+    }
+
+    #[test]
+    fn test_mid_circuit_measurement_matches_dense() {
+        let circuit = Circuit::new(2).h(0).measure(0, 0).cx(0, 1).reset(0).measure(1, 1);
+        let dense = Simulator::with_seed(4).sample(&circuit, 100).unwrap();
+        let sparse = SparseSimulator::with_seed(4).sample(&circuit, 100).unwrap();
+        assert_eq!(sparse, dense);
+    }
+
+    #[test]
+    fn test_bernstein_vazirani_stays_small_at_the_end() {
+        // The Hadamard layer fills the state, but the answer is one basis pair
+        let state = SparseSimulator::new().run(&bernstein_vazirani()).unwrap();
+        assert_eq!(state.num_nonzero(), 2);
+        assert!((state.probability(0b01011) - 0.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_wide_ghz() {
+        let counts = SparseSimulator::with_seed(3).sample(&ghz(48).measure_all(), 100).unwrap();
+        assert_eq!(counts.len(), 2);
+        assert!(counts.contains_key(&"1".repeat(48)));
+
+        assert!(matches!(
+            SparseSimulator::new().run(&Circuit::new(64)),
+            Err(HomayaError::CircuitTooLarge { qubits: 64, .. })
+        ));
+    }
+}
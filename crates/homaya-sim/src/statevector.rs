@@ -4,6 +4,18 @@
 
 use homaya_core::{Complex, HomayaError, Result};
 
+/// Smallest register the `parallel` feature splits across threads.
+///
+/// Below 2^14 amplitudes the thread hand-off costs more than the update.
+pub const PARALLEL_MIN_QUBITS: usize = 14;
+
+/// Fewest amplitude pairs one parallel task handles.
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_LEN: usize = 4096;
+
+/// Smallest amplitude [`StateVector::remove_global_phase`] treats as nonzero.
+pub const PHASE_PIVOT_EPSILON: f64 = 1e-10;
+
 /// A quantum state vector.
 ///
 /// Represents the full quantum state as a vector of 2^n complex amplitudes.
@@ -50,6 +62,28 @@ impl StateVector {
         }
     }
 
+    /// Create the computational basis state |basis_state⟩.
+    ///
+    /// Bit i of `basis_state` is qubit i, so `from_basis(2, 0b01)` is |01⟩
+    /// with qubit 0 set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `basis_state` is >= 2^num_qubits.
+    pub fn from_basis(num_qubits: usize, basis_state: usize) -> Self {
+        let dim = 1 << num_qubits;
+        assert!(
+            basis_state < dim,
+            "Basis state {} is too large for {} qubits (max: {})",
+            basis_state,
+            num_qubits,
+            dim - 1
+        );
+        let mut state = Self::new(num_qubits);
+        state.amplitudes.swap(0, basis_state);
+        state
+    }
+
     /// Create a state vector from amplitudes.
     ///
     /// # Errors
@@ -117,6 +151,14 @@ impl StateVector {
         self.amplitudes[index]
     }
 
+    /// Get the amplitude of a computational basis state.
+    ///
+    /// Same as [`get`](Self::get), named for readability at call sites.
+    #[inline]
+    pub fn amplitude(&self, basis_state: usize) -> Complex {
+        self.amplitudes[basis_state]
+    }
+
     /// Set amplitude at index.
     #[inline]
     pub fn set(&mut self, index: usize, value: Complex) {
@@ -134,6 +176,99 @@ impl StateVector {
         self.amplitudes.iter().map(|c| c.norm_sqr()).collect()
     }
 
+    /// Get P(qubit i = 1) for every qubit, without collapsing.
+    pub fn qubit_probabilities(&self) -> Vec<f64> {
+        let mut ones = vec![0.0; self.num_qubits];
+        for (i, amp) in self.amplitudes.iter().enumerate() {
+            let p = amp.norm_sqr();
+            for (qubit, one) in ones.iter_mut().enumerate() {
+                if (i >> qubit) & 1 == 1 {
+                    *one += p;
+                }
+            }
+        }
+        ones
+    }
+
+    /// Get P(`qubit` = 1), without collapsing.
+    ///
+    /// The exact probability a measurement of `qubit` reads 1: the total
+    /// probability of the basis states with that bit set. One qubit's entry
+    /// of [`qubit_probabilities`](Self::qubit_probabilities).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `qubit` is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// let state = Simulator::new().run(&Circuit::new(2).h(0).x(1)).unwrap();
+    /// assert!((state.qubit_probability_one(0) - 0.5).abs() < 1e-12);
+    /// assert!((state.qubit_probability_one(1) - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn qubit_probability_one(&self, qubit: usize) -> f64 {
+        assert!(qubit < self.num_qubits, "Qubit {} out of range for {} qubits", qubit, self.num_qubits);
+        self.amplitudes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (i >> qubit) & 1 == 1)
+            .map(|(_, amp)| amp.norm_sqr())
+            .sum()
+    }
+
+    /// Distribution over `qubits` with every other qubit traced out.
+    ///
+    /// Bit k of an index into the result is the value of `qubits[k]`, so
+    /// `&[0, 1]` gives the usual ordering of the two lowest qubits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a qubit is out of range or listed twice.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// let state = Simulator::new().run(&Circuit::new(2).x(1)).unwrap();
+    /// assert_eq!(state.marginal_probabilities(&[1]), vec![0.0, 1.0]);
+    /// ```
+    pub fn marginal_probabilities(&self, qubits: &[usize]) -> Vec<f64> {
+        for (k, &q) in qubits.iter().enumerate() {
+            assert!(q < self.num_qubits, "Qubit {} out of range for {} qubits", q, self.num_qubits);
+            assert!(!qubits[..k].contains(&q), "Qubit {} listed twice", q);
+        }
+
+        let mut marginal = vec![0.0; 1 << qubits.len()];
+        for (i, amp) in self.amplitudes.iter().enumerate() {
+            let index = qubits
+                .iter()
+                .enumerate()
+                .fold(0, |index, (k, &q)| index | (((i >> q) & 1) << k));
+            marginal[index] += amp.norm_sqr();
+        }
+        marginal
+    }
+
+    /// The most probable basis state and its probability.
+    ///
+    /// Ties go to the lowest index.
+    pub fn most_likely(&self) -> (usize, f64) {
+        let mut best = (0, self.probability(0));
+        for (i, amp) in self.amplitudes.iter().enumerate().skip(1) {
+            let p = amp.norm_sqr();
+            if p > best.1 {
+                best = (i, p);
+            }
+        }
+        best
+    }
+
     /// Normalize the state vector in-place.
     pub fn normalize(&mut self) {
         let norm_sqr: f64 = self.amplitudes.iter().map(|c| c.norm_sqr()).sum();
@@ -147,23 +282,57 @@ impl StateVector {
 
     /// Apply a single-qubit gate.
     ///
-    /// Uses an optimized algorithm that minimizes cache misses.
+    /// Uses an optimized algorithm that minimizes cache misses. With the
+    /// `parallel` feature, states of [`PARALLEL_MIN_QUBITS`] or more are
+    /// split across threads with results bit-identical to the serial path.
     pub fn apply_single(&mut self, qubit: usize, matrix: [[Complex; 2]; 2]) {
-        let dim = self.dimension();
-        let mask = 1 << qubit;
+        self.apply_single_with(qubit, matrix, self.parallel());
+    }
 
-        // Process pairs of amplitudes where they differ only in the qubit bit
-        for i in 0..dim {
-            // Only process when the qubit bit is 0
-            if (i & mask) == 0 {
-                let i0 = i;          // qubit bit = 0
-                let i1 = i | mask;   // qubit bit = 1
+    fn apply_single_with(&mut self, qubit: usize, matrix: [[Complex; 2]; 2], parallel: bool) {
+        self.for_each_pair(1 << qubit, parallel, |_, a0, a1| {
+            let (x0, x1) = (*a0, *a1);
+            *a0 = matrix[0][0] * x0 + matrix[0][1] * x1;
+            *a1 = matrix[1][0] * x0 + matrix[1][1] * x1;
+        });
+    }
 
-                let a0 = self.amplitudes[i0];
-                let a1 = self.amplitudes[i1];
+    /// Whether gates on this state take the multi-threaded path.
+    fn parallel(&self) -> bool {
+        cfg!(feature = "parallel") && self.num_qubits >= PARALLEL_MIN_QUBITS
+    }
+
+    /// Call `f(i0, a[i0], a[i0 | mask])` for every index i0 without `mask`.
+    ///
+    /// The amplitudes split into blocks of 2·mask whose halves hold the
+    /// pairs, so no two calls touch the same amplitude. Each amplitude's new
+    /// value depends only on its own pair, computed by the same
+    /// expression either way, so the parallel path is bit-identical to the
+    /// serial one; there is no reduction to reorder.
+    fn for_each_pair<F>(&mut self, mask: usize, parallel: bool, f: F)
+    where
+        F: Fn(usize, &mut Complex, &mut Complex) + Sync + Send,
+    {
+        #[cfg(feature = "parallel")]
+        if parallel {
+            use rayon::prelude::*;
+
+            self.amplitudes.par_chunks_mut(2 * mask).enumerate().for_each(|(block, chunk)| {
+                let (lo, hi) = chunk.split_at_mut(mask);
+                lo.par_iter_mut()
+                    .zip(hi.par_iter_mut())
+                    .enumerate()
+                    .with_min_len(PARALLEL_MIN_LEN)
+                    .for_each(|(offset, (a0, a1))| f(block * 2 * mask + offset, a0, a1));
+            });
+            return;
+        }
+        let _ = parallel;
 
-                self.amplitudes[i0] = matrix[0][0] * a0 + matrix[0][1] * a1;
-                self.amplitudes[i1] = matrix[1][0] * a0 + matrix[1][1] * a1;
+        for (block, chunk) in self.amplitudes.chunks_mut(2 * mask).enumerate() {
+            let (lo, hi) = chunk.split_at_mut(mask);
+            for (offset, (a0, a1)) in lo.iter_mut().zip(hi).enumerate() {
+                f(block * 2 * mask + offset, a0, a1);
             }
         }
     }
@@ -172,28 +341,62 @@ impl StateVector {
     ///
     /// Optimized for controlled gates and SWAP-like operations.
     pub fn apply_two(&mut self, q0: usize, q1: usize, matrix: [[Complex; 4]; 4]) {
-        let dim = self.dimension();
-        let mask0 = 1 << q0;
-        let mask1 = 1 << q1;
-
-        // Process groups of 4 amplitudes where both qubits are 0
-        for i in 0..dim {
-            // Only process when both qubit bits are 0
-            if (i & mask0) == 0 && (i & mask1) == 0 {
-                let i00 = i;
-                let i01 = i | mask0;
-                let i10 = i | mask1;
-                let i11 = i | mask0 | mask1;
-
-                let a00 = self.amplitudes[i00];
-                let a01 = self.amplitudes[i01];
-                let a10 = self.amplitudes[i10];
-                let a11 = self.amplitudes[i11];
-
-                self.amplitudes[i00] = matrix[0][0] * a00 + matrix[0][1] * a01 + matrix[0][2] * a10 + matrix[0][3] * a11;
-                self.amplitudes[i01] = matrix[1][0] * a00 + matrix[1][1] * a01 + matrix[1][2] * a10 + matrix[1][3] * a11;
-                self.amplitudes[i10] = matrix[2][0] * a00 + matrix[2][1] * a01 + matrix[2][2] * a10 + matrix[2][3] * a11;
-                self.amplitudes[i11] = matrix[3][0] * a00 + matrix[3][1] * a01 + matrix[3][2] * a10 + matrix[3][3] * a11;
+        self.apply_two_with(q0, q1, matrix, self.parallel());
+    }
+
+    fn apply_two_with(&mut self, q0: usize, q1: usize, matrix: [[Complex; 4]; 4], parallel: bool) {
+        let (low, high) = (q0.min(q1), q0.max(q1));
+
+        self.for_each_quad(1 << low, 1 << high, parallel, |mut quad| {
+            // Quads come ordered by (high, low) bits; the matrix wants (q1, q0)
+            if q0 > q1 {
+                quad.swap(1, 2);
+            }
+            let [a00, a01, a10, a11] = quad;
+            let (x00, x01, x10, x11) = (*a00, *a01, *a10, *a11);
+            *a00 = matrix[0][0] * x00 + matrix[0][1] * x01 + matrix[0][2] * x10 + matrix[0][3] * x11;
+            *a01 = matrix[1][0] * x00 + matrix[1][1] * x01 + matrix[1][2] * x10 + matrix[1][3] * x11;
+            *a10 = matrix[2][0] * x00 + matrix[2][1] * x01 + matrix[2][2] * x10 + matrix[2][3] * x11;
+            *a11 = matrix[3][0] * x00 + matrix[3][1] * x01 + matrix[3][2] * x10 + matrix[3][3] * x11;
+        });
+    }
+
+    /// Call `f` on [a[i], a[i | low], a[i | high], a[i | low | high]] for
+    /// every index i with neither bit set.
+    ///
+    /// Same block splitting as [`for_each_pair`](Self::for_each_pair), one
+    /// level deeper.
+    fn for_each_quad<F>(&mut self, low: usize, high: usize, parallel: bool, f: F)
+    where
+        F: Fn([&mut Complex; 4]) + Sync + Send,
+    {
+        let quads = |c0: &mut [Complex], c1: &mut [Complex]| {
+            let (a00, a01) = c0.split_at_mut(low);
+            let (a10, a11) = c1.split_at_mut(low);
+            for (((x00, x01), x10), x11) in a00.iter_mut().zip(a01).zip(a10).zip(a11) {
+                f([x00, x01, x10, x11]);
+            }
+        };
+
+        #[cfg(feature = "parallel")]
+        if parallel {
+            use rayon::prelude::*;
+
+            self.amplitudes.par_chunks_mut(2 * high).for_each(|chunk| {
+                let (h0, h1) = chunk.split_at_mut(high);
+                h0.par_chunks_mut(2 * low)
+                    .zip(h1.par_chunks_mut(2 * low))
+                    .with_min_len((PARALLEL_MIN_LEN / (2 * low)).max(1))
+                    .for_each(|(c0, c1)| quads(c0, c1));
+            });
+            return;
+        }
+        let _ = parallel;
+
+        for chunk in self.amplitudes.chunks_mut(2 * high) {
+            let (h0, h1) = chunk.split_at_mut(high);
+            for (c0, c1) in h0.chunks_mut(2 * low).zip(h1.chunks_mut(2 * low)) {
+                quads(c0, c1);
             }
         }
     }
@@ -202,25 +405,20 @@ impl StateVector {
     ///
     /// This is more efficient than the general two-qubit gate for CX, CZ, etc.
     pub fn apply_controlled(&mut self, control: usize, target: usize, matrix: [[Complex; 2]; 2]) {
-        let dim = self.dimension();
-        let control_mask = 1 << control;
-        let target_mask = 1 << target;
-
-        // Iterate over pairs that differ only in target qubit
-        // Only apply when control qubit is 1
-        for i in 0..dim {
-            // Only process when: control=1 and target=0
-            if (i & control_mask) != 0 && (i & target_mask) == 0 {
-                let i0 = i;              // target = 0
-                let i1 = i | target_mask; // target = 1
+        self.apply_controlled_with(control, target, matrix, self.parallel());
+    }
 
-                let a0 = self.amplitudes[i0];
-                let a1 = self.amplitudes[i1];
+    fn apply_controlled_with(&mut self, control: usize, target: usize, matrix: [[Complex; 2]; 2], parallel: bool) {
+        let control_mask = 1 << control;
 
-                self.amplitudes[i0] = matrix[0][0] * a0 + matrix[0][1] * a1;
-                self.amplitudes[i1] = matrix[1][0] * a0 + matrix[1][1] * a1;
+        // Pairs differ only in the target; apply when the control is 1
+        self.for_each_pair(1 << target, parallel, |i, a0, a1| {
+            if i & control_mask != 0 {
+                let (x0, x1) = (*a0, *a1);
+                *a0 = matrix[0][0] * x0 + matrix[0][1] * x1;
+                *a1 = matrix[1][0] * x0 + matrix[1][1] * x1;
             }
-        }
+        });
     }
 
     /// Measure a single qubit, collapsing the state.
@@ -290,6 +488,82 @@ impl StateVector {
     pub fn fidelity(&self, other: &StateVector) -> f64 {
         self.inner_product(other).norm_sqr()
     }
+
+    /// Whether every amplitude matches `other`'s within `tol`.
+    ///
+    /// Global phase counts; see
+    /// [`approx_eq_up_to_global_phase`](Self::approx_eq_up_to_global_phase)
+    /// to ignore it.
+    pub fn approx_eq(&self, other: &StateVector, tol: f64) -> bool {
+        self.num_qubits == other.num_qubits
+            && self.amplitudes.iter().zip(&other.amplitudes).all(|(a, b)| a.approx_eq(*b, tol))
+    }
+
+    /// Whether the states match within `tol` once a common phase factor
+    /// e^{iφ} is divided out.
+    ///
+    /// φ is read off this state's largest amplitude, which is the least
+    /// sensitive to rounding. States of different size never match.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// // Z·X = -X·Z: same state up to a sign
+    /// let zx = Simulator::new().run(&Circuit::new(1).h(0).x(0).z(0)).unwrap();
+    /// let xz = Simulator::new().run(&Circuit::new(1).h(0).z(0).x(0)).unwrap();
+    /// assert!(zx.approx_eq_up_to_global_phase(&xz, 1e-12));
+    /// assert!(!zx.approx_eq(&xz, 1e-12));
+    /// ```
+    pub fn approx_eq_up_to_global_phase(&self, other: &StateVector, tol: f64) -> bool {
+        if self.num_qubits != other.num_qubits {
+            return false;
+        }
+
+        let (pivot, _) = self.most_likely();
+        let (a, b) = (self.amplitudes[pivot], other.amplitudes[pivot]);
+        if b.abs() < tol {
+            // A zero pivot means self is all zeros; other must be too
+            return a.abs() < tol && self.approx_eq(other, tol);
+        }
+
+        // e^{iφ} taking self to other at the pivot
+        let phase = b * a.conj() * (1.0 / (a.abs() * b.abs()));
+        self.amplitudes
+            .iter()
+            .zip(&other.amplitudes)
+            .all(|(a, b)| (*a * phase).approx_eq(*b, tol))
+    }
+
+    /// Multiply by a global phase so the first significant amplitude is
+    /// real and positive.
+    ///
+    /// Amplitudes below [`PHASE_PIVOT_EPSILON`] in magnitude are skipped
+    /// when choosing the pivot. Equal states up to global phase end up with
+    /// equal amplitudes, and no probability changes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{Circuit, Complex};
+    /// use homaya_sim::Simulator;
+    ///
+    /// // Y|0⟩ = i|1⟩
+    /// let mut state = Simulator::new().run(&Circuit::new(1).y(0)).unwrap();
+    /// state.remove_global_phase();
+    /// assert!(state.amplitude(1).approx_eq(Complex::from_real(1.0), 1e-12));
+    /// ```
+    pub fn remove_global_phase(&mut self) {
+        let Some(pivot) = self.amplitudes.iter().find(|a| a.abs() > PHASE_PIVOT_EPSILON) else {
+            return;
+        };
+        let phase = pivot.conj() * (1.0 / pivot.abs());
+        for amp in &mut self.amplitudes {
+            *amp *= phase;
+        }
+    }
 }
 
 impl PartialEq for StateVector {
@@ -319,6 +593,35 @@ mod tests {
         assert_eq!(state.get(3), Complex::ZERO);
     }
 
+    #[test]
+    fn test_from_basis() {
+        let state = StateVector::from_basis(3, 0b101);
+        assert_eq!(state.num_qubits(), 3);
+        assert_eq!(state.most_likely(), (5, 1.0));
+        assert!(StateVector::from_basis(2, 0).approx_eq(&StateVector::new(2), 1e-12));
+    }
+
+    #[test]
+    #[should_panic(expected = "Basis state 4 is too large for 2 qubits (max: 3)")]
+    fn test_from_basis_out_of_range() {
+        StateVector::from_basis(2, 4);
+    }
+
+    #[test]
+    fn test_from_amplitudes_validates() {
+        let half = Complex::from_real(0.5);
+        assert!(matches!(
+            StateVector::from_amplitudes(vec![half, half]),
+            Err(HomayaError::StateNotNormalized { .. })
+        ));
+        assert!(matches!(
+            StateVector::from_amplitudes(vec![half; 3]),
+            Err(HomayaError::StateDimensionMismatch { got: 3, .. })
+        ));
+        assert!(StateVector::from_amplitudes(Vec::new()).is_err());
+        assert_eq!(StateVector::from_amplitudes(vec![half; 4]).unwrap().num_qubits(), 2);
+    }
+
     #[test]
     fn test_uniform_superposition() {
         let state = StateVector::uniform(2);
@@ -336,6 +639,16 @@ mod tests {
         assert!((sum - 1.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_qubit_probabilities() {
+        // (|00⟩ + |01⟩)/√2: qubit 0 is 50/50, qubit 1 is always 0
+        let h = Complex::from_real(homaya_core::INV_SQRT_2);
+        let state = StateVector::from_amplitudes(vec![h, h, Complex::ZERO, Complex::ZERO]).unwrap();
+        let ones = state.qubit_probabilities();
+        assert!((ones[0] - 0.5).abs() < 1e-10);
+        assert!(ones[1].abs() < 1e-10);
+    }
+
     #[test]
     fn test_hadamard_creates_superposition() {
         let mut state = StateVector::new(1);
@@ -391,10 +704,154 @@ mod tests {
         assert_eq!(state.get(1), Complex::ZERO);
     }
 
+    #[test]
+    fn test_amplitude() {
+        let state = StateVector::uniform(2);
+        assert!(state.amplitude(3).approx_eq(Complex::from_real(0.5), 1e-12));
+        assert_eq!(state.amplitude(3), state.get(3));
+    }
+
     #[test]
     fn test_fidelity_with_self() {
         let state = StateVector::uniform(3);
         let fidelity = state.fidelity(&state);
         assert!((fidelity - 1.0).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_apply_two_qubit_order() {
+        // CX with control q0, target q1 in (q1, q0) basis order
+        let (o, z) = (Complex::ONE, Complex::ZERO);
+        let cx = [[o, z, z, z], [z, z, z, o], [z, z, o, z], [z, o, z, z]];
+
+        let mut forward = StateVector::new(3);
+        forward.apply_single(0, [[z, o], [o, z]]);
+        forward.apply_two(0, 2, cx);
+        assert!(forward.get(0b101).approx_eq(o, 1e-12));
+
+        let mut reversed = StateVector::new(3);
+        reversed.apply_single(2, [[z, o], [o, z]]);
+        reversed.apply_two(2, 0, cx);
+        assert!(reversed.get(0b101).approx_eq(o, 1e-12));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_matches_serial() {
+        let n = PARALLEL_MIN_QUBITS + 2;
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        let had = [[Complex::from_real(h), Complex::from_real(h)], [Complex::from_real(h), Complex::from_real(-h)]];
+        let phase = [[Complex::ONE, Complex::ZERO], [Complex::ZERO, Complex::from_polar(1.0, 0.7)]];
+        let mut swap = [[Complex::ZERO; 4]; 4];
+        swap[0][0] = Complex::ONE;
+        swap[1][2] = Complex::ONE;
+        swap[2][1] = Complex::ONE;
+        swap[3][3] = Complex::ONE;
+
+        let mut serial = StateVector::new(n);
+        let mut parallel = StateVector::new(n);
+        for (state, threaded) in [(&mut serial, false), (&mut parallel, true)] {
+            for q in 0..n {
+                state.apply_single_with(q, had, threaded);
+            }
+            for q in 0..n - 1 {
+                state.apply_controlled_with(q, q + 1, phase, threaded);
+                state.apply_single_with(q, phase, threaded);
+            }
+            state.apply_two_with(0, n - 1, swap, threaded);
+            state.apply_two_with(n - 2, 3, swap, threaded);
+        }
+
+        for i in 0..serial.dimension() {
+            assert!(serial.get(i).approx_eq(parallel.get(i), 1e-12));
+        }
+        assert_eq!(serial.amplitudes(), parallel.amplitudes());
+    }
+
+    #[test]
+    fn test_marginal_probabilities_of_ghz() {
+        let h = Complex::from_real(std::f64::consts::FRAC_1_SQRT_2);
+        let x = [[Complex::ZERO, Complex::ONE], [Complex::ONE, Complex::ZERO]];
+        let mut state = StateVector::new(3);
+        state.apply_single(0, [[h, h], [h, -h]]);
+        state.apply_controlled(0, 1, x);
+        state.apply_controlled(1, 2, x);
+
+        let single = state.marginal_probabilities(&[0]);
+        assert!((single[0] - 0.5).abs() < 1e-12 && (single[1] - 0.5).abs() < 1e-12);
+
+        // Qubits 2 and 0 always agree
+        let pair = state.marginal_probabilities(&[2, 0]);
+        assert!((pair[0b00] - 0.5).abs() < 1e-12 && (pair[0b11] - 0.5).abs() < 1e-12);
+        assert!((state.marginal_probabilities(&[])[0] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_marginal_orders_bits_by_argument() {
+        // |q2 q1 q0⟩ = |011⟩
+        let mut amplitudes = vec![Complex::ZERO; 8];
+        amplitudes[0b011] = Complex::ONE;
+        let state = StateVector::from_amplitudes(amplitudes).unwrap();
+
+        assert_eq!(state.marginal_probabilities(&[1, 2]), vec![0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(state.marginal_probabilities(&[2, 1]), vec![0.0, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "listed twice")]
+    fn test_marginal_rejects_duplicates() {
+        StateVector::new(2).marginal_probabilities(&[1, 1]);
+    }
+
+    #[test]
+    fn test_most_likely() {
+        assert_eq!(StateVector::new(2).most_likely(), (0, 1.0));
+
+        // Uniform: every state ties, lowest index wins
+        let (index, p) = StateVector::uniform(2).most_likely();
+        assert_eq!(index, 0);
+        assert!((p - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_approx_eq_up_to_global_phase() {
+        let state = StateVector::from_amplitudes(vec![
+            Complex::from_real(0.6),
+            Complex::new(0.0, 0.48),
+            Complex::ZERO,
+            Complex::new(-0.64, 0.0),
+        ])
+        .unwrap();
+        let phase = Complex::from_polar(1.0, std::f64::consts::PI / 3.0);
+        let shifted = StateVector::from_amplitudes(state.amplitudes().iter().map(|&a| a * phase).collect()).unwrap();
+
+        assert!(state.approx_eq_up_to_global_phase(&shifted, 1e-12));
+        assert!(shifted.approx_eq_up_to_global_phase(&state, 1e-12));
+        assert!(!state.approx_eq(&shifted, 1e-12));
+        assert!(state.approx_eq(&state.clone(), 1e-15));
+
+        // A relative phase is observable
+        let mut relative = state.clone();
+        relative.set(3, -relative.get(3));
+        assert!(!state.approx_eq_up_to_global_phase(&relative, 1e-6));
+
+        assert!(!StateVector::new(1).approx_eq_up_to_global_phase(&StateVector::new(2), 1e-12));
+        assert!(!StateVector::new(1).approx_eq_up_to_global_phase(&StateVector::uniform(1), 1e-6));
+    }
+
+    #[test]
+    fn test_remove_global_phase() {
+        // e^{iπ/3} (0.6|01⟩ - 0.8i|11⟩), with a rounding-level |00⟩
+        let phase = Complex::from_polar(1.0, std::f64::consts::PI / 3.0);
+        let amplitudes = [Complex::new(1e-14, 0.0), Complex::new(0.6, 0.0), Complex::ZERO, Complex::new(0.0, -0.8)];
+        let mut state = StateVector::from_amplitudes(amplitudes.iter().map(|&a| a * phase).collect()).unwrap();
+        let before: Vec<f64> = (0..4).map(|i| state.probability(i)).collect();
+
+        state.remove_global_phase();
+        assert!(state.amplitude(1).approx_eq(Complex::from_real(0.6), 1e-12));
+        assert!(state.amplitude(3).approx_eq(Complex::new(0.0, -0.8), 1e-12));
+        for (i, p) in before.into_iter().enumerate() {
+            assert!((state.probability(i) - p).abs() < 1e-12);
+        }
+    }
 }
@@ -0,0 +1,206 @@
+//! Single-precision quantum state vector.
+//!
+//! Half the memory of [`StateVector`] at the cost of precision.
+
+use homaya_core::{Complex, Complex32};
+
+use crate::StateVector;
+
+/// A quantum state vector with single-precision amplitudes.
+///
+/// Each amplitude takes 8 bytes instead of 16, so a 28-qubit state needs
+/// 2 GiB rather than 4 GiB. Gate matrices are computed in double precision
+/// and rounded once per gate; the amplitudes themselves accumulate rounding
+/// error of about 1e-7 per gate, so probabilities drift by roughly
+/// `1e-7 × depth`. That is invisible when sampling a few thousand shots but
+/// too coarse for fidelity checks or amplitude-level comparisons.
+///
+/// Produced by [`Simulator::run_f32`](crate::Simulator::run_f32).
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_sim::StateVector32;
+///
+/// let state = StateVector32::new(2);
+/// assert_eq!(state.dimension(), 4);
+/// assert_eq!(state.probability(0), 1.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StateVector32 {
+    /// Number of qubits
+    num_qubits: usize,
+    /// Amplitude vector (length = 2^num_qubits)
+    amplitudes: Vec<Complex32>,
+}
+
+impl StateVector32 {
+    /// Create a new state vector initialized to |0...0⟩.
+    pub fn new(num_qubits: usize) -> Self {
+        let dim = 1 << num_qubits;
+        let mut amplitudes = vec![Complex32::ZERO; dim];
+        amplitudes[0] = Complex32::ONE;
+        Self {
+            num_qubits,
+            amplitudes,
+        }
+    }
+
+    /// Get the number of qubits.
+    #[inline]
+    pub const fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    /// Get the dimension (2^n).
+    #[inline]
+    pub fn dimension(&self) -> usize {
+        self.amplitudes.len()
+    }
+
+    /// Get the amplitudes as a slice.
+    #[inline]
+    pub fn amplitudes(&self) -> &[Complex32] {
+        &self.amplitudes
+    }
+
+    /// Get the probability of measuring a basis state.
+    #[inline]
+    pub fn probability(&self, index: usize) -> f64 {
+        self.amplitudes[index].norm_sqr() as f64
+    }
+
+    /// Get all probabilities.
+    pub fn probabilities(&self) -> Vec<f64> {
+        self.amplitudes.iter().map(|c| c.norm_sqr() as f64).collect()
+    }
+
+    /// Widen to a double-precision state vector.
+    ///
+    /// The rounding error already accumulated is kept, not undone.
+    pub fn to_f64(&self) -> StateVector {
+        let mut state = StateVector::new(self.num_qubits);
+        for (target, &amp) in state.amplitudes_mut().iter_mut().zip(&self.amplitudes) {
+            *target = amp.into();
+        }
+        state
+    }
+
+    /// Apply a single-qubit gate, rounding the matrix to single precision.
+    pub fn apply_single(&mut self, qubit: usize, matrix: [[Complex; 2]; 2]) {
+        let m = round_2x2(matrix);
+        let mask = 1 << qubit;
+
+        for i0 in (0..self.dimension()).filter(|i| i & mask == 0) {
+            let i1 = i0 | mask;
+            let (a0, a1) = (self.amplitudes[i0], self.amplitudes[i1]);
+            self.amplitudes[i0] = m[0][0] * a0 + m[0][1] * a1;
+            self.amplitudes[i1] = m[1][0] * a0 + m[1][1] * a1;
+        }
+    }
+
+    /// Apply a two-qubit gate, rounding the matrix to single precision.
+    pub fn apply_two(&mut self, q0: usize, q1: usize, matrix: [[Complex; 4]; 4]) {
+        let m = matrix.map(|row| row.map(Complex32::from));
+        let (mask0, mask1) = (1 << q0, 1 << q1);
+
+        for i in (0..self.dimension()).filter(|i| i & mask0 == 0 && i & mask1 == 0) {
+            let indices = [i, i | mask0, i | mask1, i | mask0 | mask1];
+            let a = indices.map(|j| self.amplitudes[j]);
+            for (row, &j) in indices.iter().enumerate() {
+                self.amplitudes[j] = m[row][0] * a[0] + m[row][1] * a[1] + m[row][2] * a[2] + m[row][3] * a[3];
+            }
+        }
+    }
+
+    /// Apply a controlled single-qubit gate, rounding the matrix to single precision.
+    pub fn apply_controlled(&mut self, control: usize, target: usize, matrix: [[Complex; 2]; 2]) {
+        let m = round_2x2(matrix);
+        let (control_mask, target_mask) = (1 << control, 1 << target);
+
+        for i0 in (0..self.dimension()).filter(|i| i & control_mask != 0 && i & target_mask == 0) {
+            let i1 = i0 | target_mask;
+            let (a0, a1) = (self.amplitudes[i0], self.amplitudes[i1]);
+            self.amplitudes[i0] = m[0][0] * a0 + m[0][1] * a1;
+            self.amplitudes[i1] = m[1][0] * a0 + m[1][1] * a1;
+        }
+    }
+
+    /// Measure a single qubit, collapsing the state.
+    ///
+    /// The outcome probability is summed in double precision so that it
+    /// does not drift further than the amplitudes themselves.
+    pub fn measure(&mut self, qubit: usize, random: f64) -> u8 {
+        let mask = 1 << qubit;
+
+        let prob_0: f64 = self.amplitudes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| i & mask == 0)
+            .map(|(_, c)| c.norm_sqr() as f64)
+            .sum();
+
+        let result = if random < prob_0 { 0 } else { 1 };
+
+        // Collapse and renormalize, which also clears accumulated drift
+        let norm = if result == 0 { prob_0 } else { 1.0 - prob_0 };
+        let inv_sqrt_norm = (1.0 / norm.sqrt()) as f32;
+
+        for (i, amp) in self.amplitudes.iter_mut().enumerate() {
+            let qubit_is_one = (i & mask) != 0;
+            if qubit_is_one == (result == 1) {
+                *amp = *amp * inv_sqrt_norm;
+            } else {
+                *amp = Complex32::ZERO;
+            }
+        }
+
+        result
+    }
+
+    /// Reset a qubit to |0⟩.
+    pub fn reset(&mut self, qubit: usize, random: f64) {
+        if self.measure(qubit, random) == 1 {
+            let x_matrix = [[Complex::ZERO, Complex::ONE], [Complex::ONE, Complex::ZERO]];
+            self.apply_single(qubit, x_matrix);
+        }
+    }
+}
+
+/// Round a 2x2 gate matrix to single precision.
+fn round_2x2(matrix: [[Complex; 2]; 2]) -> [[Complex32; 2]; 2] {
+    matrix.map(|row| row.map(Complex32::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_core::INV_SQRT_2;
+
+    #[test]
+    fn test_new_is_ground_state() {
+        let state = StateVector32::new(3);
+        assert_eq!(state.dimension(), 8);
+        assert_eq!(state.probabilities()[0], 1.0);
+    }
+
+    #[test]
+    fn test_hadamard_and_measure() {
+        let h = Complex::from_real(INV_SQRT_2);
+        let mut state = StateVector32::new(1);
+        state.apply_single(0, [[h, h], [h, -h]]);
+        assert!((state.probability(1) - 0.5).abs() < 1e-6);
+
+        assert_eq!(state.measure(0, 0.9), 1);
+        assert!((state.probability(1) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_to_f64_keeps_amplitudes() {
+        let h = Complex::from_real(INV_SQRT_2);
+        let mut state = StateVector32::new(2);
+        state.apply_single(1, [[h, h], [h, -h]]);
+        let wide = state.to_f64();
+        assert!((wide.probability(2) - 0.5).abs() < 1e-6);
+    }
+}
@@ -314,7 +314,7 @@ pub mod constants {
     pub const SQRT_2: f64 = std::f64::consts::SQRT_2;
 
     /// 1/√2 (used frequently in quantum gates)
-    pub const INV_SQRT_2: f64 = 0.7071067811865476;
+    pub const INV_SQRT_2: f64 = std::f64::consts::FRAC_1_SQRT_2;
 
     /// Default tolerance for floating point comparisons
     pub const EPSILON: f64 = 1e-10;
@@ -0,0 +1,144 @@
+//! Textbook algorithm circuits for `homaya gen`.
+//!
+//! Each variant wraps one of the builders in `homaya-algorithms`, checking
+//! its arguments first so bad input becomes an error message instead of a
+//! panic inside the builder.
+
+use clap::ValueEnum;
+use homaya_algorithms::deutsch::FunctionType;
+use homaya_algorithms::{BernsteinVazirani, DeutschJozsa, GroverSearch};
+use homaya_core::Circuit;
+
+/// Algorithms `homaya gen` can build.
+#[derive(Debug, Clone, PartialEq, clap::Subcommand)]
+pub enum Algorithm {
+    /// Grover search for one marked state
+    Grover {
+        /// Number of qubits
+        #[arg(short, long)]
+        qubits: usize,
+        /// Marked basis state, as a decimal number
+        #[arg(short, long)]
+        target: usize,
+        /// Number of Grover iterations (default: optimal)
+        #[arg(short, long)]
+        iterations: Option<usize>,
+    },
+    /// Bernstein-Vazirani with a hidden bitstring
+    Bv {
+        /// Number of query qubits
+        #[arg(short, long)]
+        qubits: usize,
+        /// Secret as a bitstring, bit 0 rightmost, e.g. 1010
+        #[arg(short, long)]
+        secret: String,
+    },
+    /// Deutsch-Jozsa with a built-in oracle function
+    Dj {
+        /// Number of query qubits
+        #[arg(short, long)]
+        qubits: usize,
+        /// Oracle function
+        #[arg(short, long, value_enum)]
+        function: Function,
+    },
+}
+
+/// Deutsch-Jozsa oracle functions, as named on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Function {
+    /// f(x) = 0
+    Zero,
+    /// f(x) = 1
+    One,
+    /// f(x) = parity of x
+    Parity,
+    /// f(x) = bit 0 of x
+    FirstBit,
+    /// f(x) = bit n-1 of x
+    LastBit,
+}
+
+impl From<Function> for FunctionType {
+    fn from(function: Function) -> Self {
+        match function {
+            Function::Zero => FunctionType::ConstantZero,
+            Function::One => FunctionType::ConstantOne,
+            Function::Parity => FunctionType::BalancedParity,
+            Function::FirstBit => FunctionType::BalancedFirstBit,
+            Function::LastBit => FunctionType::BalancedLastBit,
+        }
+    }
+}
+
+impl Algorithm {
+    /// Validate the arguments and build the circuit.
+    pub fn build(&self) -> Result<Circuit, String> {
+        match *self {
+            Algorithm::Grover { qubits, target, iterations } => {
+                check_qubits(qubits)?;
+                let mut grover = GroverSearch::try_new(qubits, target).map_err(|e| e.to_string())?;
+                if let Some(iterations) = iterations {
+                    grover = grover.with_iterations(iterations);
+                }
+                Ok(grover.build())
+            }
+            Algorithm::Bv { qubits, ref secret } => {
+                check_qubits(qubits)?;
+                if secret.len() != qubits {
+                    return Err(format!("secret `{}` has {} bits but --qubits is {}", secret, secret.len(), qubits));
+                }
+                Ok(BernsteinVazirani::from_binary(secret).map_err(|e| e.to_string())?.build())
+            }
+            Algorithm::Dj { qubits, function } => {
+                check_qubits(qubits)?;
+                Ok(DeutschJozsa::try_new(qubits, function.into()).map_err(|e| e.to_string())?.build())
+            }
+        }
+    }
+}
+
+fn check_qubits(qubits: usize) -> Result<(), String> {
+    if qubits == 0 {
+        return Err("--qubits must be at least 1".to_string());
+    }
+    if qubits >= usize::BITS as usize {
+        return Err(format!("--qubits must be less than {}", usize::BITS));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bv(qubits: usize, secret: &str) -> Result<Circuit, String> {
+        Algorithm::Bv { qubits, secret: secret.to_string() }.build()
+    }
+
+    #[test]
+    fn test_bv_qasm_has_secret_cx_gates() {
+        // Secret 1010 sets bits 1 and 3; the ancilla is qubit 4
+        let qasm = bv(4, "1010").unwrap().to_qasm();
+        assert!(qasm.contains("cx q[1],q[4];\ncx q[3],q[4];\n"), "{}", qasm);
+        assert!(!qasm.contains("cx q[0]") && !qasm.contains("cx q[2]"), "{}", qasm);
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(bv(4, "101").unwrap_err().contains("has 3 bits but --qubits is 4"));
+        assert!(bv(2, "1x").unwrap_err().contains("found 'x' at position 1"));
+
+        let grover = Algorithm::Grover { qubits: 3, target: 8, iterations: None };
+        assert_eq!(grover.build().unwrap_err(), "Target 8 is too large for 3 qubits (max: 7)");
+
+        let dj = Algorithm::Dj { qubits: 0, function: Function::Parity };
+        assert!(dj.build().unwrap_err().contains("at least 1"));
+    }
+
+    #[test]
+    fn test_dj_function_names() {
+        let dj = Algorithm::Dj { qubits: 3, function: Function::Parity }.build().unwrap();
+        assert_eq!(dj.instructions(), DeutschJozsa::new(3, FunctionType::BalancedParity).build().instructions());
+    }
+}
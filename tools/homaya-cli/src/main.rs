@@ -3,7 +3,19 @@
 //! Learn more: https://bskiller.com
 //! Enterprise: https://dataxlr8.ai
 
-use clap::Parser;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, ValueEnum};
+use homaya_algorithms::GroverSearch;
+use homaya_core::{format_outcome, Circuit};
+use homaya_sim::{SimConfig, Simulator, StateVector};
+
+mod generate;
+mod params;
+mod repl;
+
+use params::Params;
 
 const BANNER: &str = r#"
 ╦ ╦╔═╗╔╦╗╔═╗╦ ╦╔═╗
@@ -26,11 +38,72 @@ struct Cli {
 enum Commands {
     /// Run a quantum circuit
     Run {
-        /// Path to circuit file
-        file: String,
+        /// Path to an OpenQASM 2.0 file, or a JSON circuit ending in `.json`
+        file: PathBuf,
+        /// Number of shots
+        #[arg(short, long, default_value = "1024")]
+        shots: usize,
+        /// Random seed for reproducible sampling
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Sample a circuit and print only its histogram, in a chosen format
+    Simulate {
+        /// Path to an OpenQASM 2.0 file, or a JSON circuit ending in `.json`
+        file: PathBuf,
         /// Number of shots
-        #[arg(short, long, default_value = "1000")]
-        shots: u32,
+        #[arg(short, long, default_value = "1024")]
+        shots: usize,
+        /// Histogram format
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Random seed for reproducible sampling
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Draw an OpenQASM 2.0 or JSON circuit as a text diagram or SVG image
+    Draw {
+        /// Path to an OpenQASM 2.0 file, or a JSON circuit ending in `.json`
+        file: PathBuf,
+        /// Diagram format
+        #[arg(long, value_enum, default_value_t = DrawFormat::Text)]
+        format: DrawFormat,
+    },
+    /// Summarize a circuit's size and gates, and lint it for likely mistakes
+    Info {
+        /// Path to an OpenQASM 2.0 file, or a JSON circuit ending in `.json`
+        file: PathBuf,
+        /// Summary format
+        #[arg(long, value_enum, default_value_t = InfoFormat::Text)]
+        format: InfoFormat,
+    },
+    /// Print a textbook algorithm circuit as OpenQASM or JSON
+    Gen {
+        #[command(subcommand)]
+        algorithm: generate::Algorithm,
+        /// Circuit format
+        #[arg(long, value_enum, default_value_t = CircuitFormat::Qasm, global = true)]
+        format: CircuitFormat,
+    },
+    /// Build a circuit interactively, one gate per line
+    Repl {
+        /// Start with this many qubits; gates on higher ones add more
+        #[arg(short, long, default_value = "1")]
+        qubits: usize,
+        /// Shots for `run` once the circuit measures
+        #[arg(short, long, default_value = "1024")]
+        shots: usize,
+        /// Random seed for reproducible sampling
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Search for a marked state with Grover's algorithm
+    Grover {
+        #[command(flatten)]
+        params: Params,
+        /// TOML file with parameters; flags override its values
+        #[arg(long = "params", env = "HOMAYA_PARAMS", value_name = "FILE")]
+        params_file: Option<PathBuf>,
     },
     /// Show version and system info
     Version,
@@ -40,15 +113,107 @@ enum Commands {
     Gates,
 }
 
+/// Output format for `homaya simulate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Aligned `bitstring  count` table
+    Text,
+    /// A single JSON object mapping bitstrings to counts
+    Json,
+    /// `bitstring,count` rows under a header line
+    Csv,
+}
+
+/// Output format for `homaya draw`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DrawFormat {
+    /// Box-drawing text diagram
+    Text,
+    /// Self-contained SVG image
+    Svg,
+}
+
+/// Output format for `homaya info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum InfoFormat {
+    /// Aligned summary, gate tally and lint warnings
+    Text,
+    /// A single JSON object with the same fields
+    Json,
+}
+
+/// Output format for `homaya gen`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CircuitFormat {
+    /// OpenQASM 2.0
+    Qasm,
+    /// The JSON circuit schema read by `homaya run`
+    Json,
+}
+
 fn main() {
     let cli = Cli::parse();
 
     match cli.command {
-        Some(Commands::Run { file, shots }) => {
-            println!("Running circuit from: {} ({} shots)", file, shots);
-            println!("\nNote: Circuit file format coming soon.");
-            println!("For now, use the Rust API directly.");
-            println!("\nLearn how: https://bskiller.com");
+        Some(Commands::Run { file, shots, seed }) => match run_file(&file, shots, seed) {
+            Ok(counts) => print_counts(&counts),
+            Err(message) => {
+                eprintln!("error: {}", message);
+                std::process::exit(1);
+            }
+        },
+        Some(Commands::Simulate { file, shots, format, seed }) => {
+            match load_circuit(&file).and_then(|circuit| sample_circuit(&file, &circuit, shots, seed)) {
+                Ok(counts) => print!("{}", format_counts(&counts, format)),
+                Err(message) => {
+                    eprintln!("error: {}", message);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Draw { file, format }) => match load_circuit(&file) {
+            Ok(circuit) => match format {
+                DrawFormat::Text => print!("{}", circuit.draw()),
+                DrawFormat::Svg => print!("{}", circuit.to_svg()),
+            },
+            Err(message) => {
+                eprintln!("error: {}", message);
+                std::process::exit(1);
+            }
+        },
+        Some(Commands::Info { file, format }) => match load_circuit(&file) {
+            Ok(circuit) if format == InfoFormat::Json => println!("{}", info_json(&circuit)),
+            Ok(circuit) => print_info(&circuit),
+            Err(message) => {
+                eprintln!("error: {}", message);
+                std::process::exit(1);
+            }
+        },
+        Some(Commands::Gen { algorithm, format }) => match algorithm.build() {
+            Ok(circuit) if format == CircuitFormat::Json => println!("{}", circuit.to_json()),
+            Ok(circuit) => print!("{}", circuit.to_qasm()),
+            Err(message) => {
+                eprintln!("error: {}", message);
+                std::process::exit(1);
+            }
+        },
+        Some(Commands::Repl { qubits, shots, seed }) => {
+            if let Err(err) = repl::run(qubits, shots, seed) {
+                eprintln!("error: {}", err);
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Grover { params, params_file }) => {
+            let result = params
+                .resolve(params_file.as_deref())
+                .and_then(|params| run_grover(&params));
+            match result {
+                Ok(counts) => print_counts(&counts),
+                Err(message) => {
+                    eprintln!("error: {}", message);
+                    std::process::exit(1);
+                }
+            }
         }
         Some(Commands::Version) => {
             print_version();
@@ -68,12 +233,171 @@ fn main() {
     }
 }
 
+/// Read and parse an OpenQASM 2.0 file, or a JSON circuit ending in `.json`.
+fn load_circuit(file: &Path) -> Result<Circuit, String> {
+    let source = std::fs::read_to_string(file).map_err(|e| format!("cannot read {}: {}", file.display(), e))?;
+    let circuit = if file.extension().is_some_and(|ext| ext == "json") {
+        Circuit::from_json(&source)
+    } else {
+        Circuit::from_qasm(&source)
+    };
+    circuit.map_err(|e| format!("{}: {}", file.display(), e))
+}
+
+/// Load an OpenQASM 2.0 or JSON circuit file and sample it.
+fn run_file(file: &Path, shots: usize, seed: Option<u64>) -> Result<HashMap<String, usize>, String> {
+    let circuit = load_circuit(file)?;
+    println!(
+        "Running {}: {} qubits, {} gates, {} shots",
+        file.display(),
+        circuit.num_qubits(),
+        circuit.len(),
+        shots
+    );
+    sample_circuit(file, &circuit, shots, seed)
+}
+
+/// Sample a circuit loaded from `file`, seeding the simulator if asked.
+fn sample_circuit(
+    file: &Path,
+    circuit: &Circuit,
+    shots: usize,
+    seed: Option<u64>,
+) -> Result<HashMap<String, usize>, String> {
+    if circuit.num_clbits() == 0 {
+        return Err(format!("{} has no measurements, nothing to sample", file.display()));
+    }
+
+    Simulator::with_config(SimConfig { seed, shots })
+        .sample_configured(circuit)
+        .map_err(|e| e.to_string())
+}
+
+/// Build and sample a Grover search from resolved parameters.
+fn run_grover(params: &Params) -> Result<HashMap<String, usize>, String> {
+    let qubits = params.qubits.ok_or("missing --qubits (or `qubits` in the params file)")?;
+    let target = params.target.ok_or("missing --target (or `target` in the params file)")?;
+    if qubits == 0 || qubits > StateVector::MAX_QUBITS {
+        return Err(format!("qubits must be between 1 and {}", StateVector::MAX_QUBITS));
+    }
+    if target >> qubits != 0 {
+        return Err(format!("target {} does not fit in {} qubits", target, qubits));
+    }
+
+    let mut grover = GroverSearch::new(qubits, target);
+    if grover.total_qubits() > StateVector::MAX_QUBITS {
+        return Err(format!(
+            "{} search qubits need {} with ancillas, more than the simulator's {}",
+            qubits,
+            grover.total_qubits(),
+            StateVector::MAX_QUBITS
+        ));
+    }
+    if let Some(iterations) = params.iterations {
+        grover = grover.with_iterations(iterations);
+    }
+    let shots = params.shots.unwrap_or(1000);
+
+    println!(
+        "Grover search: {} qubits, target {}, {} iterations, {} shots",
+        qubits,
+        format_outcome(target, qubits),
+        grover.oracle_call_count(),
+        shots
+    );
+
+    Simulator::with_config(SimConfig { seed: params.seed, shots })
+        .sample_configured(&grover.build())
+        .map_err(|e| e.to_string())
+}
+
+/// Print a circuit's size, gate counts and lint warnings.
+fn print_info(circuit: &Circuit) {
+    println!("Qubits:  {}", circuit.num_qubits());
+    println!("Clbits:  {}", circuit.num_clbits());
+    println!("Gates:   {} ({} two-qubit)", circuit.gate_count(), circuit.two_qubit_gate_count());
+    println!("Depth:   {}", circuit.depth());
+    println!();
+    for (gate_type, count) in circuit.count_gates() {
+        println!("  {:<10} {}", format!("{:?}", gate_type), count);
+    }
+
+    let warnings = circuit.lint();
+    println!();
+    if warnings.is_empty() {
+        println!("No lint warnings");
+    } else {
+        println!("Lint warnings:");
+        for warning in &warnings {
+            println!("  warning: {}", warning);
+        }
+    }
+}
+
+/// The `homaya info` summary as a JSON object.
+fn info_json(circuit: &Circuit) -> serde_json::Value {
+    let gate_counts: BTreeMap<String, usize> = circuit
+        .count_gates()
+        .into_iter()
+        .map(|(gate_type, count)| (format!("{:?}", gate_type), count))
+        .collect();
+    let warnings: Vec<String> = circuit.lint().iter().map(|warning| warning.to_string()).collect();
+
+    serde_json::json!({
+        "qubits": circuit.num_qubits(),
+        "clbits": circuit.num_clbits(),
+        "gates": circuit.gate_count(),
+        "two_qubit_gates": circuit.two_qubit_gate_count(),
+        "depth": circuit.depth(),
+        "gate_counts": gate_counts,
+        "lint": warnings,
+    })
+}
+
+/// Print a histogram, most frequent outcome first.
+fn print_counts(counts: &HashMap<String, usize>) {
+    println!();
+    print!("{}", format_top_counts(counts));
+}
+
+/// The eight most frequent outcomes with their share of shots.
+fn format_top_counts(counts: &HashMap<String, usize>) -> String {
+    let total: usize = counts.values().sum();
+    let mut outcomes: Vec<_> = counts.iter().collect();
+    outcomes.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+
+    outcomes
+        .iter()
+        .take(8)
+        .map(|(bits, &count)| format!("  {}  {:>6}  ({:.1}%)\n", bits, count, 100.0 * count as f64 / total as f64))
+        .collect()
+}
+
+/// Render a histogram sorted by bitstring.
+fn format_counts(counts: &HashMap<String, usize>, format: Format) -> String {
+    let sorted: BTreeMap<&str, usize> = counts.iter().map(|(bits, &count)| (bits.as_str(), count)).collect();
+
+    match format {
+        Format::Text => sorted.iter().map(|(bits, count)| format!("{}  {:>6}\n", bits, count)).collect(),
+        Format::Json => format!("{}\n", serde_json::to_string(&sorted).expect("counts always serialize")),
+        Format::Csv => std::iter::once("bitstring,count\n".to_string())
+            .chain(sorted.iter().map(|(bits, count)| format!("{},{}\n", bits, count)))
+            .collect(),
+    }
+}
+
 fn print_banner() {
     println!("{}", BANNER);
     println!("Quantum Computing Framework");
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!();
-    println!("  homaya run <file>     Run a quantum circuit");
+    println!("  homaya run <file>     Run an OpenQASM 2.0 or JSON circuit");
+    println!("  homaya simulate <f>   Print a histogram as text, JSON or CSV");
+    println!("  homaya draw <file>    Draw a circuit as text or SVG");
+    println!("  homaya info <file>    Summarize and lint a circuit");
+    println!("  homaya repl           Build a circuit interactively");
+    println!("  homaya grover         Run Grover's search");
+    println!("  homaya gen <alg>      Print a grover/bv/dj circuit");
     println!("  homaya gates          List available gates");
     println!("  homaya verify         Verify simulator correctness");
     println!("  homaya version        Show version info");
@@ -131,3 +455,55 @@ fn print_gates() {
     println!("Learn how to use these → https://bskiller.com");
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grover_params(args: &[&str]) -> Params {
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Some(Commands::Grover { params, params_file }) => params.resolve(params_file.as_deref()).unwrap(),
+            _ => panic!("expected the grover subcommand"),
+        }
+    }
+
+    #[test]
+    fn test_params_file_matches_flags() {
+        let path = std::env::temp_dir().join(format!("homaya-cli-{}.toml", std::process::id()));
+        std::fs::write(&path, "qubits = 3\ntarget = 6\niterations = 2\nshots = 300\nseed = 9\n").unwrap();
+
+        let from_file = grover_params(&["homaya", "grover", "--params", path.to_str().unwrap()]);
+        let from_flags = grover_params(&[
+            "homaya", "grover", "--qubits", "3", "--target", "6", "--iterations", "2", "--shots", "300", "--seed", "9",
+        ]);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(from_file, from_flags);
+        assert_eq!(run_grover(&from_file).unwrap(), run_grover(&from_flags).unwrap());
+    }
+
+    #[test]
+    fn test_grover_requires_target() {
+        let params = grover_params(&["homaya", "grover", "--qubits", "3"]);
+        assert!(run_grover(&params).unwrap_err().contains("--target"));
+    }
+
+    #[test]
+    fn test_format_counts() {
+        let counts = HashMap::from([("11".to_string(), 7), ("00".to_string(), 5)]);
+
+        assert_eq!(format_counts(&counts, Format::Text), "00       5\n11       7\n");
+        assert_eq!(format_counts(&counts, Format::Json), "{\"00\":5,\"11\":7}\n");
+        assert_eq!(format_counts(&counts, Format::Csv), "bitstring,count\n00,5\n11,7\n");
+    }
+
+    #[test]
+    fn test_grover_json_round_trip() {
+        let circuit = homaya_algorithms::GroverSearch::new(3, 5).build();
+        let parsed = Circuit::from_json(&circuit.to_json()).unwrap();
+
+        assert_eq!(parsed.instructions(), circuit.instructions());
+        assert_eq!(parsed.num_clbits(), circuit.num_clbits());
+    }
+}
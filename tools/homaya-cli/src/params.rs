@@ -0,0 +1,99 @@
+//! Experiment parameters from flags, a TOML file, or both.
+//!
+//! Every field is optional so that a params file can fill in whatever the
+//! command line leaves out. Flags always win over file values.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+/// Parameters shared by the simulation subcommands.
+///
+/// Parsed from flags by clap and from a TOML file by serde, e.g.
+///
+/// ```toml
+/// qubits = 4
+/// target = 13
+/// shots = 2000
+/// seed = 42
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, clap::Args, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Params {
+    /// Number of qubits
+    #[arg(short, long)]
+    pub qubits: Option<usize>,
+    /// Target basis state to search for
+    #[arg(short, long)]
+    pub target: Option<usize>,
+    /// Number of Grover iterations (default: optimal)
+    #[arg(short, long)]
+    pub iterations: Option<usize>,
+    /// Number of shots (default: 1000)
+    #[arg(short, long)]
+    pub shots: Option<usize>,
+    /// Random seed for reproducible sampling
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+impl Params {
+    /// Load parameters from a TOML file.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        toml::from_str(&text).map_err(|e| format!("invalid params file {}: {}", path.display(), e))
+    }
+
+    /// Fill every unset field from `fallback`.
+    pub fn or(self, fallback: Params) -> Params {
+        Params {
+            qubits: self.qubits.or(fallback.qubits),
+            target: self.target.or(fallback.target),
+            iterations: self.iterations.or(fallback.iterations),
+            shots: self.shots.or(fallback.shots),
+            seed: self.seed.or(fallback.seed),
+        }
+    }
+
+    /// Merge flags with an optional params file, flags taking precedence.
+    pub fn resolve(self, file: Option<&Path>) -> Result<Params, String> {
+        match file {
+            Some(path) => Ok(self.or(Params::load(path)?)),
+            None => Ok(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_params(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("homaya-{}-{}.toml", name, std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_flags_override_file() {
+        let path = write_params("override", "qubits = 3\ntarget = 5\nshots = 100\n");
+        let flags = Params {
+            shots: Some(500),
+            ..Params::default()
+        };
+
+        let params = flags.resolve(Some(&path)).unwrap();
+        assert_eq!(params.qubits, Some(3));
+        assert_eq!(params.target, Some(5));
+        assert_eq!(params.shots, Some(500));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_unknown_key_rejected() {
+        let path = write_params("unknown", "qubit = 3\n");
+        assert!(Params::load(&path).unwrap_err().contains("unknown field"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,263 @@
+//! Interactive circuit building for `homaya repl`.
+//!
+//! Each line is one command. Gates use their OpenQASM names with qubit
+//! numbers as operands (`h 0`, `cx 0 1`, `rx(pi/2) 2`) and are checked by
+//! the same parser as `.qasm` files, so every gate and angle expression
+//! `homaya run` accepts works here too. The register is sized to the
+//! highest qubit in use, never below `--qubits`, so `undo` can shrink it.
+
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use homaya_core::{format_outcome, Circuit, HomayaError};
+use homaya_sim::{SimConfig, Simulator, StateVector};
+
+const HELP: &str = "\
+Commands:
+  <gate> <qubits...>   apply a gate, e.g. `h 0`, `cx 0 1`, `rx(pi/2) 2`
+  measure <q> <c>      measure qubit q into classical bit c
+  reset <q>            reset qubit q to |0⟩
+  undo                 remove the last gate
+  reset                clear the circuit
+  run                  print the state, or a histogram once anything is measured
+  draw                 print the circuit diagram
+  qasm                 print the circuit as OpenQASM 2.0
+  help                 show this list
+  quit                 leave (end of input works too)
+";
+
+/// One accepted gate or measurement.
+struct Statement {
+    /// OpenQASM gate name with any parameters, e.g. `rx(pi/2)`
+    op: String,
+    /// Qubit operands
+    qubits: Vec<usize>,
+    /// Target bit, for `measure`
+    clbit: Option<usize>,
+}
+
+impl Statement {
+    /// The statement as an OpenQASM line.
+    fn to_qasm(&self) -> String {
+        let qubits: Vec<String> = self.qubits.iter().map(|q| format!("q[{}]", q)).collect();
+        match self.clbit {
+            Some(c) => format!("{} {} -> c[{}];\n", self.op, qubits.join(","), c),
+            None => format!("{} {};\n", self.op, qubits.join(",")),
+        }
+    }
+}
+
+/// A circuit being built one command at a time.
+pub struct Session {
+    /// Accepted statements, in order
+    statements: Vec<Statement>,
+    /// Register width before any gate asks for more
+    min_qubits: usize,
+    /// Shots for histograms
+    shots: usize,
+    /// Seed for histograms
+    seed: Option<u64>,
+}
+
+impl Session {
+    /// An empty session on at least `min_qubits` qubits.
+    pub fn new(min_qubits: usize, shots: usize, seed: Option<u64>) -> Self {
+        Self {
+            statements: Vec::new(),
+            min_qubits,
+            shots,
+            seed,
+        }
+    }
+
+    /// Run one command and return what it prints.
+    ///
+    /// Gates print nothing; a rejected command leaves the circuit as it was.
+    pub fn execute(&mut self, line: &str) -> Result<String, String> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(String::new());
+        }
+
+        match line {
+            "help" => Ok(HELP.to_string()),
+            "run" => self.run(),
+            "draw" => Ok(self.circuit()?.draw()),
+            "qasm" => Ok(self.circuit()?.to_qasm()),
+            "undo" => match self.statements.pop() {
+                Some(statement) => Ok(format!("removed {}", statement.to_qasm())),
+                None => Err("nothing to undo".to_string()),
+            },
+            "reset" => {
+                self.statements.clear();
+                Ok("circuit cleared\n".to_string())
+            }
+            _ => {
+                self.statements.push(parse_statement(line)?);
+                if let Err(message) = self.circuit() {
+                    self.statements.pop();
+                    return Err(message);
+                }
+                Ok(String::new())
+            }
+        }
+    }
+
+    /// The circuit so far.
+    fn circuit(&self) -> Result<Circuit, String> {
+        let qubits = self.statements.iter().flat_map(|s| &s.qubits).map(|&q| q + 1).max().unwrap_or(0);
+        let clbits = self.statements.iter().filter_map(|s| s.clbit).map(|c| c + 1).max().unwrap_or(0);
+
+        let mut source = format!("OPENQASM 2.0;\ninclude \"qelib1.inc\";\nqreg q[{}];\n", qubits.max(self.min_qubits));
+        if clbits > 0 {
+            source.push_str(&format!("creg c[{}];\n", clbits));
+        }
+        for statement in &self.statements {
+            source.push_str(&statement.to_qasm());
+        }
+
+        Circuit::from_qasm(&source).map_err(|err| match err {
+            // Only the newest statement can be at fault; its line number means nothing here
+            HomayaError::ParseError { message, .. } => message,
+            err => err.to_string(),
+        })
+    }
+
+    /// The final state, or a histogram once the circuit measures.
+    fn run(&self) -> Result<String, String> {
+        let circuit = self.circuit()?;
+        if circuit.num_clbits() > 0 {
+            let counts = Simulator::with_config(SimConfig { seed: self.seed, shots: self.shots })
+                .sample_configured(&circuit)
+                .map_err(|e| e.to_string())?;
+            return Ok(super::format_top_counts(&counts));
+        }
+
+        let state = Simulator::new().run(&circuit).map_err(|e| e.to_string())?;
+        let mut out = String::new();
+        for (i, amp) in state.amplitudes().iter().enumerate() {
+            if amp.norm_sqr() > 1e-12 {
+                out.push_str(&format!(
+                    "  |{}⟩  {:+.4}{:+.4}i  ({:.1}%)\n",
+                    format_outcome(i, circuit.num_qubits()),
+                    amp.re,
+                    amp.im,
+                    100.0 * amp.norm_sqr()
+                ));
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Parse `op q...` or `measure q c`.
+fn parse_statement(line: &str) -> Result<Statement, String> {
+    // Parameters may contain spaces, so they end at the last `)`
+    let split = match line.rfind(')') {
+        Some(close) => close + 1,
+        None => line.find(char::is_whitespace).unwrap_or(line.len()),
+    };
+    let (op, operands) = line.split_at(split);
+    let operands = operands
+        .split_whitespace()
+        .map(|operand| {
+            let index: usize = operand.parse().map_err(|_| format!("`{}` is not a qubit number", operand))?;
+            if index >= StateVector::MAX_QUBITS {
+                return Err(format!("index {} is too large (max: {})", index, StateVector::MAX_QUBITS - 1));
+            }
+            Ok(index)
+        })
+        .collect::<Result<Vec<usize>, String>>()?;
+
+    if operands.is_empty() {
+        return Err(format!("unknown command `{}`; type `help` for a list", line));
+    }
+    if op == "measure" {
+        return match *operands {
+            [q, c] => Ok(Statement {
+                op: op.to_string(),
+                qubits: vec![q],
+                clbit: Some(c),
+            }),
+            _ => Err("usage: measure <qubit> <clbit>".to_string()),
+        };
+    }
+    Ok(Statement {
+        op: op.to_string(),
+        qubits: operands,
+        clbit: None,
+    })
+}
+
+/// Read commands from stdin until `quit` or end of input.
+///
+/// The prompt and greeting only appear when stdin is a terminal, so piped
+/// scripts print nothing but command output.
+pub fn run(min_qubits: usize, shots: usize, seed: Option<u64>) -> io::Result<()> {
+    let stdin = io::stdin();
+    let interactive = stdin.is_terminal();
+    if interactive {
+        println!("HOMAYA REPL - type `help` for commands, `quit` to leave");
+    }
+
+    let mut session = Session::new(min_qubits, shots, seed);
+    let mut line = String::new();
+    loop {
+        if interactive {
+            print!("homaya> ");
+            io::stdout().flush()?;
+        }
+        line.clear();
+        if stdin.lock().read_line(&mut line)? == 0 || matches!(line.trim(), "quit" | "exit") {
+            return Ok(());
+        }
+        match session.execute(&line) {
+            Ok(output) => print!("{}", output),
+            Err(message) => eprintln!("error: {}", message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(lines: &[&str]) -> Session {
+        let mut session = Session::new(1, 100, Some(5));
+        for line in lines {
+            session.execute(line).unwrap();
+        }
+        session
+    }
+
+    #[test]
+    fn test_register_grows_to_fit() {
+        let mut session = session(&["h 0", "cx 0 2", "rx(pi / 2) 1"]);
+        let qasm = session.execute("qasm").unwrap();
+        // Angles come back evaluated
+        assert!(qasm.contains("qreg q[3];\nh q[0];\ncx q[0],q[2];\nrx(1.5707963267948966) q[1];\n"), "{}", qasm);
+
+        session.execute("undo").unwrap();
+        session.execute("undo").unwrap();
+        assert!(session.execute("qasm").unwrap().contains("qreg q[1];\nh q[0];\n"));
+    }
+
+    #[test]
+    fn test_rejected_command_leaves_circuit() {
+        let mut session = session(&["x 0"]);
+        assert!(session.execute("cx 0 0").unwrap_err().contains("same qubit more than once"));
+        assert!(session.execute("foo 1").unwrap_err().contains("unsupported gate `foo`"));
+        assert!(session.execute("h zero").unwrap_err().contains("not a qubit number"));
+        assert!(session.execute("measure 0").unwrap_err().contains("usage"));
+        assert!(session.execute("frobnicate").unwrap_err().contains("unknown command"));
+        assert_eq!(session.execute("run").unwrap(), "  |1⟩  +1.0000+0.0000i  (100.0%)\n");
+    }
+
+    #[test]
+    fn test_reset_clears_but_reset_gate_applies() {
+        let mut session = session(&["x 0", "reset 0"]);
+        assert!(session.execute("qasm").unwrap().contains("reset q[0];"));
+        session.execute("reset").unwrap();
+        assert!(session.execute("qasm").unwrap().ends_with("qreg q[1];\n"));
+        assert_eq!(session.execute("undo").unwrap_err(), "nothing to undo");
+    }
+}
@@ -0,0 +1,24 @@
+// Exercises every gate `homaya run` is documented to support.
+// The result is deterministic: every shot measures 110.
+OPENQASM 2.0;
+include "qelib1.inc";
+
+qreg q[3];
+creg c[3];
+
+x q[0];
+
+// H Z H = X, so q[1] ends in |1⟩
+h q[1];
+z q[1];
+h q[1];
+
+// Both controls set: q[2] flips to |1⟩
+ccx q[0],q[1],q[2];
+
+// q[2] is |1⟩: q[0] flips back to |0⟩
+cx q[2],q[0];
+
+measure q[0] -> c[0];
+measure q[1] -> c[1];
+measure q[2] -> c[2];
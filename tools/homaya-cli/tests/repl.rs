@@ -0,0 +1,62 @@
+//! End-to-end tests for `homaya repl`, driven by piped scripts.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Feed `script` to `homaya repl` and return (stdout, stderr).
+fn repl(args: &[&str], script: &str) -> (String, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_homaya"))
+        .arg("repl")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(script.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    (String::from_utf8(output.stdout).unwrap(), String::from_utf8(output.stderr).unwrap())
+}
+
+#[test]
+fn test_bell_pair_histogram() {
+    let (stdout, stderr) = repl(
+        &["--shots", "400", "--seed", "3"],
+        "h 0\ncx 0 1\nmeasure 0 0\nmeasure 1 1\nrun\n",
+    );
+
+    assert!(stderr.is_empty(), "{}", stderr);
+    let mut outcomes: Vec<&str> = stdout.lines().filter_map(|line| line.split_whitespace().next()).collect();
+    outcomes.sort();
+    assert_eq!(outcomes, ["00", "11"], "{}", stdout);
+}
+
+#[test]
+fn test_undo_then_print_state() {
+    // The H is undone, so qubit 1 stays |0⟩ and the state is |01⟩
+    let (stdout, _) = repl(&["--qubits", "2"], "x 0\nh 1\nundo\nrun\n");
+
+    assert!(stdout.starts_with("removed h q[1];\n"), "{}", stdout);
+    assert!(stdout.ends_with("  |01⟩  +1.0000+0.0000i  (100.0%)\n"), "{}", stdout);
+}
+
+#[test]
+fn test_errors_do_not_end_session() {
+    let (stdout, stderr) = repl(&[], "h 0\ncx 1 1\nfoo 0\nqasm\nquit\nx 0\n");
+
+    assert!(stderr.contains("error: `cx` acts on the same qubit more than once"), "{}", stderr);
+    assert!(stderr.contains("error: unsupported gate `foo`"), "{}", stderr);
+    // The bad commands were dropped, and nothing runs after `quit`
+    assert!(stdout.ends_with("qreg q[1];\nh q[0];\n"), "{}", stdout);
+}
+
+#[test]
+fn test_reset_and_draw() {
+    let (stdout, _) = repl(&["--qubits", "2"], "x 1\nreset\nh 0\ndraw\n");
+
+    assert!(stdout.starts_with("circuit cleared\n"), "{}", stdout);
+    assert!(stdout.contains("q0: ─H─"), "{}", stdout);
+    assert!(stdout.contains("q1:"), "{}", stdout);
+}
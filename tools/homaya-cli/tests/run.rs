@@ -0,0 +1,162 @@
+//! End-to-end tests for the file-based subcommands.
+
+use std::process::Command;
+
+fn homaya_run(fixture: &str) -> std::process::Output {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), fixture);
+    Command::new(env!("CARGO_BIN_EXE_homaya"))
+        .args(["run", &path, "--shots", "200", "--seed", "7"])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_run_prints_histogram() {
+    let output = homaya_run("all_gates.qasm");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.contains("3 qubits, 9 gates, 200 shots"), "{}", stdout);
+    assert!(stdout.contains("110     200  (100.0%)"), "{}", stdout);
+}
+
+#[test]
+fn test_run_accepts_json() {
+    let output = homaya_run("bell.json");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.contains("2 qubits, 4 gates, 200 shots"), "{}", stdout);
+    let mut outcomes: Vec<&str> = stdout.lines().skip(1).filter_map(|line| line.split_whitespace().next()).collect();
+    outcomes.sort();
+    assert_eq!(outcomes, ["00", "11"], "{}", stdout);
+}
+
+#[test]
+fn test_run_reports_unsupported_gate_line() {
+    let output = homaya_run("unsupported.qasm");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("line 5: unsupported gate `sx`"), "{}", stderr);
+}
+
+#[test]
+fn test_simulate_bell_formats() {
+    let path = format!("{}/tests/fixtures/bell.qasm", env!("CARGO_MANIFEST_DIR"));
+    let simulate = |format: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_homaya"))
+            .args(["simulate", &path, "--shots", "400", "--seed", "11", "--format", format])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    // Only 00 and 11 appear, each close to half the shots
+    let csv = simulate("csv");
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("bitstring,count"));
+    let rows: Vec<(&str, usize)> = lines
+        .map(|line| {
+            let (bits, count) = line.split_once(',').unwrap();
+            (bits, count.parse().unwrap())
+        })
+        .collect();
+    assert_eq!(rows.iter().map(|&(bits, _)| bits).collect::<Vec<_>>(), ["00", "11"], "{}", csv);
+    assert_eq!(rows.iter().map(|&(_, count)| count).sum::<usize>(), 400);
+    assert!(rows.iter().all(|&(_, count)| count > 150), "{}", csv);
+
+    // Same seed, same histogram in every format
+    let json = simulate("json");
+    assert_eq!(json, format!("{{\"00\":{},\"11\":{}}}\n", rows[0].1, rows[1].1));
+    assert!(simulate("text").starts_with(&format!("00  {:>6}\n", rows[0].1)));
+}
+
+#[test]
+fn test_gen_output_runs() {
+    let gen = |args: &[&str]| Command::new(env!("CARGO_BIN_EXE_homaya")).arg("gen").args(args).output().unwrap();
+
+    let output = gen(&["bv", "--qubits", "4", "--secret", "1010"]);
+    let qasm = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(qasm.starts_with("OPENQASM 2.0;"), "{}", qasm);
+    assert!(qasm.contains("cx q[1],q[4];\ncx q[3],q[4];\n"), "{}", qasm);
+
+    // JSON output loads back through `homaya run`
+    let output = gen(&["grover", "--qubits", "3", "--target", "5", "--format", "json"]);
+    assert!(output.status.success());
+    let path = std::env::temp_dir().join(format!("homaya-gen-{}.json", std::process::id()));
+    std::fs::write(&path, &output.stdout).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_homaya"))
+        .args(["simulate", path.to_str().unwrap(), "--shots", "100", "--seed", "7"])
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("101"), "{}", stdout);
+
+    let output = gen(&["dj", "--qubits", "3", "--function", "majority"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_draw_prints_diagram() {
+    let path = format!("{}/tests/fixtures/all_gates.qasm", env!("CARGO_MANIFEST_DIR"));
+    let output = Command::new(env!("CARGO_BIN_EXE_homaya")).args(["draw", &path]).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.starts_with("q0: ─X─"), "{}", stdout);
+    assert!(stdout.contains("⊕"), "{}", stdout);
+}
+
+#[test]
+fn test_draw_svg() {
+    let path = format!("{}/tests/fixtures/bell.qasm", env!("CARGO_MANIFEST_DIR"));
+    let output = Command::new(env!("CARGO_BIN_EXE_homaya"))
+        .args(["draw", &path, "--format", "svg"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.starts_with("<svg "), "{}", stdout);
+    assert_eq!(stdout.matches("class=\"connector\"").count(), 1, "{}", stdout);
+}
+
+#[test]
+fn test_info_tallies_grover_gates() {
+    let path = format!("{}/tests/fixtures/grover.qasm", env!("CARGO_MANIFEST_DIR"));
+    let output = Command::new(env!("CARGO_BIN_EXE_homaya")).args(["info", &path]).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("Qubits:  3"), "{}", stdout);
+    assert!(stdout.contains("  CCX        4"), "{}", stdout);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_homaya"))
+        .args(["info", &path, "--format", "json"])
+        .output()
+        .unwrap();
+    let info: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(info["qubits"], 3);
+    assert_eq!(info["gate_counts"]["CCX"], 4);
+    assert_eq!(info["two_qubit_gates"], 0);
+    assert!(info["lint"].as_array().unwrap().is_empty(), "{}", info);
+}
+
+#[test]
+fn test_info_prints_lint_warnings() {
+    let path = format!("{}/tests/fixtures/lint.qasm", env!("CARGO_MANIFEST_DIR"));
+    let output = Command::new(env!("CARGO_BIN_EXE_homaya")).args(["info", &path]).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.contains("Qubits:  3"), "{}", stdout);
+    assert!(stdout.contains("warning: instruction 2 acts on qubit 0 after it was measured"), "{}", stdout);
+    assert!(stdout.contains("warning: qubit 2 is never used"), "{}", stdout);
+}